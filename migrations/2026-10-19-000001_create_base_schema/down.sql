@@ -0,0 +1,2 @@
+-- ludium backend 소유 테이블은 공유 DB 에서 함께 쓰이므로 되돌리지 않는다
+SELECT 1;
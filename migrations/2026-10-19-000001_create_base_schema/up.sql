@@ -1,6 +1,7 @@
 ---- Base app schema
+-- tb_ldm_usr, mission, mission_submit 는 ludium backend 소유 테이블. 공유 DB 에서는 이미 존재하므로 IF NOT EXISTS
 
-CREATE TABLE public.tb_ldm_usr (
+CREATE TABLE IF NOT EXISTS public.tb_ldm_usr (
     id uuid NOT NULL, -- 아이디
     nick varchar(30) NOT NULL DEFAULT ''::character varying, -- 닉네임
     self_intro varchar(100) NOT NULL DEFAULT ''::character varying, -- 자기소개
@@ -8,7 +9,7 @@ CREATE TABLE public.tb_ldm_usr (
     CONSTRAINT tb_ldm_usr_pk PRIMARY KEY (id)
 );
 
-CREATE TABLE public.tb_ldm_usr_rgh (
+CREATE TABLE IF NOT EXISTS public.tb_ldm_usr_rgh (
     id uuid NOT NULL, -- 아이디
     is_crt bool NOT NULL DEFAULT false, -- 컨트리뷰터 여부
     is_prv bool NOT NULL DEFAULT false, -- 프로바이더 여부
@@ -17,7 +18,7 @@ CREATE TABLE public.tb_ldm_usr_rgh (
     CONSTRAINT usr_rgh_fk FOREIGN KEY (id) REFERENCES public.tb_ldm_usr(id)
 );
 
-CREATE TABLE public.mission (
+CREATE TABLE IF NOT EXISTS public.mission (
 	mission_id uuid NOT NULL,
 	curriculum_id uuid NOT NULL,
 	title varchar(255) NOT NULL,
@@ -29,7 +30,7 @@ CREATE TABLE public.mission (
 	CONSTRAINT mission_tb_ldm_usr_fk FOREIGN KEY (usr_id) REFERENCES public.tb_ldm_usr(id)
 );
 
-CREATE TABLE public.mission_submit (
+CREATE TABLE IF NOT EXISTS public.mission_submit (
 	mission_id uuid NOT NULL,
 	usr_id uuid NOT NULL,
 	description text NOT NULL,
@@ -38,4 +39,4 @@ CREATE TABLE public.mission_submit (
 	CONSTRAINT mission_submit_pkey PRIMARY KEY (mission_id, usr_id),
 	CONSTRAINT mission_submit_mission_id_fkey FOREIGN KEY (mission_id) REFERENCES public.mission(mission_id),
 	CONSTRAINT mission_submit_usr_id_fkey FOREIGN KEY (usr_id) REFERENCES public.tb_ldm_usr(id)
-);
\ No newline at end of file
+);
@@ -0,0 +1,4 @@
+DROP TABLE public.coin_network;
+DROP TABLE public.network;
+DROP TABLE public.coin;
+DROP TYPE coin_type;
@@ -0,0 +1,3 @@
+DROP TABLE public.reward_claim_detail;
+DROP TABLE public.reward_claim;
+DROP TYPE reward_claim_status;
@@ -1,5 +1,5 @@
-
-CREATE TABLE public.detailed_posting (
+-- ludium backend 소유 테이블. 공유 DB 에서는 이미 존재하므로 IF NOT EXISTS
+CREATE TABLE IF NOT EXISTS public.detailed_posting (
     detail_id uuid NOT NULL,
     posting_id uuid NOT NULL,
     title varchar(255),
@@ -0,0 +1,10 @@
+ALTER TABLE public.reward_claim DROP CONSTRAINT unique_resource_user;
+
+ALTER TABLE public.reward_claim ADD COLUMN mission_id uuid NOT NULL DEFAULT '00000000-0000-0000-0000-000000000000';
+UPDATE public.reward_claim SET mission_id = resource_id WHERE resource_type = 'MISSION';
+ALTER TABLE public.reward_claim ALTER COLUMN mission_id DROP DEFAULT;
+
+ALTER TABLE public.reward_claim DROP COLUMN resource_type, DROP COLUMN resource_id;
+DROP TYPE resource_type;
+
+ALTER TABLE public.reward_claim ADD CONSTRAINT unique_mission_user UNIQUE (mission_id, user_id);
@@ -0,0 +1,2 @@
+DROP TABLE public.outbox_event;
+DROP TYPE outbox_event_status;
@@ -0,0 +1,19 @@
+CREATE TYPE outbox_event_status AS ENUM ('PENDING', 'DELIVERED', 'DEAD_LETTER');
+
+CREATE TABLE public.outbox_event (
+    id uuid NOT NULL,
+    aggregate_type varchar(50) NOT NULL, -- ex) REWARD_CLAIM
+    aggregate_id uuid NOT NULL,
+    event_type varchar(50) NOT NULL, -- ex) claim.created, claim.approved
+    payload jsonb NOT NULL,
+    status outbox_event_status NOT NULL DEFAULT 'PENDING',
+    attempts int NOT NULL DEFAULT 0, -- 전송 시도 횟수
+    next_attempt_date timestamp NOT NULL DEFAULT NOW(), -- 다음 전송 시도 시각
+    last_error text,
+    delivered_date timestamp,
+    created_date timestamp NOT NULL DEFAULT NOW(),
+    updated_date timestamp NOT NULL DEFAULT NOW(),
+    CONSTRAINT outbox_event_pk PRIMARY KEY (id)
+);
+
+CREATE INDEX outbox_event_pending_idx ON public.outbox_event (next_attempt_date) WHERE status = 'PENDING';
@@ -0,0 +1,16 @@
+CREATE TABLE public.webhook_delivery (
+    id uuid NOT NULL,
+    event_id uuid NOT NULL,
+    endpoint_name varchar(50) NOT NULL,
+    endpoint_url varchar(255) NOT NULL,
+    attempt int NOT NULL, -- outbox_event.attempts 기준 시도 번호
+    success boolean NOT NULL,
+    status_code int, -- 응답 http status (전송 실패시 NULL)
+    error text,
+    duration_ms int NOT NULL,
+    created_date timestamp NOT NULL DEFAULT NOW(),
+    CONSTRAINT webhook_delivery_pk PRIMARY KEY (id),
+    CONSTRAINT webhook_delivery_fk_outbox_event FOREIGN KEY (event_id) REFERENCES public.outbox_event (id)
+);
+
+CREATE INDEX webhook_delivery_event_idx ON public.webhook_delivery (event_id);
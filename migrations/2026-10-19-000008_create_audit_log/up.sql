@@ -0,0 +1,26 @@
+CREATE TABLE public.audit_log (
+    id uuid NOT NULL,
+    request_id uuid, -- response::mapper 의 request uuid (시스템 작업은 NULL)
+    actor_id varchar(100) NOT NULL, -- user id or SYSTEM
+    actor_type varchar(20) NOT NULL, -- ex) USER, ADMIN, SYSTEM
+    action varchar(100) NOT NULL, -- ex) reward_claim.update_status
+    entity_type varchar(50) NOT NULL, -- ex) REWARD_CLAIM
+    entity_id varchar(100) NOT NULL,
+    before_value jsonb,
+    after_value jsonb,
+    created_date timestamp NOT NULL DEFAULT NOW(),
+    CONSTRAINT audit_log_pk PRIMARY KEY (id)
+);
+
+CREATE INDEX audit_log_entity_idx ON public.audit_log (entity_type, entity_id);
+CREATE INDEX audit_log_created_date_idx ON public.audit_log (created_date);
+
+-- append-only: UPDATE, DELETE 는 에러
+CREATE FUNCTION public.audit_log_append_only() RETURNS trigger AS $$
+BEGIN
+    RAISE EXCEPTION 'audit_log is append-only, % is not allowed', TG_OP;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER audit_log_append_only BEFORE UPDATE OR DELETE ON public.audit_log
+    FOR EACH ROW EXECUTE FUNCTION public.audit_log_append_only();
@@ -0,0 +1,16 @@
+CREATE TABLE public.api_key (
+    id uuid NOT NULL,
+    name varchar(100) NOT NULL, -- ex) ludium-backend
+    key_prefix varchar(16) NOT NULL, -- 키 조회용 prefix (lpk_<key_prefix>_<secret>)
+    key_hash varchar(64) NOT NULL, -- sha256 hex, 원문 키는 저장하지 않음
+    scopes text[] NOT NULL, -- ex) {claims:write, reports:read, admin}
+    expires_date timestamp,
+    revoked_date timestamp,
+    last_used_date timestamp,
+    rotated_from uuid, -- rotate 로 발급된 경우 이전 키
+    created_by varchar(100) NOT NULL,
+    created_date timestamp NOT NULL DEFAULT NOW(),
+    updated_date timestamp NOT NULL DEFAULT NOW(),
+    CONSTRAINT api_key_pk PRIMARY KEY (id),
+    CONSTRAINT api_key_prefix_unique UNIQUE (key_prefix)
+);
@@ -0,0 +1,7 @@
+-- rate_limit.backend = "postgres" 일 때 인스턴스간 공유하는 token bucket
+CREATE TABLE public.rate_limit_bucket (
+    bucket_key varchar(200) NOT NULL, -- ex) payout:user:<user_id>, payout:ip:<ip>
+    tokens double precision NOT NULL,
+    updated_date timestamp NOT NULL DEFAULT NOW(),
+    CONSTRAINT rate_limit_bucket_pk PRIMARY KEY (bucket_key)
+);
@@ -0,0 +1,3 @@
+ALTER TABLE public.coin_network DROP COLUMN is_active;
+ALTER TABLE public.network DROP COLUMN is_active;
+ALTER TABLE public.coin DROP COLUMN is_active;
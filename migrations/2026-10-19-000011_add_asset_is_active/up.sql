@@ -0,0 +1,4 @@
+-- admin api 로 비활성화된 coin / network / coin_network 는 is_active = false
+ALTER TABLE public.coin ADD COLUMN is_active boolean NOT NULL DEFAULT true;
+ALTER TABLE public.network ADD COLUMN is_active boolean NOT NULL DEFAULT true;
+ALTER TABLE public.coin_network ADD COLUMN is_active boolean NOT NULL DEFAULT true;
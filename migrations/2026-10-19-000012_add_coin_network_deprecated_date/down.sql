@@ -0,0 +1 @@
+ALTER TABLE public.coin_network DROP COLUMN deprecated_date;
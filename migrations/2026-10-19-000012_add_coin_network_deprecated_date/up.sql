@@ -0,0 +1,2 @@
+-- soft delete. deprecated_date 가 있으면 다시 활성화할 수 없고, 기존 reward_claim 은 그대로 조회된다
+ALTER TABLE public.coin_network ADD COLUMN deprecated_date timestamp;
@@ -0,0 +1,2 @@
+ALTER TABLE public.coin_network DROP COLUMN metadata_checked_date;
+ALTER TABLE public.coin_network DROP COLUMN metadata_mismatch;
@@ -0,0 +1,3 @@
+-- FT 컨트랙트 ft_metadata 와 coin (decimals, symbol) 비교 결과. metadata_mismatch 가 있으면 지급 불가
+ALTER TABLE public.coin_network ADD COLUMN metadata_mismatch varchar(300);
+ALTER TABLE public.coin_network ADD COLUMN metadata_checked_date timestamp;
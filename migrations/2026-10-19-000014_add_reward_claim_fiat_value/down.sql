@@ -0,0 +1,6 @@
+ALTER TABLE public.reward_claim DROP COLUMN priced_date;
+ALTER TABLE public.reward_claim DROP COLUMN price_source;
+ALTER TABLE public.reward_claim DROP COLUMN krw_value;
+ALTER TABLE public.reward_claim DROP COLUMN usd_value;
+ALTER TABLE public.reward_claim DROP COLUMN krw_rate;
+ALTER TABLE public.reward_claim DROP COLUMN usd_rate;
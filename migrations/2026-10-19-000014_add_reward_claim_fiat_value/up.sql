@@ -0,0 +1,7 @@
+-- 지급 승인 시점의 USD, KRW 환산 가치 스냅샷 (회계, 그랜트 리포트용). 가격 조회 실패 시 NULL
+ALTER TABLE public.reward_claim ADD COLUMN usd_rate numeric;
+ALTER TABLE public.reward_claim ADD COLUMN krw_rate numeric;
+ALTER TABLE public.reward_claim ADD COLUMN usd_value numeric;
+ALTER TABLE public.reward_claim ADD COLUMN krw_value numeric;
+ALTER TABLE public.reward_claim ADD COLUMN price_source varchar(50);
+ALTER TABLE public.reward_claim ADD COLUMN priced_date timestamp;
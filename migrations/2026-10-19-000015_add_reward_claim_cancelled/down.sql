@@ -0,0 +1,7 @@
+UPDATE public.reward_claim SET reward_claim_status = 'TRANSACTION_FAILED' WHERE reward_claim_status = 'CANCELLED';
+
+ALTER TYPE reward_claim_status RENAME TO reward_claim_status_old;
+CREATE TYPE reward_claim_status AS ENUM ('READY', 'TRANSACTION_APPROVED', 'TRANSACTION_FAILED');
+ALTER TABLE public.reward_claim
+    ALTER COLUMN reward_claim_status TYPE reward_claim_status USING reward_claim_status::text::reward_claim_status;
+DROP TYPE reward_claim_status_old;
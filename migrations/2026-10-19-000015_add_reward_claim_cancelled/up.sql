@@ -0,0 +1,2 @@
+-- payment-admin claims cancel 로 종료된 claim. 같은 resource 로 다시 청구할 수 없다
+ALTER TYPE reward_claim_status ADD VALUE IF NOT EXISTS 'CANCELLED';
@@ -0,0 +1 @@
+ALTER TABLE public.audit_log DROP COLUMN on_behalf_of;
@@ -0,0 +1 @@
+ALTER TABLE public.audit_log ADD COLUMN on_behalf_of varchar(100); -- api key 가 x-on-behalf-of 로 대신한 user id
@@ -0,0 +1 @@
+DROP INDEX public.rate_limit_bucket_updated_date_idx;
@@ -0,0 +1 @@
+CREATE INDEX rate_limit_bucket_updated_date_idx ON public.rate_limit_bucket (updated_date); -- 오래된 bucket 정리용
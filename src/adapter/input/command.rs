@@ -0,0 +1,63 @@
+use crate::adapter::output::persistence::db::{migration, postgres::PostgresDbManager};
+use crate::config::Config;
+use super::error::Result;
+
+pub const MIGRATE_USAGE: &str = "usage: payment migrate [run | status | baseline <version>]";
+
+/// Arguments after `migrate`, `None` when the binary should serve.
+/// Anything before `migrate` (e.g. `--config config.toml` of the docker entrypoint) is ignored.
+pub fn migrate_args(args: &[String]) -> Option<&[String]> {
+    args.iter().position(|arg| arg == "migrate").map(|index| &args[index + 1..])
+}
+
+/// `payment migrate` applies pending migrations and exits.
+/// `baseline <version>` marks migrations as applied on databases that were built from `scripts/dev_initial`.
+pub async fn run_migrate_command(config: &Config, args: &[String]) -> Result<()> {
+    let db_manager = PostgresDbManager::new(config.db_url(), 1).await?;
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    match args.as_slice() {
+        [] | ["run"] => {
+            let versions = migration::run_pending_migrations(&db_manager).await?;
+            if versions.is_empty() {
+                println!("database is up to date");
+            }
+            for version in versions {
+                println!("applied {}", version);
+            }
+        }
+        ["status"] => {
+            for status in migration::migration_status(&db_manager).await? {
+                println!("[{}] {}", if status.applied { "x" } else { " " }, status.name);
+            }
+        }
+        ["baseline", version] => {
+            for version in migration::baseline_migrations(&db_manager, version.to_string()).await? {
+                println!("marked {} as applied", version);
+            }
+        }
+        _ => {
+            eprintln!("{}", MIGRATE_USAGE);
+            std::process::exit(2);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::migrate_args;
+
+    #[test]
+    fn test_migrate_args() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<String>>();
+
+        assert_eq!(migrate_args(&args(&["payment"])), None);
+        assert_eq!(migrate_args(&args(&["payment", "--config", "config.toml"])), None);
+        assert_eq!(migrate_args(&args(&["payment", "migrate"])), Some(&[][..]));
+        assert_eq!(
+            migrate_args(&args(&["payment", "--config", "config.toml", "migrate", "baseline", "2026-10-19-000014"])),
+            Some(&args(&["baseline", "2026-10-19-000014"])[..]),
+        );
+    }
+}
@@ -1,7 +1,31 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::domain::model::api_key::ApiKeyScope;
+use crate::domain::model::audit_log::{AuditActor, ACTOR_TYPE_ADMIN, ACTOR_TYPE_API_KEY, ACTOR_TYPE_USER};
+use crate::domain::model::user::AuthClaims;
 
+/// Taken from the caller or generated once per request by `mw_request_id`, echoed and logged
+/// by `response::mapper`, recorded on the request span and stored on audit rows so all can be correlated.
+#[derive(Clone, Copy, Debug)]
+pub struct RequestId(pub Uuid);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Role {
+    Admin,
+    Provider,
+    Contributor,
+}
+
+impl core::fmt::Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Role::Admin => write!(f, "ADMIN"),
+            Role::Provider => write!(f, "PROVIDER"),
+            Role::Contributor => write!(f, "CONTRIBUTOR"),
+        }
+    }
+}
 
-#[allow(unused)] // authentication code will be added later
 #[derive(Debug, Clone, Deserialize)]
 pub struct UserInfo {
     id: String,
@@ -10,11 +34,46 @@ pub struct UserInfo {
     crt: bool,
 }
 
+impl From<AuthClaims> for UserInfo {
+    fn from(claims: AuthClaims) -> Self {
+        Self {
+            id: claims.sub,
+            adm: claims.adm,
+            prv: claims.prv,
+            crt: claims.crt,
+        }
+    }
+}
+
 impl UserInfo {
+    pub fn new(id: String, adm: bool, prv: bool, crt: bool) -> Self {
+        Self { id, adm, prv, crt }
+    }
+
     pub fn user_id(&self) -> &str {
         &self.id
     }
 
+    pub fn is_admin(&self) -> bool {
+        self.adm
+    }
+
+    pub fn has_role(&self, role: Role) -> bool {
+        match role {
+            Role::Admin => self.adm,
+            Role::Provider => self.prv,
+            Role::Contributor => self.crt,
+        }
+    }
+}
+
+/// The calling service when a request authenticated with `x-api-key` instead of cookies.
+#[derive(Clone, Debug)]
+pub struct ApiKeyPrincipal {
+    pub key_id: Uuid,
+    pub scopes: Vec<ApiKeyScope>,
+    /// user id from `x-on-behalf-of`, only accepted with the `users:act_as` scope
+    pub on_behalf_of: Option<String>,
 }
 
 #[derive(Clone, Debug)]
@@ -22,14 +81,49 @@ pub struct Ctx {
     user_info: UserInfo,
     access_token: String,
     ggl_id: String,
+    request_id: Uuid,
+    api_key: Option<ApiKeyPrincipal>,
 }
 
 impl Ctx {
-    pub fn new(user_info: UserInfo, access_token: String, ggl_id: String) -> Self {
+    pub fn new(user_info: UserInfo, access_token: String, ggl_id: String, request_id: Uuid) -> Self {
         Self {
             user_info,
             access_token,
             ggl_id,
+            request_id,
+            api_key: None,
+        }
+    }
+
+    /// `user_info` is the user the service acts on behalf of (`x-on-behalf-of`), or the key itself.
+    pub fn from_api_key(user_info: UserInfo, api_key: ApiKeyPrincipal, request_id: Uuid) -> Self {
+        Self {
+            user_info,
+            access_token: String::new(),
+            ggl_id: String::new(),
+            request_id,
+            api_key: Some(api_key),
+        }
+    }
+
+    pub fn api_key_id(&self) -> Option<Uuid> {
+        self.api_key.as_ref().map(|api_key| api_key.key_id)
+    }
+
+    /// Scopes only restrict api keys, cookie sessions are limited by their roles.
+    pub fn has_scope(&self, scope: ApiKeyScope) -> bool {
+        self.api_key.as_ref().map_or(true, |api_key| api_key.scopes.contains(&scope))
+    }
+
+    pub fn audit_actor(&self) -> AuditActor {
+        match &self.api_key {
+            Some(api_key) => AuditActor::new(api_key.key_id.to_string(), ACTOR_TYPE_API_KEY, Some(self.request_id))
+                .on_behalf_of(api_key.on_behalf_of.clone()),
+            None => {
+                let actor_type = if self.user_info.is_admin() { ACTOR_TYPE_ADMIN } else { ACTOR_TYPE_USER };
+                AuditActor::new(self.user_info.user_id(), actor_type, Some(self.request_id))
+            }
         }
     }
 
@@ -38,6 +132,6 @@ impl Ctx {
     }
 
     pub fn is_authenticated(&self) -> bool {
-        !self.access_token.is_empty() && !self.ggl_id.is_empty()
+        self.api_key.is_some() || (!self.access_token.is_empty() && !self.ggl_id.is_empty())
     }
 }
@@ -4,7 +4,7 @@ use axum::{http::StatusCode, response::{IntoResponse, Response}};
 use serde::Serialize;
 use serde_with::serde_as;
 
-use crate::{adapter::output::{self, near, persistence::db}, usecase};
+use crate::{adapter::{input::ctx::Role, output::{self, auth, near, persistence::db}}, domain::model::api_key::ApiKeyScope, usecase};
 
 pub type Result<T> = core::result::Result<T, Error>;
 
@@ -16,9 +16,14 @@ pub enum Error {
     AuthFailNoAuthInformation,
     AuthFailCtxNotInRequestExt,
 	Unauthorized { message: String },
+	Forbidden { required_role: Role },
+	AuthFailTokenInvalid(auth::error::Error),
+	AuthFailApiKeyInvalid,
+	MissingScope { scope: ApiKeyScope },
 
 	// -- Request Params
 	UUIDParsingError { message: String },
+	TooManyRequests { retry_after_secs: u64 },
 	
 	// -- Output
 	Postgres(db::error::Error),
@@ -67,12 +72,32 @@ impl Error {
 				StatusCode::FORBIDDEN,
 				message.to_string(),
 			),
+			Self::AuthFailTokenInvalid(_) => (
+				StatusCode::UNAUTHORIZED,
+				"Invalid Access Token".to_string(),
+			),
+			Self::AuthFailApiKeyInvalid => (
+				StatusCode::UNAUTHORIZED,
+				"Invalid Api Key".to_string(),
+			),
+			Self::MissingScope { scope } => (
+				StatusCode::FORBIDDEN,
+				format!("{} scope required", scope),
+			),
+			Self::Forbidden { required_role } => (
+				StatusCode::FORBIDDEN,
+				format!("{} role required", required_role),
+			),
 
 			// -- Request Params
 			Self::UUIDParsingError { message } => (
 				StatusCode::BAD_REQUEST,
 				message.to_string(),
 			),
+			Self::TooManyRequests { retry_after_secs } => (
+				StatusCode::TOO_MANY_REQUESTS,
+				format!("Too many requests, retry after {} seconds", retry_after_secs),
+			),
 			// -- Output
 			Self::Postgres(error) => error.client_status_and_error(),
 			Self::Near(error) => error.client_status_and_error(),
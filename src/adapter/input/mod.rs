@@ -5,6 +5,7 @@ use tower_http::services::ServeDir;
 pub mod web;
 pub mod ctx;
 pub mod error;
+pub mod command;
 
 
 pub fn routes_static() -> Router {
@@ -6,7 +6,7 @@ use serde_json::{json, Value};
 use tower_cookies::{Cookie, Cookies};
 use utoipa::ToSchema;
 
-use crate::web;
+use crate::adapter::input::web;
 use crate::adapter::input::error::{Error, Result};
 use std::env;
 use dotenvy::dotenv;
@@ -1,8 +1,10 @@
 use std::sync::Arc;
 
-use crate::adapter::input::ctx::{Ctx, UserInfo};
-use crate::web::{ACCESS_TOKEN, GOOGLE_ID};
-use crate::AppState;
+use crate::adapter::input::ctx::{ApiKeyPrincipal, Ctx, RequestId, UserInfo};
+use crate::domain::model::api_key::{ApiKey, ApiKeyScope};
+use crate::adapter::input::web::{ACCESS_TOKEN, API_KEY_HEADER, GOOGLE_ID, ON_BEHALF_OF_HEADER};
+use crate::state::AppState;
+use crate::port::output::token_verifier::TokenVerifier;
 use axum::async_trait;
 use axum::body::Body;
 use axum::extract::{FromRequestParts, State};
@@ -12,33 +14,43 @@ use axum::http::HeaderMap;
 use axum::middleware::Next;
 use axum::response::Response;
 use tower_cookies::{Cookie, Cookies};
+use uuid::Uuid;
 
 use crate::adapter::input::error::{Error, Result};
 
 pub async fn mw_ctx_resolver(
-	_state: State<Arc<AppState>>,
+	State(state): State<Arc<AppState>>,
 	cookies: Cookies,
 	mut req: Request<Body>,
 	next: Next,
 ) -> Result<Response> {
+	// --- set by `mw_request_id`, which runs first
+	let request_id = req.extensions().get::<RequestId>().map(|RequestId(id)| *id).unwrap_or_else(Uuid::new_v4);
+
+	// --- service to service calls
+	if let Some(raw_key) = req.headers().get(API_KEY_HEADER).and_then(|value| value.to_str().ok()).map(str::to_string) {
+		let on_behalf_of = req.headers().get(ON_BEHALF_OF_HEADER).and_then(|value| value.to_str().ok()).map(str::to_string);
+		let result_ctx = resolve_api_key_ctx(&state, &raw_key, on_behalf_of, request_id).await;
+		req.extensions_mut().insert(result_ctx);
+		req.extensions_mut().insert(RequestId(request_id));
+		return Ok(next.run(req).await);
+	}
+
 	let access_token = cookies.get(ACCESS_TOKEN).map(|c| c.value().to_string());
     let ggl_id = cookies.get(GOOGLE_ID).map(|c| c.value().to_string());
 
-    let headers: &HeaderMap = req.headers();
-	let user_info = headers.get("x-user-right")
-        .and_then(|value| {
-            value.to_str().ok().map(|s| {
-                serde_json::from_str::<UserInfo>(s).map_err(|e| {
-                    tracing::error!("Failed to parse x-user-right: {}", e);
-                    e
-                }).ok()
-            }).flatten()
-        });
+	let user_info = match (&state.token_verifier, &access_token) {
+		(Some(token_verifier), Some(access_token)) => verify_user_info(token_verifier.as_ref(), access_token).await.map(Some),
+		(Some(_), None) => Ok(None),
+		// --- local only (see AuthConfig::init)
+		(None, _) => Ok(parse_user_right_header(req.headers())),
+	};
 
 	let result_ctx = match (user_info, access_token, ggl_id) {
-        (Some(user_info), Some(access_token), Some(ggl_id)) => {
+		(Err(error), _, _) => Err(error),
+        (Ok(Some(user_info)), Some(access_token), Some(ggl_id)) => {
 			tracing::debug!("[middleware] mw_ctx_resolver - Ok");
-			Ok(Ctx::new(user_info, access_token, ggl_id))
+			Ok(Ctx::new(user_info, access_token, ggl_id, request_id))
         }
         _ => Err(Error::AuthFailNoAuthInformation),
     };
@@ -51,10 +63,63 @@ pub async fn mw_ctx_resolver(
 	}
 
 	req.extensions_mut().insert(result_ctx);
+	req.extensions_mut().insert(RequestId(request_id));
 
 	Ok(next.run(req).await)
 }
 
+async fn resolve_api_key_ctx(state: &AppState, raw_key: &str, on_behalf_of: Option<String>, request_id: Uuid) -> Result<Ctx> {
+	let api_key = state.api_key_usecase.authenticate(raw_key).await.map_err(|e| {
+		tracing::info!("Failed to authenticate api key: {}", e);
+		Error::AuthFailApiKeyInvalid
+	})?;
+
+	tracing::debug!("[middleware] mw_ctx_resolver - api key {}", api_key.id);
+	api_key_ctx(&api_key, on_behalf_of, request_id)
+}
+
+/// Acting for a user is only allowed to keys with the `users:act_as` scope, the user id ends up on every audit row.
+fn api_key_ctx(api_key: &ApiKey, on_behalf_of: Option<String>, request_id: Uuid) -> Result<Ctx> {
+	let scopes = api_key.scopes();
+	let on_behalf_of = on_behalf_of.map(|user_id| user_id.trim().to_string()).filter(|user_id| !user_id.is_empty());
+	if let Some(user_id) = &on_behalf_of {
+		if !scopes.contains(&ApiKeyScope::ActAsUser) {
+			tracing::warn!("[middleware] api key {} without {} sent {ON_BEHALF_OF_HEADER}", api_key.id, ApiKeyScope::ActAsUser);
+			return Err(Error::MissingScope { scope: ApiKeyScope::ActAsUser });
+		}
+		tracing::info!("[middleware] api key {} acts on behalf of user {}", api_key.id, user_id);
+	}
+
+	let user_info = UserInfo::new(
+		on_behalf_of.clone().unwrap_or_else(|| api_key.id.to_string()),
+		scopes.contains(&ApiKeyScope::Admin),
+		false,
+		false,
+	);
+	Ok(Ctx::from_api_key(user_info, ApiKeyPrincipal { key_id: api_key.id, scopes, on_behalf_of }, request_id))
+}
+
+async fn verify_user_info(token_verifier: &dyn TokenVerifier, access_token: &str) -> Result<UserInfo> {
+	token_verifier.verify(access_token).await
+		.map(UserInfo::from)
+		.map_err(|e| {
+			tracing::info!("Failed to verify access_token: {}", e);
+			Error::AuthFailTokenInvalid(e)
+		})
+}
+
+fn parse_user_right_header(headers: &HeaderMap) -> Option<UserInfo> {
+	headers.get("x-user-right")
+        .and_then(|value| {
+            value.to_str().ok().map(|s| {
+                serde_json::from_str::<UserInfo>(s).map_err(|e| {
+                    tracing::error!("Failed to parse x-user-right: {}", e);
+                    e
+                }).ok()
+            }).flatten()
+        })
+}
+
 // region:    --- Ctx Extractor
 #[async_trait]
 impl<S: Send + Sync> FromRequestParts<S> for Ctx {
@@ -70,3 +135,51 @@ impl<S: Send + Sync> FromRequestParts<S> for Ctx {
 }
 
 // endregion: --- Ctx Extractor
+
+#[cfg(test)]
+mod tests {
+	use crate::domain::model::api_key::{generate_api_key, hash_api_key};
+	use super::*;
+
+	fn api_key(scopes: &[ApiKeyScope]) -> ApiKey {
+		let (raw_key, key_prefix) = generate_api_key();
+		let now = chrono::Utc::now().naive_utc();
+		ApiKey {
+			id: Uuid::new_v4(),
+			name: "ludium-backend".to_string(),
+			key_prefix,
+			key_hash: hash_api_key(&raw_key),
+			scopes: scopes.iter().map(ToString::to_string).collect(),
+			expires_date: None,
+			revoked_date: None,
+			last_used_date: None,
+			rotated_from: None,
+			created_by: "admin".to_string(),
+			created_date: now,
+			updated_date: now,
+		}
+	}
+
+	#[test]
+	fn test_on_behalf_of_requires_scope() {
+		let api_key = api_key(&[ApiKeyScope::ClaimsWrite]);
+		let result = api_key_ctx(&api_key, Some("user-1".to_string()), Uuid::new_v4());
+		assert!(matches!(result, Err(Error::MissingScope { scope: ApiKeyScope::ActAsUser })));
+
+		// --- without the header the key acts as itself
+		let ctx = api_key_ctx(&api_key, None, Uuid::new_v4()).unwrap();
+		assert_eq!(ctx.user_info().user_id(), api_key.id.to_string());
+		assert_eq!(ctx.audit_actor().on_behalf_of, None);
+	}
+
+	#[test]
+	fn test_on_behalf_of_is_audited() {
+		let api_key = api_key(&[ApiKeyScope::ClaimsWrite, ApiKeyScope::ActAsUser]);
+		let ctx = api_key_ctx(&api_key, Some("user-1".to_string()), Uuid::new_v4()).unwrap();
+		assert_eq!(ctx.user_info().user_id(), "user-1");
+
+		let actor = ctx.audit_actor();
+		assert_eq!(actor.actor_id, api_key.id.to_string());
+		assert_eq!(actor.on_behalf_of.as_deref(), Some("user-1"));
+	}
+}
@@ -0,0 +1,30 @@
+use std::sync::Arc;
+use std::time::Instant;
+use axum::body::Body;
+use axum::extract::MatchedPath;
+use axum::http::Request;
+use axum::middleware::Next;
+use axum::response::Response;
+use crate::adapter::input::error::Error;
+use crate::config::metrics::metrics;
+
+/// Counts requests by matched route, so path parameters do not split the series.
+/// Added with `route_layer`, after routing and before `response::mapper`.
+pub async fn mw_track_metrics(req: Request<Body>, next: Next) -> Response {
+    let started = Instant::now();
+    let method = req.method().to_string();
+    let route = req.extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+
+    let response = next.run(req).await;
+
+    // --- errors are plain 500s until `response::mapper` turns them into their client status
+    let status = response.extensions()
+        .get::<Arc<Error>>()
+        .map(|error| error.client_status_and_error().0)
+        .unwrap_or(response.status());
+    metrics().observe_http(&method, &route, status.as_u16(), started.elapsed());
+    response
+}
@@ -1,3 +1,6 @@
 pub mod auth;
 pub mod response;
-pub mod permission;
\ No newline at end of file
+pub mod permission;
+pub mod rate_limit;
+pub mod metrics;
+pub mod request_id;
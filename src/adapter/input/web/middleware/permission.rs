@@ -1,8 +1,18 @@
+use std::convert::Infallible;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use axum::async_trait;
 use axum::body::Body;
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
 use axum::middleware::Next;
 use axum::http::Request;
-use axum::response::Response;
-use crate::adapter::input::ctx::Ctx;
+use axum::response::{IntoResponse, Response};
+use tower::{Layer, Service};
+use crate::adapter::input::ctx::{Ctx, Role};
+use crate::domain::model::api_key::ApiKeyScope;
 use crate::adapter::input::error::{Error, Result};
 
 pub async fn mw_require_auth(
@@ -21,3 +31,176 @@ pub async fn mw_require_auth(
         Err(_) => Err(Error::Unauthorized { message: "Unauthorized".to_string()}),
     }
 }
+
+#[derive(Clone, Copy, Debug)]
+enum Requirement {
+    Role(Role),
+    Scope(ApiKeyScope),
+}
+
+fn check(ctx: Option<&Result<Ctx>>, requirement: Requirement) -> Result<Ctx> {
+    let ctx = ctx.ok_or(Error::AuthFailCtxNotInRequestExt)?.clone()?;
+    match requirement {
+        Requirement::Role(role) if !ctx.user_info().has_role(role) => {
+            tracing::debug!("[permission] user {} does not have role {}", ctx.user_info().user_id(), role);
+            Err(Error::Forbidden { required_role: role })
+        }
+        Requirement::Scope(scope) if !ctx.has_scope(scope) => {
+            tracing::debug!("[permission] api key {:?} does not have scope {}", ctx.api_key_id(), scope);
+            Err(Error::MissingScope { scope })
+        }
+        _ => Ok(ctx),
+    }
+}
+
+// region:    --- Permission Layer
+
+/// Route layer rejecting requests whose user lacks `role`.
+///
+/// ```ignore
+/// Router::new()
+///     .route("/admin/...", get(handler))
+///     .route_layer(require_role(Role::Admin))
+/// ```
+pub fn require_role(role: Role) -> RequirePermissionLayer {
+    RequirePermissionLayer { requirement: Requirement::Role(role) }
+}
+
+/// Route layer rejecting api keys without `scope`. Cookie sessions pass through.
+pub fn require_scope(scope: ApiKeyScope) -> RequirePermissionLayer {
+    RequirePermissionLayer { requirement: Requirement::Scope(scope) }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct RequirePermissionLayer {
+    requirement: Requirement,
+}
+
+impl<S> Layer<S> for RequirePermissionLayer {
+    type Service = RequirePermission<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequirePermission { inner, requirement: self.requirement }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RequirePermission<S> {
+    inner: S,
+    requirement: Requirement,
+}
+
+impl<S> Service<Request<Body>> for RequirePermission<S>
+where
+    S: Service<Request<Body>, Response = Response, Error = Infallible> + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = core::result::Result<Response, Infallible>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<core::result::Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        match check(req.extensions().get::<Result<Ctx>>(), self.requirement) {
+            Ok(_) => Box::pin(self.inner.call(req)),
+            Err(error) => Box::pin(async move { Ok(error.into_response()) }),
+        }
+    }
+}
+
+// endregion: --- Permission Layer
+
+// region:    --- Role Extractor
+
+pub trait RequiredRole: Send + Sync {
+    const ROLE: Role;
+}
+
+pub struct AdminRole;
+
+impl RequiredRole for AdminRole {
+    const ROLE: Role = Role::Admin;
+}
+
+/// `Ctx` extractor that also checks a role, for handlers living next to routes with other roles.
+pub struct RoleCtx<R: RequiredRole>(pub Ctx, PhantomData<R>);
+
+pub type AdminCtx = RoleCtx<AdminRole>;
+
+#[async_trait]
+impl<S: Send + Sync, R: RequiredRole> FromRequestParts<S> for RoleCtx<R> {
+	type Rejection = Error;
+
+	async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self> {
+		let ctx = check(parts.extensions.get::<Result<Ctx>>(), Requirement::Role(R::ROLE))?;
+		Ok(RoleCtx(ctx, PhantomData))
+	}
+}
+
+// endregion: --- Role Extractor
+
+#[cfg(test)]
+mod tests {
+	use axum::http::StatusCode;
+	use axum::routing::get;
+	use axum::Router;
+	use std::sync::Arc;
+	use tower::ServiceExt;
+	use uuid::Uuid;
+	use crate::adapter::input::ctx::{ApiKeyPrincipal, UserInfo};
+	use super::*;
+
+	fn user_ctx(adm: bool) -> Ctx {
+		let user_info = UserInfo::new("user-1".to_string(), adm, false, false);
+		Ctx::new(user_info, "access-token".to_string(), "ggl-id".to_string(), Uuid::new_v4())
+	}
+
+	fn api_key_ctx(scopes: Vec<ApiKeyScope>) -> Ctx {
+		let user_info = UserInfo::new("service-1".to_string(), false, false, false);
+		Ctx::from_api_key(user_info, ApiKeyPrincipal { key_id: Uuid::new_v4(), scopes, on_behalf_of: None }, Uuid::new_v4())
+	}
+
+	/// Status the client gets, `response::mapper` maps the error left in the extensions.
+	async fn status(layer: RequirePermissionLayer, ctx: Option<Result<Ctx>>) -> StatusCode {
+		let router = Router::new().route("/", get(|| async { "ok" })).route_layer(layer);
+		let mut req = Request::builder().uri("/").body(Body::empty()).unwrap();
+		if let Some(ctx) = ctx {
+			req.extensions_mut().insert(ctx);
+		}
+		let response = router.oneshot(req).await.unwrap();
+		response.extensions().get::<Arc<Error>>()
+			.map_or(response.status(), |error| error.client_status_and_error().0)
+	}
+
+	#[tokio::test]
+	async fn test_require_role() {
+		assert_eq!(status(require_role(Role::Admin), Some(Ok(user_ctx(true)))).await, StatusCode::OK);
+		assert_eq!(status(require_role(Role::Admin), Some(Ok(user_ctx(false)))).await, StatusCode::FORBIDDEN);
+		assert_eq!(status(require_role(Role::Admin), Some(Err(Error::AuthFailApiKeyInvalid))).await, StatusCode::UNAUTHORIZED);
+		assert_eq!(status(require_role(Role::Admin), None).await, StatusCode::FORBIDDEN);
+	}
+
+	#[tokio::test]
+	async fn test_require_scope() {
+		// --- cookie sessions are not limited by scopes
+		assert_eq!(status(require_scope(ApiKeyScope::ReportsRead), Some(Ok(user_ctx(false)))).await, StatusCode::OK);
+		assert_eq!(status(require_scope(ApiKeyScope::ReportsRead), Some(Ok(api_key_ctx(vec![ApiKeyScope::ReportsRead])))).await, StatusCode::OK);
+		assert_eq!(status(require_scope(ApiKeyScope::ReportsRead), Some(Ok(api_key_ctx(vec![ApiKeyScope::ClaimsWrite])))).await, StatusCode::FORBIDDEN);
+	}
+
+	#[tokio::test]
+	async fn test_role_ctx() {
+		let (mut parts, _) = Request::builder().body(()).unwrap().into_parts();
+		parts.extensions.insert::<Result<Ctx>>(Ok(user_ctx(true)));
+		let RoleCtx(ctx, _) = AdminCtx::from_request_parts(&mut parts, &()).await.unwrap();
+		assert_eq!(ctx.user_info().user_id(), "user-1");
+
+		let (mut parts, _) = Request::builder().body(()).unwrap().into_parts();
+		parts.extensions.insert::<Result<Ctx>>(Ok(user_ctx(false)));
+		let rejection = AdminCtx::from_request_parts(&mut parts, &()).await.err().unwrap();
+		assert!(matches!(rejection, Error::Forbidden { required_role: Role::Admin }));
+	}
+}
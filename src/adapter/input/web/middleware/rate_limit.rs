@@ -0,0 +1,181 @@
+use std::convert::Infallible;
+use std::future::Future;
+use std::net::{IpAddr, SocketAddr};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use axum::body::Body;
+use axum::extract::ConnectInfo;
+use axum::http::Request;
+use axum::response::{IntoResponse, Response};
+use tokio::time::MissedTickBehavior;
+use tower::{Layer, Service};
+use crate::adapter::input::ctx::Ctx;
+use crate::adapter::input::error::{Error, Result};
+use crate::config::rate_limit::RateLimitConfig;
+use crate::domain::model::rate_limit::{RateLimitDecision, TokenBucketConfig};
+use crate::port::output::rate_limit_store::RateLimitStore;
+
+const FORWARDED_FOR_HEADER: &str = "x-forwarded-for";
+
+#[derive(Clone, Copy, Debug)]
+pub enum RateLimitGroup {
+    Payout,
+    Api,
+}
+
+impl RateLimitGroup {
+    fn as_str(&self) -> &'static str {
+        match self {
+            RateLimitGroup::Payout => "payout",
+            RateLimitGroup::Api => "api",
+        }
+    }
+}
+
+pub struct RateLimiter {
+    store: Arc<dyn RateLimitStore>,
+    config: RateLimitConfig,
+}
+
+impl RateLimiter {
+    pub fn new(store: Arc<dyn RateLimitStore>, config: RateLimitConfig) -> Self {
+        Self { store, config }
+    }
+
+    fn bucket(&self, group: RateLimitGroup) -> &TokenBucketConfig {
+        match group {
+            RateLimitGroup::Payout => &self.config.payout,
+            RateLimitGroup::Api => &self.config.api,
+        }
+    }
+
+    fn client_ip(&self, req: &Request<Body>) -> Option<IpAddr> {
+        let forwarded_ip = self.config.trust_forwarded_for
+            .then(|| req.headers().get(FORWARDED_FOR_HEADER))
+            .flatten()
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split(',').next())
+            .and_then(|ip| ip.trim().parse::<IpAddr>().ok());
+
+        forwarded_ip.or_else(|| req.extensions().get::<ConnectInfo<SocketAddr>>().map(|ConnectInfo(addr)| addr.ip()))
+    }
+
+    /// Takes a token from the user bucket and the client ip bucket, the request passes only if both have one.
+    /// Store failures let the request through, the limiter must not take the api down with it.
+    async fn check(&self, group: RateLimitGroup, user_id: Option<String>, client_ip: Option<IpAddr>) -> Result<()> {
+        let keys = user_id.map(|user_id| format!("{}:user:{}", group.as_str(), user_id))
+            .into_iter()
+            .chain(client_ip.map(|ip| format!("{}:ip:{}", group.as_str(), ip)));
+
+        for key in keys {
+            match self.store.try_acquire(&key, self.bucket(group)).await {
+                Ok(RateLimitDecision::Allowed) => {}
+                Ok(RateLimitDecision::Limited { retry_after_secs }) => {
+                    tracing::debug!("[rate_limit] {} limited, retry after {}s", key, retry_after_secs);
+                    return Err(Error::TooManyRequests { retry_after_secs });
+                }
+                Err(e) => tracing::warn!("[rate_limit] failed to check {}: {:?}", key, e),
+            }
+        }
+        Ok(())
+    }
+
+    /// Periodically drops the buckets that had time to fill up again, a dropped key starts again with a full bucket.
+    /// Without it the postgres backend keeps one row per user and ip ever seen.
+    pub async fn run_cleanup(self: Arc<Self>) {
+        let Some(idle_secs) = [self.config.payout, self.config.api].iter()
+            .map(|bucket| bucket.refill_secs())
+            .collect::<Option<Vec<f64>>>()
+            .map(|refill_secs| refill_secs.into_iter().fold(0.0, f64::max).ceil() as u64)
+        else {
+            tracing::warn!("[rate_limit] a bucket never refills, idle buckets are not cleaned up");
+            return;
+        };
+
+        let mut interval = tokio::time::interval(Duration::from_secs(self.config.cleanup_interval_secs.max(1)));
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            match self.store.purge_idle(idle_secs).await {
+                Ok(purged) => tracing::debug!("[rate_limit] purged {} idle buckets", purged),
+                Err(e) => tracing::warn!("[rate_limit] failed to purge idle buckets: {:?}", e),
+            }
+        }
+    }
+}
+
+// region:    --- Rate Limit Layer
+
+/// Route layer limiting requests per `Ctx` user and per client ip with the token bucket of `group`.
+///
+/// ```ignore
+/// Router::new()
+///     .route("/reward-claims", post(handler))
+///     .route_layer(rate_limit(&state.rate_limiter, RateLimitGroup::Payout))
+/// ```
+pub fn rate_limit(limiter: &Arc<RateLimiter>, group: RateLimitGroup) -> RateLimitLayer {
+    RateLimitLayer { limiter: Arc::clone(limiter), group }
+}
+
+#[derive(Clone)]
+pub struct RateLimitLayer {
+    limiter: Arc<RateLimiter>,
+    group: RateLimitGroup,
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimit<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimit { inner, limiter: Arc::clone(&self.limiter), group: self.group }
+    }
+}
+
+#[derive(Clone)]
+pub struct RateLimit<S> {
+    inner: S,
+    limiter: Arc<RateLimiter>,
+    group: RateLimitGroup,
+}
+
+impl<S> Service<Request<Body>> for RateLimit<S>
+where
+    S: Service<Request<Body>, Response = Response, Error = Infallible> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = core::result::Result<Response, Infallible>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<core::result::Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        // the ready inner service goes into the future, its fresh clone stays for the next call
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        if !self.limiter.config.enabled {
+            return Box::pin(inner.call(req));
+        }
+
+        let limiter = Arc::clone(&self.limiter);
+        let group = self.group;
+        let user_id = req.extensions().get::<Result<Ctx>>()
+            .and_then(|ctx| ctx.as_ref().ok())
+            .map(|ctx| ctx.user_info().user_id().to_string());
+        let client_ip = limiter.client_ip(&req);
+
+        Box::pin(async move {
+            match limiter.check(group, user_id, client_ip).await {
+                Ok(()) => inner.call(req).await,
+                Err(error) => Ok(error.into_response()),
+            }
+        })
+    }
+}
+
+// endregion: --- Rate Limit Layer
@@ -0,0 +1,60 @@
+use axum::body::Body;
+use axum::http::{HeaderMap, Request};
+use axum::middleware::Next;
+use axum::response::Response;
+use uuid::Uuid;
+use crate::adapter::input::ctx::RequestId;
+use crate::adapter::input::web::{REQUEST_ID_HEADER, TRACEPARENT_HEADER};
+
+/// Outermost layer, so the request span, `mw_ctx_resolver` and `response::mapper` share one id.
+pub async fn mw_request_id(mut req: Request<Body>, next: Next) -> Response {
+	let request_id = request_id_from_headers(req.headers()).unwrap_or_else(Uuid::new_v4);
+	req.extensions_mut().insert(RequestId(request_id));
+	next.run(req).await
+}
+
+/// The caller's `X-Request-Id` when it is a uuid, else the trace id of its `traceparent`.
+fn request_id_from_headers(headers: &HeaderMap) -> Option<Uuid> {
+	let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+
+	header(REQUEST_ID_HEADER)
+		.and_then(|value| Uuid::parse_str(value.trim()).ok())
+		.or_else(|| {
+			// --- version-trace_id-parent_id-flags, an all zero trace id is invalid
+			let trace_id = header(TRACEPARENT_HEADER)?.split('-').nth(1)?;
+			Uuid::parse_str(trace_id).ok().filter(|id| !id.is_nil())
+		})
+}
+
+#[cfg(test)]
+mod tests {
+	use axum::http::HeaderValue;
+	use super::*;
+
+	fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+		let mut headers = HeaderMap::new();
+		for (name, value) in pairs {
+			headers.insert(*name, HeaderValue::from_static(value));
+		}
+		headers
+	}
+
+	#[test]
+	fn test_request_id_from_headers() {
+		let request_id = "5f1c7c52-8a8e-4f57-9f0e-2c3f4a1b6d7e";
+		let traceparent = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+		assert_eq!(request_id_from_headers(&headers(&[(REQUEST_ID_HEADER, request_id)])), Uuid::parse_str(request_id).ok());
+		assert_eq!(
+			request_id_from_headers(&headers(&[(TRACEPARENT_HEADER, traceparent)])),
+			Uuid::parse_str("4bf92f3577b34da6a3ce929d0e0e4736").ok(),
+		);
+		// --- a request id that is not a uuid falls back to the trace id
+		assert_eq!(
+			request_id_from_headers(&headers(&[(REQUEST_ID_HEADER, "req-1"), (TRACEPARENT_HEADER, traceparent)])),
+			Uuid::parse_str("4bf92f3577b34da6a3ce929d0e0e4736").ok(),
+		);
+		assert_eq!(request_id_from_headers(&headers(&[(TRACEPARENT_HEADER, "00-00000000000000000000000000000000-00f067aa0ba902b7-01")])), None);
+		assert_eq!(request_id_from_headers(&HeaderMap::new()), None);
+	}
+}
@@ -1,24 +1,28 @@
 use std::sync::Arc;
 
-use axum::{body::{Body, Bytes}, http::{Method, Uri}, response::{IntoResponse, Response}, Json};
+use axum::{body::{Body, Bytes}, http::{header, HeaderValue, Method, Uri}, response::{IntoResponse, Response}, Extension, Json};
 use serde::Serialize;
 use utoipa::ToSchema;
 use uuid::Uuid;
-use crate::{adapter::input::{ctx::Ctx, error::Error}, config::log::log_request};
+use crate::{adapter::input::{ctx::{Ctx, RequestId}, error::Error}, config::log::{inject_trace_context, log_request}};
+use crate::adapter::input::web::REQUEST_ID_HEADER;
 
 #[derive(Serialize, ToSchema)]
 pub struct ErrorResponse{
 	pub status_code: u16,
 	pub message: String,
+	/// same as the `x-request-id` response header, quote it when reporting the error
+	pub request_id: String,
 }
 
 pub async fn mapper(
 	ctx: Option<Ctx>,
+	request_id: Option<Extension<RequestId>>,
 	uri: Uri,
 	req_method: Method,
 	res: Response,
 ) -> Response {
-	let uuid = Uuid::new_v4();
+	let uuid = request_id.map(|Extension(RequestId(id))| id).unwrap_or_else(Uuid::new_v4);
 
 	let service_error = res.extensions().get::<Arc<Error>>().map(Arc::as_ref).cloned();
 	let client_status_error = service_error.clone().map(|se| se.client_status_and_error());
@@ -30,11 +34,17 @@ pub async fn mapper(
 				let error_response = ErrorResponse {
 					status_code: status_code.as_u16(),
 					message: client_error_message.to_string(),
+					request_id: uuid.to_string(),
 				};
 
-				(*status_code, Json(error_response)).into_response()
+				let mut response = (*status_code, Json(error_response)).into_response();
+				if let Some(Error::TooManyRequests { retry_after_secs }) = service_error.as_ref() {
+					response.headers_mut().insert(header::RETRY_AFTER, HeaderValue::from(*retry_after_secs));
+				}
+				response
 			});
 
+	let status_code = client_status_error.as_ref().map(|(status_code, _)| *status_code).unwrap_or(res.status());
 	let client_error_message = client_status_error.unzip().1;
 
 	let (parts, body) = res.into_parts();
@@ -48,11 +58,15 @@ pub async fn mapper(
 	let res_body_str = String::from_utf8_lossy(&res_body_bytes).to_string();
 
 	let _ =
-		log_request(uuid, req_method, uri, ctx, 
+		log_request(uuid, req_method, uri, status_code, ctx, 
 			service_error.as_ref(), 
 			client_error_message.clone().as_ref(),
 			Some(&res_body_str)).await;
 
-	error_response.unwrap_or_else(|| Response::from_parts(parts, Body::from(res_body_bytes)))
+	let mut response = error_response.unwrap_or_else(|| Response::from_parts(parts, Body::from(res_body_bytes)));
+	// --- runs inside the request span of `request_logging_middleware`, its trace context goes back to the caller
+	response.headers_mut().insert(REQUEST_ID_HEADER, HeaderValue::from_str(&uuid.to_string()).expect("a uuid is a valid header value"));
+	inject_trace_context(&tracing::Span::current(), response.headers_mut());
+	response
 }
 
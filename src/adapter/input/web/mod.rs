@@ -5,8 +5,22 @@ pub mod routes_coin;
 pub mod routes_network;
 pub mod routes_coin_network;
 pub mod routes_reward_claim;
+pub mod routes_admin_webhook;
+pub mod routes_admin_audit_log;
+pub mod routes_admin_api_key;
+pub mod routes_admin_coin;
+pub mod routes_admin_signer_key;
+pub mod routes_metrics;
+pub mod routes_health;
 
 pub const ACCESS_TOKEN: &str = "access_token";
 pub const GOOGLE_ID: &str = "ggl_id";
+pub const API_KEY_HEADER: &str = "x-api-key";
+/// user id a service acts for when calling user scoped apis with an api key
+pub const ON_BEHALF_OF_HEADER: &str = "x-on-behalf-of";
+/// taken from the caller when it is a uuid, echoed on every response
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+/// W3C trace context
+pub const TRACEPARENT_HEADER: &str = "traceparent";
 
 pub mod _dev_routes_login;
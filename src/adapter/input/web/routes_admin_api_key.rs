@@ -0,0 +1,110 @@
+use std::sync::Arc;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use uuid::Uuid;
+use crate::adapter::input::ctx::Role;
+use crate::adapter::input::error::{Error, Result};
+use crate::adapter::input::web::middleware::permission::{require_role, AdminCtx};
+use crate::domain::model::api_key::{ApiKeyResponse, IssuedApiKeyResponse, NewApiKeyPayload, RotateApiKeyPayload};
+use crate::state::AppState;
+
+pub fn routes(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/admin/api-keys", get(list_api_keys).post(create_api_key))
+        .route("/admin/api-keys/:id/rotate", post(rotate_api_key))
+        .route("/admin/api-keys/:id/revoke", post(revoke_api_key))
+        .route_layer(require_role(Role::Admin))
+        .with_state(state)
+}
+
+fn parse_uuid(id: &str) -> Result<Uuid> {
+    Uuid::parse_str(id).map_err(|_| Error::UUIDParsingError { message: format!("invalid UUID : {}", id) })
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/api-keys",
+    request_body = NewApiKeyPayload,
+    responses(
+        (status = 201, description = "Issued api key, the plaintext key is only returned here", body = IssuedApiKeyResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 500, description = "Internel Server Error", body = ErrorResponse)
+    ),
+    tag = "Admin"
+)]
+pub async fn create_api_key(
+    State(state): State<Arc<AppState>>,
+    admin: AdminCtx,
+    Json(payload): Json<NewApiKeyPayload>,
+) -> Result<impl IntoResponse> {
+    let (raw_key, api_key) = state.api_key_usecase.create_api_key(payload, admin.0.audit_actor()).await?;
+    Ok((StatusCode::CREATED, Json(IssuedApiKeyResponse { api_key: raw_key, key: ApiKeyResponse::from(api_key) })))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/api-keys",
+    responses(
+        (status = 200, description = "List of api keys", body = Vec<ApiKeyResponse>),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 500, description = "Internel Server Error", body = ErrorResponse)
+    ),
+    tag = "Admin"
+)]
+pub async fn list_api_keys(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<ApiKeyResponse>>> {
+    let api_keys = state.api_key_usecase.list_api_keys().await?;
+    Ok(Json(api_keys.into_iter().map(ApiKeyResponse::from).collect()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/api-keys/{id}/rotate",
+    params(("id" = String, Path, description = "api key id")),
+    request_body = RotateApiKeyPayload,
+    responses(
+        (status = 201, description = "Replacement key, the old key expires after the grace period", body = IssuedApiKeyResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internel Server Error", body = ErrorResponse)
+    ),
+    tag = "Admin"
+)]
+pub async fn rotate_api_key(
+    State(state): State<Arc<AppState>>,
+    admin: AdminCtx,
+    Path(id): Path<String>,
+    payload: Option<Json<RotateApiKeyPayload>>,
+) -> Result<impl IntoResponse> {
+    let payload = payload.map(|Json(payload)| payload).unwrap_or_default();
+    let (raw_key, api_key) = state.api_key_usecase.rotate_api_key(parse_uuid(&id)?, payload, admin.0.audit_actor()).await?;
+    Ok((StatusCode::CREATED, Json(IssuedApiKeyResponse { api_key: raw_key, key: ApiKeyResponse::from(api_key) })))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/api-keys/{id}/revoke",
+    params(("id" = String, Path, description = "api key id")),
+    responses(
+        (status = 200, description = "Revoked api key", body = ApiKeyResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internel Server Error", body = ErrorResponse)
+    ),
+    tag = "Admin"
+)]
+pub async fn revoke_api_key(
+    State(state): State<Arc<AppState>>,
+    admin: AdminCtx,
+    Path(id): Path<String>,
+) -> Result<Json<ApiKeyResponse>> {
+    let api_key = state.api_key_usecase.revoke_api_key(parse_uuid(&id)?, admin.0.audit_actor()).await?;
+    Ok(Json(ApiKeyResponse::from(api_key)))
+}
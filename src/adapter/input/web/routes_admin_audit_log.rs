@@ -0,0 +1,58 @@
+use std::sync::Arc;
+use axum::extract::{Query, State};
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::Deserialize;
+use utoipa::IntoParams;
+use crate::adapter::input::ctx::Role;
+use crate::adapter::input::web::middleware::permission::require_role;
+use crate::adapter::input::error::Result;
+use crate::domain::model::audit_log::{AuditLogFilter, AuditLogResponse};
+use crate::port::output::audit_log_repository::AuditLogRepository;
+use crate::port::output::DbManager;
+use crate::state::AppState;
+
+const DEFAULT_LIST_LIMIT: i64 = 100;
+const MAX_LIST_LIMIT: i64 = 1000;
+
+pub fn routes(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/admin/audit-logs", get(list_audit_logs))
+        .route_layer(require_role(Role::Admin))
+        .with_state(state)
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct AuditLogQuery {
+    /// ex) REWARD_CLAIM, OUTBOX_EVENT
+    entity_type: Option<String>,
+    entity_id: Option<String>,
+    actor_id: Option<String>,
+    /// default 100, max 1000
+    limit: Option<i64>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/audit-logs",
+    params(AuditLogQuery),
+    responses(
+        (status = 200, description = "List of audit logs, newest first", body = Vec<AuditLogResponse>),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 500, description = "Internel Server Error", body = ErrorResponse)
+    ),
+    tag = "Admin"
+)]
+pub async fn list_audit_logs(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<AuditLogQuery>,
+) -> Result<Json<Vec<AuditLogResponse>>> {
+    let filter = AuditLogFilter {
+        entity_type: query.entity_type,
+        entity_id: query.entity_id,
+        actor_id: query.actor_id,
+        limit: query.limit.unwrap_or(DEFAULT_LIST_LIMIT).clamp(1, MAX_LIST_LIMIT),
+    };
+    let audit_logs = state.audit_log_repo.list(state.db_manager.get_connection().await?, filter).await?;
+    Ok(Json(audit_logs.into_iter().map(AuditLogResponse::from).collect()))
+}
@@ -0,0 +1,327 @@
+use std::sync::Arc;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::{patch, post, put};
+use axum::{Json, Router};
+use serde::Deserialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use crate::adapter::input::ctx::Role;
+use crate::adapter::input::error::{Error, Result};
+use crate::adapter::input::web::middleware::permission::{require_role, AdminCtx};
+use crate::domain::model::coin::{CoinResponse, NewCoinPayload, UpdateCoinPayload};
+use crate::domain::model::coin_network::{CoinNetworkDetailsResponse, FtMetadataCheckResponse, NewCoinNetworkPayload, UpdateCoinNetworkPayload};
+use crate::domain::model::network::{NetworkResponse, NewNetworkPayload, UpdateNetworkPayload};
+use crate::state::AppState;
+
+pub fn routes(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/admin/coins", post(create_coin))
+        .route("/admin/coins/:id", patch(update_coin))
+        .route("/admin/coins/:id/active", put(set_coin_active))
+        .route("/admin/networks", post(create_network))
+        .route("/admin/networks/:id", patch(update_network))
+        .route("/admin/networks/:id/active", put(set_network_active))
+        .route("/admin/coin-networks", post(create_coin_network))
+        .route("/admin/coin-networks/:id", patch(update_coin_network).delete(deprecate_coin_network))
+        .route("/admin/coin-networks/:id/active", put(set_coin_network_active))
+        .route("/admin/coin-networks/check-metadata", post(check_ft_metadata))
+        .route("/admin/coin-networks/:id/sync-metadata", post(sync_ft_metadata))
+        .route_layer(require_role(Role::Admin))
+        .with_state(state)
+}
+
+/// `false` disables, `true` enables again. Rows are never deleted, existing claims keep referring to them.
+#[derive(Deserialize, ToSchema)]
+pub struct SetActivePayload {
+    pub is_active: bool,
+}
+
+fn parse_uuid(id: &str) -> Result<Uuid> {
+    Uuid::parse_str(id).map_err(|_| Error::UUIDParsingError { message: format!("invalid UUID : {}", id) })
+}
+
+// region:    --- Coin
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/coins",
+    request_body = NewCoinPayload,
+    responses(
+        (status = 201, description = "Created coin", body = CoinResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 500, description = "Internel Server Error", body = ErrorResponse)
+    ),
+    tag = "Admin"
+)]
+pub async fn create_coin(
+    State(state): State<Arc<AppState>>,
+    admin: AdminCtx,
+    Json(payload): Json<NewCoinPayload>,
+) -> Result<impl IntoResponse> {
+    let coin = state.coin_admin_usecase.create_coin(payload, admin.0.audit_actor()).await?;
+    Ok((StatusCode::CREATED, Json(CoinResponse::from(coin))))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/admin/coins/{id}",
+    params(("id" = String, Path, description = "coin id")),
+    request_body = UpdateCoinPayload,
+    responses(
+        (status = 200, description = "Updated coin", body = CoinResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internel Server Error", body = ErrorResponse)
+    ),
+    tag = "Admin"
+)]
+pub async fn update_coin(
+    State(state): State<Arc<AppState>>,
+    admin: AdminCtx,
+    Path(id): Path<String>,
+    Json(payload): Json<UpdateCoinPayload>,
+) -> Result<Json<CoinResponse>> {
+    let coin = state.coin_admin_usecase.update_coin(parse_uuid(&id)?, payload, admin.0.audit_actor()).await?;
+    Ok(Json(CoinResponse::from(coin)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/admin/coins/{id}/active",
+    params(("id" = String, Path, description = "coin id")),
+    request_body = SetActivePayload,
+    responses(
+        (status = 200, description = "Enabled or disabled coin", body = CoinResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internel Server Error", body = ErrorResponse)
+    ),
+    tag = "Admin"
+)]
+pub async fn set_coin_active(
+    State(state): State<Arc<AppState>>,
+    admin: AdminCtx,
+    Path(id): Path<String>,
+    Json(payload): Json<SetActivePayload>,
+) -> Result<Json<CoinResponse>> {
+    let coin = state.coin_admin_usecase.set_coin_active(parse_uuid(&id)?, payload.is_active, admin.0.audit_actor()).await?;
+    Ok(Json(CoinResponse::from(coin)))
+}
+
+// endregion: --- Coin
+
+// region:    --- Network
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/networks",
+    request_body = NewNetworkPayload,
+    responses(
+        (status = 201, description = "Created network", body = NetworkResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 409, description = "Conflict", body = ErrorResponse),
+        (status = 500, description = "Internel Server Error", body = ErrorResponse)
+    ),
+    tag = "Admin"
+)]
+pub async fn create_network(
+    State(state): State<Arc<AppState>>,
+    admin: AdminCtx,
+    Json(payload): Json<NewNetworkPayload>,
+) -> Result<impl IntoResponse> {
+    let network = state.coin_admin_usecase.create_network(payload, admin.0.audit_actor()).await?;
+    Ok((StatusCode::CREATED, Json(NetworkResponse::from(network))))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/admin/networks/{id}",
+    params(("id" = String, Path, description = "network id")),
+    request_body = UpdateNetworkPayload,
+    responses(
+        (status = 200, description = "Updated network", body = NetworkResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internel Server Error", body = ErrorResponse)
+    ),
+    tag = "Admin"
+)]
+pub async fn update_network(
+    State(state): State<Arc<AppState>>,
+    admin: AdminCtx,
+    Path(id): Path<String>,
+    Json(payload): Json<UpdateNetworkPayload>,
+) -> Result<Json<NetworkResponse>> {
+    let network = state.coin_admin_usecase.update_network(parse_uuid(&id)?, payload, admin.0.audit_actor()).await?;
+    Ok(Json(NetworkResponse::from(network)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/admin/networks/{id}/active",
+    params(("id" = String, Path, description = "network id")),
+    request_body = SetActivePayload,
+    responses(
+        (status = 200, description = "Enabled or disabled network", body = NetworkResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internel Server Error", body = ErrorResponse)
+    ),
+    tag = "Admin"
+)]
+pub async fn set_network_active(
+    State(state): State<Arc<AppState>>,
+    admin: AdminCtx,
+    Path(id): Path<String>,
+    Json(payload): Json<SetActivePayload>,
+) -> Result<Json<NetworkResponse>> {
+    let network = state.coin_admin_usecase.set_network_active(parse_uuid(&id)?, payload.is_active, admin.0.audit_actor()).await?;
+    Ok(Json(NetworkResponse::from(network)))
+}
+
+// endregion: --- Network
+
+// region:    --- Coin Network
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/coin-networks",
+    request_body = NewCoinNetworkPayload,
+    responses(
+        (status = 201, description = "Created coin network", body = CoinNetworkDetailsResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 409, description = "Conflict", body = ErrorResponse),
+        (status = 500, description = "Internel Server Error", body = ErrorResponse)
+    ),
+    tag = "Admin"
+)]
+pub async fn create_coin_network(
+    State(state): State<Arc<AppState>>,
+    admin: AdminCtx,
+    Json(payload): Json<NewCoinNetworkPayload>,
+) -> Result<impl IntoResponse> {
+    let coin_network = state.coin_admin_usecase.create_coin_network(payload, admin.0.audit_actor()).await?;
+    Ok((StatusCode::CREATED, Json(CoinNetworkDetailsResponse::from(coin_network))))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/admin/coin-networks/{id}",
+    params(("id" = String, Path, description = "coin network id")),
+    request_body = UpdateCoinNetworkPayload,
+    responses(
+        (status = 200, description = "Updated coin network", body = CoinNetworkDetailsResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internel Server Error", body = ErrorResponse)
+    ),
+    tag = "Admin"
+)]
+pub async fn update_coin_network(
+    State(state): State<Arc<AppState>>,
+    admin: AdminCtx,
+    Path(id): Path<String>,
+    Json(payload): Json<UpdateCoinNetworkPayload>,
+) -> Result<Json<CoinNetworkDetailsResponse>> {
+    let coin_network = state.coin_admin_usecase.update_coin_network(parse_uuid(&id)?, payload, admin.0.audit_actor()).await?;
+    Ok(Json(CoinNetworkDetailsResponse::from(coin_network)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/admin/coin-networks/{id}/active",
+    params(("id" = String, Path, description = "coin network id")),
+    request_body = SetActivePayload,
+    responses(
+        (status = 200, description = "Enabled or disabled coin network", body = CoinNetworkDetailsResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internel Server Error", body = ErrorResponse)
+    ),
+    tag = "Admin"
+)]
+pub async fn set_coin_network_active(
+    State(state): State<Arc<AppState>>,
+    admin: AdminCtx,
+    Path(id): Path<String>,
+    Json(payload): Json<SetActivePayload>,
+) -> Result<Json<CoinNetworkDetailsResponse>> {
+    let coin_network = state.coin_admin_usecase.set_coin_network_active(parse_uuid(&id)?, payload.is_active, admin.0.audit_actor()).await?;
+    Ok(Json(CoinNetworkDetailsResponse::from(coin_network)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/admin/coin-networks/{id}",
+    params(("id" = String, Path, description = "coin network id")),
+    responses(
+        (status = 200, description = "Deprecated coin network. It is hidden from listings and can not be enabled again, existing claims stay readable", body = CoinNetworkDetailsResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internel Server Error", body = ErrorResponse)
+    ),
+    tag = "Admin"
+)]
+pub async fn deprecate_coin_network(
+    State(state): State<Arc<AppState>>,
+    admin: AdminCtx,
+    Path(id): Path<String>,
+) -> Result<Json<CoinNetworkDetailsResponse>> {
+    let coin_network = state.coin_admin_usecase.deprecate_coin_network(parse_uuid(&id)?, admin.0.audit_actor()).await?;
+    Ok(Json(CoinNetworkDetailsResponse::from(coin_network)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/coin-networks/check-metadata",
+    responses(
+        (status = 200, description = "ft_metadata comparison of every active FT coin network. Mismatching ones refuse payouts", body = Vec<FtMetadataCheckResponse>),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 500, description = "Internel Server Error", body = ErrorResponse)
+    ),
+    tag = "Admin"
+)]
+pub async fn check_ft_metadata(
+    State(state): State<Arc<AppState>>,
+    admin: AdminCtx,
+) -> Result<Json<Vec<FtMetadataCheckResponse>>> {
+    let checks = state.coin_admin_usecase.check_ft_metadata(admin.0.audit_actor()).await?;
+    Ok(Json(checks))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/coin-networks/{id}/sync-metadata",
+    params(("id" = String, Path, description = "coin network id")),
+    responses(
+        (status = 200, description = "Coin decimals and symbol copied from ft_metadata, payouts are allowed again", body = CoinNetworkDetailsResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 502, description = "ft_metadata could not be read", body = ErrorResponse)
+    ),
+    tag = "Admin"
+)]
+pub async fn sync_ft_metadata(
+    State(state): State<Arc<AppState>>,
+    admin: AdminCtx,
+    Path(id): Path<String>,
+) -> Result<Json<CoinNetworkDetailsResponse>> {
+    let coin_network = state.coin_admin_usecase.sync_ft_metadata(parse_uuid(&id)?, admin.0.audit_actor()).await?;
+    Ok(Json(CoinNetworkDetailsResponse::from(coin_network)))
+}
+
+// endregion: --- Coin Network
@@ -0,0 +1,96 @@
+use std::sync::Arc;
+use axum::extract::State;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use crate::adapter::input::ctx::Role;
+use crate::adapter::input::error::Result;
+use crate::adapter::input::web::middleware::permission::{require_role, AdminCtx};
+use crate::domain::model::near::{AddSignerKeysPayload, RemoveSignerKeysPayload, SignerKey, SignerKeyChanges};
+use crate::state::AppState;
+
+pub fn routes(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/admin/signer-keys", get(list_signer_keys).post(add_signer_keys))
+        .route("/admin/signer-keys/remove", post(remove_signer_keys))
+        .route("/admin/signer-keys/reload", post(reload_signer_keys))
+        .route_layer(require_role(Role::Admin))
+        .with_state(state)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/signer-keys",
+    responses(
+        (status = 200, description = "Public keys the payouts are currently signed with", body = Vec<SignerKey>),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 500, description = "Internel Server Error", body = ErrorResponse)
+    ),
+    tag = "Admin"
+)]
+pub async fn list_signer_keys(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<SignerKey>>> {
+    Ok(Json(state.signer_key_usecase.list_keys()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/signer-keys/reload",
+    responses(
+        (status = 200, description = "Keys added and removed by the reload, empty when the keys file did not change", body = SignerKeyChanges),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 500, description = "Internel Server Error", body = ErrorResponse)
+    ),
+    tag = "Admin"
+)]
+pub async fn reload_signer_keys(
+    State(state): State<Arc<AppState>>,
+    admin: AdminCtx,
+) -> Result<Json<SignerKeyChanges>> {
+    let changes = state.signer_key_usecase.reload_keys(admin.0.audit_actor()).await?;
+    Ok(Json(changes))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/signer-keys",
+    request_body = AddSignerKeysPayload,
+    responses(
+        (status = 200, description = "Keys added on chain and to the keys file", body = SignerKeyChanges),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 500, description = "Internel Server Error", body = ErrorResponse)
+    ),
+    tag = "Admin"
+)]
+pub async fn add_signer_keys(
+    State(state): State<Arc<AppState>>,
+    admin: AdminCtx,
+    Json(payload): Json<AddSignerKeysPayload>,
+) -> Result<Json<SignerKeyChanges>> {
+    let changes = state.signer_key_usecase.add_keys(payload, admin.0.audit_actor()).await?;
+    Ok(Json(changes))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/signer-keys/remove",
+    request_body = RemoveSignerKeysPayload,
+    responses(
+        (status = 200, description = "Keys removed from the keys file and deleted on chain", body = SignerKeyChanges),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internel Server Error", body = ErrorResponse)
+    ),
+    tag = "Admin"
+)]
+pub async fn remove_signer_keys(
+    State(state): State<Arc<AppState>>,
+    admin: AdminCtx,
+    Json(payload): Json<RemoveSignerKeysPayload>,
+) -> Result<Json<SignerKeyChanges>> {
+    let changes = state.signer_key_usecase.remove_keys(payload, admin.0.audit_actor()).await?;
+    Ok(Json(changes))
+}
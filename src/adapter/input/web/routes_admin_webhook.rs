@@ -0,0 +1,103 @@
+use std::sync::Arc;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::Deserialize;
+use utoipa::IntoParams;
+use uuid::Uuid;
+use crate::adapter::input::ctx::Role;
+use crate::adapter::input::web::middleware::permission::{require_role, AdminCtx};
+use crate::adapter::input::error::{Error, Result};
+use crate::domain::model::webhook_delivery::WebhookDeliveryResponse;
+use crate::state::AppState;
+
+const DEFAULT_LIST_LIMIT: i64 = 100;
+
+pub fn routes(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/admin/webhook-deliveries", get(list_webhook_deliveries))
+        .route("/admin/webhook-deliveries/:id/redeliver", post(redeliver_webhook))
+        .route("/admin/outbox-events/:id/requeue", post(requeue_outbox_event))
+        .route_layer(require_role(Role::Admin))
+        .with_state(state)
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct WebhookDeliveryQuery {
+    /// only deliveries of this outbox event
+    event_id: Option<String>,
+    /// ignored when `event_id` is set (default 100)
+    limit: Option<i64>,
+}
+
+fn parse_uuid(id: &str) -> Result<Uuid> {
+    Uuid::parse_str(id).map_err(|_| Error::UUIDParsingError { message: format!("invalid UUID : {}", id) })
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/webhook-deliveries",
+    params(WebhookDeliveryQuery),
+    responses(
+        (status = 200, description = "List of webhook deliveries", body = Vec<WebhookDeliveryResponse>),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 500, description = "Internel Server Error", body = ErrorResponse)
+    ),
+    tag = "Admin"
+)]
+pub async fn list_webhook_deliveries(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<WebhookDeliveryQuery>,
+) -> Result<Json<Vec<WebhookDeliveryResponse>>> {
+    let event_id = query.event_id.as_deref().map(parse_uuid).transpose()?;
+    let deliveries = state.webhook_usecase
+        .list_deliveries(event_id, query.limit.unwrap_or(DEFAULT_LIST_LIMIT))
+        .await?;
+    Ok(Json(deliveries.into_iter().map(WebhookDeliveryResponse::from).collect()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/webhook-deliveries/{id}/redeliver",
+    params(("id" = String, Path, description = "webhook delivery id")),
+    responses(
+        (status = 200, description = "New delivery attempt", body = WebhookDeliveryResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internel Server Error", body = ErrorResponse)
+    ),
+    tag = "Admin"
+)]
+pub async fn redeliver_webhook(
+    State(state): State<Arc<AppState>>,
+    admin: AdminCtx,
+    Path(id): Path<String>,
+) -> Result<Json<WebhookDeliveryResponse>> {
+    let delivery = state.webhook_usecase.redeliver(parse_uuid(&id)?, admin.0.audit_actor()).await?;
+    Ok(Json(WebhookDeliveryResponse::from(delivery)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/outbox-events/{id}/requeue",
+    params(("id" = String, Path, description = "outbox event id")),
+    responses(
+        (status = 202, description = "Event requeued for delivery"),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internel Server Error", body = ErrorResponse)
+    ),
+    tag = "Admin"
+)]
+pub async fn requeue_outbox_event(
+    State(state): State<Arc<AppState>>,
+    admin: AdminCtx,
+    Path(id): Path<String>,
+) -> Result<impl IntoResponse> {
+    state.webhook_usecase.requeue_event(parse_uuid(&id)?, admin.0.audit_actor()).await?;
+    Ok(StatusCode::ACCEPTED)
+}
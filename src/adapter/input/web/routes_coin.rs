@@ -2,12 +2,12 @@ use std::sync::Arc;
 use axum::extract::State;
 use axum::{Router, Json, extract::Path, routing::get};
 use crate::adapter::input::ctx::Ctx;
-use crate::domain::model::coin::{CoinResponse, NewCoinPayload};
+use crate::domain::model::coin::CoinResponse;
 use crate::domain::model::coin_network::CoinNetworkResponse;
 use crate::port::output::coin_network_repository::CoinNetworkRepository;
 use crate::port::output::coin_repository::CoinRepository;
 use crate::port::output::DbManager;
-use crate::AppState;
+use crate::state::AppState;
 use crate::adapter::input::error::{Error, Result};
 use uuid::Uuid; 
 
@@ -19,20 +19,6 @@ pub fn routes(state: Arc<AppState>) -> Router {
         .with_state(state)
 }
 
-#[allow(unused)]
-async fn create_coin(
-	State(state): State<Arc<AppState>>,
-    _ctx: Ctx,
-	Json(new_coin_payload): Json<NewCoinPayload>,
-) -> Result<Json<CoinResponse>> {
-	let coin = state.coin_repo.insert(
-        state.db_manager.get_connection().await?,
-        new_coin_payload,
-    ).await?;
-
-	Ok(Json(CoinResponse::from(coin)))
-}
-
 async fn list_coins(
     State(state): State<Arc<AppState>>,
     _ctx: Ctx,
@@ -7,7 +7,7 @@ use crate::adapter::input::ctx::Ctx;
 use crate::domain::model::coin_network::CoinNetworkDetailsResponse;
 use crate::port::output::coin_network_repository::CoinNetworkRepository;
 use crate::port::output::DbManager;
-use crate::AppState;
+use crate::state::AppState;
 use crate::adapter::input::error::Result;
 
 pub fn routes(state: Arc<AppState>) -> Router {
@@ -19,6 +19,8 @@ pub fn routes(state: Arc<AppState>) -> Router {
 #[derive(Deserialize, IntoParams)]
 pub struct NetworkCodeQuery {
     network_code: Option<String>,
+    /// also list disabled and deprecated coin networks
+    include_inactive: Option<bool>,
 }
 
 #[utoipa::path(
@@ -37,15 +39,16 @@ pub async fn list_coin_networks(
     _ctx: Ctx,
     Query(query): Query<NetworkCodeQuery>,
 ) -> Result<Json<Vec<CoinNetworkDetailsResponse>>> {
+    let include_inactive = query.include_inactive.unwrap_or(false);
     let coin_network_details = if let Some(network_code) = query.network_code {
         state
             .coin_network_repo
-            .list_all_by_network_code(state.db_manager.get_connection().await?, network_code)
+            .list_all_by_network_code(state.db_manager.get_connection().await?, network_code, include_inactive)
             .await?
     } else {
         state
             .coin_network_repo
-            .list_all(state.db_manager.get_connection().await?)
+            .list_all(state.db_manager.get_connection().await?, include_inactive)
             .await?
     };
 
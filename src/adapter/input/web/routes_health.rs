@@ -0,0 +1,47 @@
+use std::sync::Arc;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::{Json, Router};
+use crate::domain::model::health::{HealthStatus, LivenessReport, ReadinessReport};
+use crate::state::AppState;
+
+/// Probe targets, outside `/api` and without auth.
+pub fn routes(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/health/live", get(live))
+        .route("/health/ready", get(ready))
+        .with_state(state)
+}
+
+#[utoipa::path(
+    get,
+    path = "/health/live",
+    responses(
+        (status = 200, description = "The process serves requests", body = LivenessReport)
+    ),
+    tag = "Health"
+)]
+pub async fn live() -> Json<LivenessReport> {
+    Json(LivenessReport { status: HealthStatus::Up })
+}
+
+#[utoipa::path(
+    get,
+    path = "/health/ready",
+    responses(
+        (status = 200, description = "Every dependency is up", body = ReadinessReport),
+        (status = 503, description = "At least one dependency is down, see the components", body = ReadinessReport)
+    ),
+    tag = "Health"
+)]
+pub async fn ready(
+    State(state): State<Arc<AppState>>,
+) -> (StatusCode, Json<ReadinessReport>) {
+    let report = state.health_usecase.readiness().await;
+    let status_code = match report.status {
+        HealthStatus::Up => StatusCode::OK,
+        HealthStatus::Down => StatusCode::SERVICE_UNAVAILABLE,
+    };
+    (status_code, Json(report))
+}
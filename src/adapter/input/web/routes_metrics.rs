@@ -0,0 +1,67 @@
+use std::sync::Arc;
+use axum::extract::State;
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Router;
+use crate::config::metrics::{metrics, sync_counter};
+use crate::domain::model::near::CircuitState;
+use crate::state::AppState;
+
+/// Served on the `[metrics]` listener, not under `/api`.
+pub fn routes(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/metrics", get(get_metrics))
+        .with_state(state)
+}
+
+pub async fn get_metrics(
+    State(state): State<Arc<AppState>>,
+) -> impl IntoResponse {
+    refresh(&state);
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        metrics().encode(),
+    )
+}
+
+/// Copies the state kept outside the registry into it.
+fn refresh(state: &AppState) {
+    let metrics = metrics();
+    let near_rpc_manager = &state.near_rpc_manager;
+
+    for count in near_rpc_manager.retry_stats().snapshot() {
+        let class = count.class.to_string();
+        sync_counter(&metrics.rpc_retries.with_label_values(&[class.as_str()]), count.retried);
+        sync_counter(&metrics.rpc_retries_exhausted.with_label_values(&[class.as_str()]), count.exhausted);
+    }
+
+    for endpoint in near_rpc_manager.endpoints.statuses() {
+        metrics.rpc_endpoint_healthy.with_label_values(&[endpoint.url.as_str()]).set(endpoint.healthy as i64);
+        if let Some(block_lag) = endpoint.block_lag {
+            metrics.rpc_endpoint_block_lag.with_label_values(&[endpoint.url.as_str()]).set(block_lag as i64);
+        }
+    }
+
+    let breaker = near_rpc_manager.circuit_breaker_status();
+    for circuit_state in [CircuitState::Closed, CircuitState::Open, CircuitState::HalfOpen] {
+        metrics.rpc_circuit_breaker_state
+            .with_label_values(&[circuit_state.to_string().as_str()])
+            .set((breaker.state == circuit_state) as i64);
+    }
+    sync_counter(&metrics.rpc_circuit_breaker_opened, breaker.opened_count);
+    sync_counter(&metrics.rpc_circuit_breaker_rejected, breaker.rejected_count);
+
+    // --- removed keys drop out of the gauge, their transaction counts stay
+    metrics.signer_key_in_flight.reset();
+    for usage in near_rpc_manager.signer_key_usage() {
+        metrics.signer_key_in_flight.with_label_values(&[usage.public_key.as_str()]).set(usage.in_flight as i64);
+        sync_counter(&metrics.signer_key_transactions.with_label_values(&[usage.public_key.as_str()]), usage.transactions);
+    }
+
+    let pool = state.db_manager.pool_status();
+    metrics.db_pool_max_size.set(pool.max_size);
+    metrics.db_pool_size.set(pool.size);
+    metrics.db_pool_available.set(pool.available);
+    metrics.db_pool_waiting.set(pool.waiting);
+}
@@ -2,10 +2,10 @@ use std::sync::Arc;
 use axum::extract::State;
 use axum::{Router, Json, extract::Path, routing::get};
 use crate::adapter::input::ctx::Ctx;
-use crate::domain::model::network::{NetworkResponse, NewNetworkPayload};
+use crate::domain::model::network::NetworkResponse;
 use crate::port::output::network_repository::NetworkRepository;
 use crate::port::output::DbManager;
-use crate::AppState;
+use crate::state::AppState;
 use crate::adapter::input::error::{Error, Result};
 use uuid::Uuid; 
 
@@ -16,20 +16,6 @@ pub fn routes(state: Arc<AppState>) -> Router {
         .with_state(state)
 }
 
-#[allow(unused)]
-async fn create_network(
-	State(state): State<Arc<AppState>>,
-    _ctx: Ctx,
-	Json(new_network_payload): Json<NewNetworkPayload>,
-) -> Result<Json<NetworkResponse>> {
-	let network = state.network_repo.insert(
-        state.db_manager.get_connection().await?,
-        new_network_payload,
-    ).await?;
-
-	Ok(Json(NetworkResponse::from(network)))
-}
-
 async fn list_networks(
     State(state): State<Arc<AppState>>,
     _ctx: Ctx,
@@ -4,15 +4,27 @@ use axum::response::IntoResponse;
 use axum::routing::get;
 use axum::{extract::State, routing::post, Json, Router};
 use crate::adapter::input::{ctx::Ctx, error::Error};
+use crate::adapter::input::web::middleware::permission::require_scope;
+use crate::adapter::input::web::middleware::rate_limit::{rate_limit, RateLimitGroup};
+use crate::domain::model::api_key::ApiKeyScope;
 use crate::domain::model::reward_claim::{CombinedRewardClaimResponse, NewRewardClaimPayload};
-use crate::AppState;
+use crate::state::AppState;
 use crate::adapter::input::error::Result;
+use crate::usecase;
+use tracing::Instrument;
 use uuid::Uuid;
 
 pub fn routes(state: Arc<AppState>) -> Router {
-    Router::new()
+    let claims_write_routes = Router::new()
         .route("/reward-claims", post(create_reward_claim))
+        .route_layer(rate_limit(&state.rate_limiter, RateLimitGroup::Payout))
+        .route_layer(require_scope(ApiKeyScope::ClaimsWrite));
+    let reports_read_routes = Router::new()
         .route("/me/reward-claims", get(list_me_reward_claim))
+        .route_layer(require_scope(ApiKeyScope::ReportsRead));
+
+    claims_write_routes
+        .merge(reports_read_routes)
         .with_state(state)
 }
 
@@ -22,11 +34,13 @@ pub fn routes(state: Arc<AppState>) -> Router {
     request_body = NewRewardClaimPayload,
     responses(
         (status = 201, description = "Create reward claim", body = Vec<CombinedRewardClaimResponse>),
-        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 400, description = "Bad Request, or the coin network is disabled", body = ErrorResponse),
         (status = 403, description = "Forbidden", body = ErrorResponse),
         (status = 404, description = "Not Found", body = ErrorResponse),
         (status = 409, description = "Conflict", body = ErrorResponse),
-        (status = 500, description = "Internel Server Error", body = ErrorResponse)
+        (status = 429, description = "Too Many Requests", body = ErrorResponse),
+        (status = 500, description = "Internel Server Error", body = ErrorResponse),
+        (status = 503, description = "Near rpc is unavailable, or the instance is shutting down", body = ErrorResponse)
     ),
     tag = "RewardClaim"
 )]
@@ -38,7 +52,14 @@ pub async fn create_reward_claim(
     let user_id = Uuid::parse_str(ctx.user_info().user_id())
         .map_err(|_| Error::UUIDParsingError{ message: format!("invalid User UUID : {}", ctx.user_info().user_id())})?;
 
-    let reward_claim = state.reward_claim_usecase.create_reward_claim(user_id, new_reward_claim_payload).await?;
+    // --- detached from the request: a client disconnect or a forced shutdown must not stop a payout between broadcast and persist
+    let reward_claim_usecase = Arc::clone(&state.reward_claim_usecase);
+    let actor = ctx.audit_actor();
+    let reward_claim = tokio::spawn(
+        async move { reward_claim_usecase.create_reward_claim(user_id, new_reward_claim_payload, actor).await }.in_current_span(),
+    )
+    .await
+    .map_err(|e| usecase::error::Error::InternalServerError { message: format!("reward claim task failed: {e}") })??;
     Ok((StatusCode::CREATED, Json(CombinedRewardClaimResponse::from(reward_claim))))
 }
 
@@ -0,0 +1,28 @@
+use serde::Serialize;
+use serde_with::serde_as;
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[serde_as]
+#[derive(Debug, Serialize, Clone)]
+pub enum Error {
+	InvalidToken { message: String },
+	KeyNotFound { kid: Option<String> },
+	SessionInactive,
+	IntrospectionFailed { message: String },
+	KeyLoadFailed { message: String },
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<jsonwebtoken::errors::Error> for Error {
+	fn from(error: jsonwebtoken::errors::Error) -> Self {
+		Self::InvalidToken { message: error.to_string() }
+	}
+}
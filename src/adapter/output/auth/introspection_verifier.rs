@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use async_trait::async_trait;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use crate::config::auth::IntrospectionConfig;
+use crate::domain::model::user::AuthClaims;
+use crate::port::output::token_verifier::TokenVerifier;
+use super::error::{Error, Result};
+
+/// RFC 7662 style response with the role flags of `x-user-right`.
+#[derive(Debug, Deserialize)]
+struct IntrospectionResponse {
+    active: bool,
+    #[serde(default, alias = "id")]
+    sub: Option<String>,
+    #[serde(default)]
+    adm: bool,
+    #[serde(default)]
+    prv: bool,
+    #[serde(default)]
+    crt: bool,
+}
+
+/// Cache key, the raw token is never kept in memory past the request.
+type TokenHash = [u8; 32];
+
+fn token_hash(access_token: &str) -> TokenHash {
+    Sha256::digest(access_token.as_bytes()).into()
+}
+
+/// Asks the session owner whether `access_token` is still active.
+/// Active sessions are cached for `cache_secs`, up to `cache_max_entries` of them,
+/// so that every api call does not cost a round trip.
+pub struct IntrospectionTokenVerifier {
+    url: String,
+    client_secret: Option<String>,
+    client: reqwest::Client,
+    cache_ttl: Duration,
+    cache_max_entries: usize,
+    cache: Mutex<HashMap<TokenHash, (AuthClaims, Instant)>>,
+}
+
+impl IntrospectionTokenVerifier {
+    pub fn new(config: &IntrospectionConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(config.timeout_ms))
+            .build()
+            .expect("Failed to build http client");
+
+        Self {
+            url: config.url.clone(),
+            client_secret: config.client_secret_env.as_ref().and_then(|name| std::env::var(name).ok()),
+            client,
+            cache_ttl: Duration::from_secs(config.cache_secs),
+            cache_max_entries: config.cache_max_entries,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn cached(&self, access_token: &str) -> Option<AuthClaims> {
+        let cache = self.cache.lock().unwrap();
+        cache.get(&token_hash(access_token))
+            .filter(|(_, cached_at)| cached_at.elapsed() < self.cache_ttl)
+            .map(|(claims, _)| claims.clone())
+    }
+
+    fn store(&self, access_token: &str, claims: &AuthClaims) {
+        if self.cache_ttl.is_zero() || self.cache_max_entries == 0 {
+            return;
+        }
+        let mut cache = self.cache.lock().unwrap();
+        if cache.len() >= self.cache_max_entries {
+            cache.retain(|_, (_, cached_at)| cached_at.elapsed() < self.cache_ttl);
+        }
+        // --- still full of live sessions: the oldest one asks the endpoint again
+        if cache.len() >= self.cache_max_entries {
+            let oldest = cache.iter().min_by_key(|(_, (_, cached_at))| *cached_at).map(|(key, _)| *key);
+            if let Some(oldest) = oldest {
+                cache.remove(&oldest);
+            }
+        }
+        cache.insert(token_hash(access_token), (claims.clone(), Instant::now()));
+    }
+}
+
+#[async_trait]
+impl TokenVerifier for IntrospectionTokenVerifier {
+    async fn verify(&self, access_token: &str) -> Result<AuthClaims> {
+        if let Some(claims) = self.cached(access_token) {
+            return Ok(claims);
+        }
+
+        let mut request = self.client.post(&self.url).form(&[("token", access_token)]);
+        if let Some(client_secret) = &self.client_secret {
+            request = request.bearer_auth(client_secret);
+        }
+        let response = request
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| Error::IntrospectionFailed { message: e.to_string() })?
+            .json::<IntrospectionResponse>()
+            .await
+            .map_err(|e| Error::IntrospectionFailed { message: e.to_string() })?;
+
+        let sub = match response.sub {
+            Some(sub) if response.active => sub,
+            _ => return Err(Error::SessionInactive),
+        };
+        let claims = AuthClaims { sub, adm: response.adm, prv: response.prv, crt: response.crt };
+        self.store(access_token, &claims);
+        Ok(claims)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn verifier(cache_secs: u64, cache_max_entries: usize) -> IntrospectionTokenVerifier {
+        IntrospectionTokenVerifier::new(&IntrospectionConfig {
+            url: "http://localhost:8080/internal/sessions/introspect".to_string(),
+            client_secret_env: None,
+            timeout_ms: 1_000,
+            cache_secs,
+            cache_max_entries,
+        })
+    }
+
+    fn claims(sub: &str) -> AuthClaims {
+        AuthClaims { sub: sub.to_string(), adm: false, prv: false, crt: false }
+    }
+
+    #[test]
+    fn test_cache_is_bounded() {
+        let verifier = verifier(60, 2);
+        verifier.store("token-1", &claims("1"));
+        verifier.store("token-2", &claims("2"));
+        verifier.store("token-3", &claims("3"));
+
+        assert_eq!(verifier.cache.lock().unwrap().len(), 2);
+        assert!(verifier.cached("token-1").is_none());
+        assert_eq!(verifier.cached("token-3").map(|claims| claims.sub), Some("3".to_string()));
+        assert!(!verifier.cache.lock().unwrap().contains_key(&token_hash("token-1")));
+    }
+
+    #[test]
+    fn test_cache_disabled() {
+        let verifier = verifier(0, 2);
+        verifier.store("token-1", &claims("1"));
+        assert!(verifier.cached("token-1").is_none());
+    }
+}
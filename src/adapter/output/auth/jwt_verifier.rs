@@ -0,0 +1,148 @@
+use async_trait::async_trait;
+use jsonwebtoken::{decode, decode_header, jwk::JwkSet, Algorithm, DecodingKey, Validation};
+use crate::config::auth::{JwtAlgorithm, JwtConfig};
+use crate::domain::model::user::AuthClaims;
+use crate::port::output::token_verifier::TokenVerifier;
+use super::error::{Error, Result};
+
+enum JwtKeys {
+    Single(DecodingKey),
+    /// (kid, key) pairs from a JWKS document
+    Set(Vec<(Option<String>, DecodingKey)>),
+}
+
+/// Verifies `access_token` as a JWT signed by the main Ludium backend.
+/// `sub` (or `id`) becomes the user id, `adm`/`prv`/`crt` the roles. `exp` is required.
+pub struct JwtTokenVerifier {
+    keys: JwtKeys,
+    validation: Validation,
+}
+
+impl JwtTokenVerifier {
+    pub fn new(config: &JwtConfig) -> Result<Self> {
+        let (algorithm, keys) = match config.algorithm {
+            JwtAlgorithm::HS256 => {
+                let secret_env = config.secret_env.as_ref()
+                    .ok_or(Error::KeyLoadFailed { message: "HS256 requires secret_env".to_string() })?;
+                let secret = std::env::var(secret_env)
+                    .map_err(|_| Error::KeyLoadFailed { message: format!("{secret_env} is not set") })?;
+                (Algorithm::HS256, JwtKeys::Single(DecodingKey::from_secret(secret.as_bytes())))
+            }
+            JwtAlgorithm::RS256 => (Algorithm::RS256, Self::load_rsa_keys(config)?),
+        };
+
+        let mut validation = Validation::new(algorithm);
+        validation.leeway = config.leeway_secs;
+        // --- not `sub`: it is checked on the raw claims, `AuthClaims` takes the user id as `sub` or `id`
+        validation.set_required_spec_claims(&["exp"]);
+        if let Some(issuer) = &config.issuer {
+            validation.set_issuer(&[issuer]);
+        }
+        match &config.audience {
+            Some(audience) => validation.set_audience(&[audience]),
+            None => validation.validate_aud = false,
+        }
+
+        Ok(Self { keys, validation })
+    }
+
+    fn load_rsa_keys(config: &JwtConfig) -> Result<JwtKeys> {
+        if let Some(jwks_file) = &config.jwks_file {
+            let content = std::fs::read_to_string(jwks_file)
+                .map_err(|e| Error::KeyLoadFailed { message: format!("{jwks_file}: {e}") })?;
+            let jwks: JwkSet = serde_json::from_str(&content)
+                .map_err(|e| Error::KeyLoadFailed { message: format!("{jwks_file}: {e}") })?;
+            let keys = jwks.keys
+                .iter()
+                .map(|jwk| Ok((jwk.common.key_id.clone(), DecodingKey::from_jwk(jwk)?)))
+                .collect::<Result<Vec<_>>>()?;
+            return Ok(JwtKeys::Set(keys));
+        }
+
+        let public_key_file = config.public_key_file.as_ref()
+            .ok_or(Error::KeyLoadFailed { message: "RS256 requires jwks_file or public_key_file".to_string() })?;
+        let pem = std::fs::read(public_key_file)
+            .map_err(|e| Error::KeyLoadFailed { message: format!("{public_key_file}: {e}") })?;
+        Ok(JwtKeys::Single(DecodingKey::from_rsa_pem(&pem)?))
+    }
+
+    fn decoding_key(&self, token: &str) -> Result<&DecodingKey> {
+        match &self.keys {
+            JwtKeys::Single(key) => Ok(key),
+            JwtKeys::Set(keys) => {
+                let kid = decode_header(token)?.kid;
+                let found = match &kid {
+                    Some(_) => keys.iter().find(|(key_id, _)| key_id == &kid),
+                    // a single key set does not need a kid
+                    None if keys.len() == 1 => keys.first(),
+                    None => None,
+                };
+                found.map(|(_, key)| key).ok_or(Error::KeyNotFound { kid })
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl TokenVerifier for JwtTokenVerifier {
+    async fn verify(&self, access_token: &str) -> Result<AuthClaims> {
+        let key = self.decoding_key(access_token)?;
+        let token_data = decode::<AuthClaims>(access_token, key, &self.validation)?;
+        Ok(token_data.claims)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonwebtoken::{encode, EncodingKey, Header};
+    use serde_json::json;
+
+    const SECRET_ENV: &str = "TEST_JWT_VERIFIER_SECRET";
+
+    fn hs256_verifier() -> JwtTokenVerifier {
+        std::env::set_var(SECRET_ENV, "test-secret");
+        JwtTokenVerifier::new(&JwtConfig {
+            algorithm: JwtAlgorithm::HS256,
+            secret_env: Some(SECRET_ENV.to_string()),
+            public_key_file: None,
+            jwks_file: None,
+            issuer: Some("ludium".to_string()),
+            audience: None,
+            leeway_secs: 0,
+        }).unwrap()
+    }
+
+    fn token(secret: &str, claims: serde_json::Value) -> String {
+        encode(&Header::default(), &claims, &EncodingKey::from_secret(secret.as_bytes())).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_verify_hs256() {
+        let verifier = hs256_verifier();
+        let exp = chrono::Utc::now().timestamp() + 60;
+
+        let claims = verifier.verify(&token("test-secret", json!({ "sub": "user-1", "adm": true, "iss": "ludium", "exp": exp }))).await.unwrap();
+        assert_eq!(claims.sub, "user-1");
+        assert!(claims.adm);
+        assert!(!claims.prv);
+
+        // --- forged signature, wrong issuer, expired
+        assert!(verifier.verify(&token("other-secret", json!({ "sub": "user-1", "adm": true, "iss": "ludium", "exp": exp }))).await.is_err());
+        assert!(verifier.verify(&token("test-secret", json!({ "sub": "user-1", "iss": "someone", "exp": exp }))).await.is_err());
+        assert!(verifier.verify(&token("test-secret", json!({ "sub": "user-1", "iss": "ludium", "exp": exp - 120 }))).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_verify_hs256_id_claim() {
+        let verifier = hs256_verifier();
+        let exp = chrono::Utc::now().timestamp() + 60;
+
+        let claims = verifier.verify(&token("test-secret", json!({ "id": "user-1", "prv": true, "iss": "ludium", "exp": exp }))).await.unwrap();
+        assert_eq!(claims.sub, "user-1");
+        assert!(claims.prv);
+
+        // --- no user id at all
+        assert!(verifier.verify(&token("test-secret", json!({ "iss": "ludium", "exp": exp }))).await.is_err());
+    }
+}
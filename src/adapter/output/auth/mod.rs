@@ -0,0 +1,3 @@
+pub mod error;
+pub mod jwt_verifier;
+pub mod introspection_verifier;
@@ -0,0 +1,34 @@
+use serde::Serialize;
+use serde_with::serde_as;
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[serde_as]
+#[derive(Debug, Serialize, Clone)]
+pub enum Error {
+	RequestFailed {
+		consumer: String,
+		message: String,
+	},
+	UnexpectedStatus {
+		consumer: String,
+		status: u16,
+	},
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Error {
+	pub fn status_code(&self) -> Option<u16> {
+		match self {
+			Self::UnexpectedStatus { status, .. } => Some(*status),
+			Self::RequestFailed { .. } => None,
+		}
+	}
+}
@@ -0,0 +1,116 @@
+use std::time::Duration;
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use crate::config::outbox::OutboxConsumerConfig;
+use crate::domain::model::outbox_event::OutboxEventMessage;
+use crate::port::output::event_consumer::EventConsumer;
+use super::error::{Error, Result};
+
+type HmacSha256 = Hmac<Sha256>;
+
+pub const EVENT_ID_HEADER: &str = "x-ludium-event-id";
+pub const EVENT_TYPE_HEADER: &str = "x-ludium-event-type";
+pub const TIMESTAMP_HEADER: &str = "x-ludium-timestamp";
+pub const SIGNATURE_HEADER: &str = "x-ludium-signature";
+
+/// Signs `"{timestamp}.{body}"` with the endpoint secret: `v1=<hex(HMAC-SHA256)>`.
+///
+/// Receivers recompute the signature over the raw body and reject requests whose
+/// `x-ludium-timestamp` is too old (we recommend 5 minutes) to prevent replays.
+pub fn sign_payload(secret: &[u8], timestamp: i64, body: &[u8]) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC can take key of any size");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!("v1={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// POSTs every accepted event as JSON to a single webhook endpoint.
+/// Any non-2xx response counts as a failed delivery and is retried by the dispatcher.
+pub struct WebhookConsumer {
+    name: String,
+    url: String,
+    secret: Option<String>,
+    event_types: Vec<String>,
+    client: reqwest::Client,
+}
+
+impl WebhookConsumer {
+    pub fn new(config: &OutboxConsumerConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(config.timeout_ms))
+            .build()
+            .expect("Failed to build http client");
+
+        let secret = config.secret();
+        if secret.is_none() {
+            tracing::warn!("[webhook] endpoint {} has no secret, deliveries will not be signed", config.name);
+        }
+
+        Self {
+            name: config.name.clone(),
+            url: config.url.clone(),
+            secret,
+            event_types: config.event_types.clone(),
+            client,
+        }
+    }
+}
+
+#[async_trait]
+impl EventConsumer for WebhookConsumer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn endpoint(&self) -> &str {
+        &self.url
+    }
+
+    fn accepts(&self, event_type: &str) -> bool {
+        self.event_types.is_empty() || self.event_types.iter().any(|t| t == event_type)
+    }
+
+    async fn deliver(&self, message: &OutboxEventMessage) -> Result<u16> {
+        let body = serde_json::to_vec(message)
+            .map_err(|e| Error::RequestFailed { consumer: self.name.clone(), message: e.to_string() })?;
+        let timestamp = chrono::Utc::now().timestamp();
+
+        let mut request = self.client
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(EVENT_ID_HEADER, &message.id)
+            .header(EVENT_TYPE_HEADER, &message.event_type)
+            .header(TIMESTAMP_HEADER, timestamp.to_string());
+        if let Some(secret) = &self.secret {
+            request = request.header(SIGNATURE_HEADER, sign_payload(secret.as_bytes(), timestamp, &body));
+        }
+
+        let response = request
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| Error::RequestFailed { consumer: self.name.clone(), message: e.to_string() })?;
+
+        let status = response.status().as_u16();
+        if !response.status().is_success() {
+            return Err(Error::UnexpectedStatus { consumer: self.name.clone(), status });
+        }
+        Ok(status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sign_payload;
+
+    #[test]
+    fn test_sign_payload() {
+        let signature = sign_payload(b"whsec_test", 1700000000, br#"{"id":"1"}"#);
+        assert_eq!(signature, "v1=11bf4466ea17c3df3fd743af0b435368e16b7a05eb8eced85e8c4670767bdec5");
+
+        let other_timestamp = sign_payload(b"whsec_test", 1700000001, br#"{"id":"1"}"#);
+        assert_ne!(signature, other_timestamp);
+    }
+}
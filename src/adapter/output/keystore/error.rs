@@ -0,0 +1,28 @@
+use serde::Serialize;
+use serde_with::serde_as;
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[serde_as]
+#[derive(Debug, Serialize, Clone)]
+pub enum Error {
+	ReadFailed { message: String },
+	WriteFailed { message: String },
+	InvalidFormat { message: String },
+	UnsupportedVersion { version: u32 },
+	UnsupportedAlgorithm { algorithm: String },
+	KeyDerivationFailed { message: String },
+	EncryptionFailed,
+	/// wrong passphrase or a modified file, aes-gcm can not tell them apart
+	DecryptionFailed,
+	PassphraseNotSet,
+	PassphraseReadFailed { message: String },
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,279 @@
+pub mod error;
+
+use std::io::{Read, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+use self::error::{Error, Result};
+
+pub const KEYSTORE_VERSION: u32 = 1;
+pub const KDF_ARGON2ID: &str = "argon2id";
+pub const CIPHER_AES_256_GCM: &str = "aes-256-gcm";
+
+/// Passphrase of the keys file. It stays in the process environment, prefer `KEYSTORE_PASSPHRASE_FD`.
+pub const PASSPHRASE_ENV: &str = "KEYSTORE_PASSPHRASE";
+/// File descriptor to read the passphrase from, e.g. `KEYSTORE_PASSPHRASE_FD=3 payment 3< secret`. Readable once.
+pub const PASSPHRASE_FD_ENV: &str = "KEYSTORE_PASSPHRASE_FD";
+
+const KEY_LEN: usize = 32;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+/// Set once the descriptor is taken over, a second `from_raw_fd` on the same number could close a file opened since.
+static PASSPHRASE_FD_TAKEN: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KdfParams {
+    pub algorithm: String,
+    pub salt: String,
+    /// memory in KiB
+    pub m_cost: u32,
+    pub t_cost: u32,
+    pub p_cost: u32,
+}
+
+impl KdfParams {
+    /// argon2id with the OWASP minimum of 19 MiB, 2 passes
+    pub fn new_argon2id() -> Self {
+        Self::with_costs(19 * 1024, 2, 1)
+    }
+
+    pub fn with_costs(m_cost: u32, t_cost: u32, p_cost: u32) -> Self {
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        Self {
+            algorithm: KDF_ARGON2ID.to_string(),
+            salt: BASE64.encode(salt),
+            m_cost,
+            t_cost,
+            p_cost,
+        }
+    }
+
+    fn derive_key(&self, passphrase: &str) -> Result<Zeroizing<[u8; KEY_LEN]>> {
+        if self.algorithm != KDF_ARGON2ID {
+            return Err(Error::UnsupportedAlgorithm { algorithm: self.algorithm.clone() });
+        }
+        let salt = decode_base64("salt", &self.salt)?;
+        let params = Params::new(self.m_cost, self.t_cost, self.p_cost, Some(KEY_LEN))
+            .map_err(|e| Error::KeyDerivationFailed { message: e.to_string() })?;
+
+        let mut key = Zeroizing::new([0u8; KEY_LEN]);
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(passphrase.as_bytes(), &salt, key.as_mut())
+            .map_err(|e| Error::KeyDerivationFailed { message: e.to_string() })?;
+        Ok(key)
+    }
+}
+
+/// Keys file encrypted with a passphrase, the plaintext is the usual signer array.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncryptedKeystore {
+    pub version: u32,
+    pub kdf: KdfParams,
+    pub cipher: String,
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+impl EncryptedKeystore {
+    pub fn encrypt(plaintext: &[u8], passphrase: &str, kdf: KdfParams) -> Result<Self> {
+        let key = kdf.derive_key(passphrase)?;
+        let mut nonce = [0u8; NONCE_LEN];
+        OsRng.fill_bytes(&mut nonce);
+
+        let ciphertext = Aes256Gcm::new_from_slice(key.as_ref())
+            .map_err(|_| Error::EncryptionFailed)?
+            .encrypt(Nonce::from_slice(&nonce), plaintext)
+            .map_err(|_| Error::EncryptionFailed)?;
+
+        Ok(Self {
+            version: KEYSTORE_VERSION,
+            kdf,
+            cipher: CIPHER_AES_256_GCM.to_string(),
+            nonce: BASE64.encode(nonce),
+            ciphertext: BASE64.encode(ciphertext),
+        })
+    }
+
+    pub fn decrypt(&self, passphrase: &str) -> Result<Zeroizing<Vec<u8>>> {
+        if self.version != KEYSTORE_VERSION {
+            return Err(Error::UnsupportedVersion { version: self.version });
+        }
+        if self.cipher != CIPHER_AES_256_GCM {
+            return Err(Error::UnsupportedAlgorithm { algorithm: self.cipher.clone() });
+        }
+        let nonce = decode_base64("nonce", &self.nonce)?;
+        if nonce.len() != NONCE_LEN {
+            return Err(Error::InvalidFormat { message: format!("nonce must be {} bytes", NONCE_LEN) });
+        }
+        let ciphertext = decode_base64("ciphertext", &self.ciphertext)?;
+        let key = self.kdf.derive_key(passphrase)?;
+
+        Aes256Gcm::new_from_slice(key.as_ref())
+            .map_err(|_| Error::DecryptionFailed)?
+            .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+            .map(Zeroizing::new)
+            .map_err(|_| Error::DecryptionFailed)
+    }
+
+    /// `None` for plaintext keys files, which are a json array.
+    pub fn parse(contents: &[u8]) -> Result<Option<Self>> {
+        match serde_json::from_slice::<serde_json::Value>(contents) {
+            Ok(serde_json::Value::Object(_)) => serde_json::from_slice(contents)
+                .map(Some)
+                .map_err(|e| Error::InvalidFormat { message: e.to_string() }),
+            Ok(_) => Ok(None),
+            Err(e) => Err(Error::InvalidFormat { message: e.to_string() }),
+        }
+    }
+}
+
+fn decode_base64(field: &str, value: &str) -> Result<Vec<u8>> {
+    BASE64.decode(value).map_err(|e| Error::InvalidFormat { message: format!("{field}: {e}") })
+}
+
+/// Keys files hold secrets, new ones are only readable by the owner.
+pub fn write_private_file(path: &Path, contents: &[u8]) -> Result<()> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    options.open(path)
+        .and_then(|mut file| file.write_all(contents))
+        .map_err(|e| Error::WriteFailed { message: format!("{}: {e}", path.display()) })
+}
+
+/// Reads the passphrase from `KEYSTORE_PASSPHRASE` or the descriptor in `KEYSTORE_PASSPHRASE_FD`.
+/// The environment is left as is, changing it is not sound once the runtime threads run.
+pub fn read_passphrase() -> Result<Zeroizing<String>> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        let passphrase = Zeroizing::new(passphrase);
+        if !passphrase.is_empty() {
+            return Ok(passphrase);
+        }
+    }
+
+    let fd = std::env::var(PASSPHRASE_FD_ENV).map_err(|_| Error::PassphraseNotSet)?;
+    let fd: i32 = fd.parse()
+        .map_err(|_| Error::PassphraseReadFailed { message: format!("{PASSPHRASE_FD_ENV} is not a file descriptor: {fd}") })?;
+    read_passphrase_fd(fd)
+}
+
+/// Passphrase of the encrypted keys file, read on first use and kept by the config owning the keys file:
+/// the descriptor is readable once and key reloads need the passphrase again. Never printed.
+#[derive(Default)]
+pub struct KeystorePassphrase(OnceLock<Zeroizing<String>>);
+
+impl KeystorePassphrase {
+    pub fn get(&self) -> Result<&str> {
+        if let Some(passphrase) = self.0.get() {
+            return Ok(passphrase.as_str());
+        }
+        let passphrase = read_passphrase()?;
+        Ok(self.0.get_or_init(|| passphrase).as_str())
+    }
+}
+
+impl core::fmt::Debug for KeystorePassphrase {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("KeystorePassphrase(..)")
+    }
+}
+
+/// Only a descriptor past stdio that is open on a file or a pipe is taken, and only once.
+#[cfg(unix)]
+fn validate_passphrase_fd(fd: i32) -> Result<()> {
+    use std::os::unix::fs::FileTypeExt;
+
+    let invalid = |reason: String| Error::PassphraseReadFailed { message: format!("{PASSPHRASE_FD_ENV}={fd} {reason}") };
+    if fd <= 2 {
+        return Err(invalid("is stdin, stdout or stderr".to_string()));
+    }
+    let file_type = std::fs::metadata(format!("/dev/fd/{fd}"))
+        .map_err(|e| invalid(format!("is not an open descriptor: {e}")))?
+        .file_type();
+    if !file_type.is_file() && !file_type.is_fifo() {
+        return Err(invalid("is not a file or a pipe".to_string()));
+    }
+    if PASSPHRASE_FD_TAKEN.swap(true, Ordering::SeqCst) {
+        return Err(invalid("was already read".to_string()));
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn read_passphrase_fd(fd: i32) -> Result<Zeroizing<String>> {
+    use std::os::unix::io::FromRawFd;
+
+    validate_passphrase_fd(fd)?;
+    // SAFETY: the descriptor is open on a file or pipe handed to this process for the passphrase,
+    // it is taken over once (`PASSPHRASE_FD_TAKEN`) and closed after the read.
+    let mut file = unsafe { std::fs::File::from_raw_fd(fd) };
+    let mut passphrase = Zeroizing::new(String::new());
+    file.read_to_string(&mut passphrase)
+        .map_err(|e| Error::PassphraseReadFailed { message: e.to_string() })?;
+
+    let trimmed_len = passphrase.trim_end_matches(['\r', '\n']).len();
+    passphrase.truncate(trimmed_len);
+    Ok(passphrase)
+}
+
+#[cfg(not(unix))]
+fn read_passphrase_fd(_fd: i32) -> Result<Zeroizing<String>> {
+    Err(Error::PassphraseReadFailed { message: format!("{PASSPHRASE_FD_ENV} is only supported on unix") })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEYS: &[u8] = br#"[{"account_id":"payout.testnet","public_key":"ed25519:pub","secret_key":"ed25519:secret"}]"#;
+
+    fn cheap_kdf() -> KdfParams {
+        KdfParams::with_costs(8, 1, 1)
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_roundtrip() {
+        let keystore = EncryptedKeystore::encrypt(KEYS, "correct horse", cheap_kdf()).unwrap();
+        let contents = serde_json::to_vec(&keystore).unwrap();
+
+        assert!(!String::from_utf8_lossy(&contents).contains("ed25519:secret"));
+        let parsed = EncryptedKeystore::parse(&contents).unwrap().expect("encrypted keystore");
+        assert_eq!(parsed.decrypt("correct horse").unwrap().as_slice(), KEYS);
+    }
+
+    #[test]
+    fn test_decrypt_with_wrong_passphrase() {
+        let keystore = EncryptedKeystore::encrypt(KEYS, "correct horse", cheap_kdf()).unwrap();
+
+        assert!(matches!(keystore.decrypt("battery staple"), Err(Error::DecryptionFailed)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_validate_passphrase_fd() {
+        assert!(matches!(validate_passphrase_fd(0), Err(Error::PassphraseReadFailed { .. })));
+        assert!(matches!(validate_passphrase_fd(2), Err(Error::PassphraseReadFailed { .. })));
+        assert!(matches!(validate_passphrase_fd(i32::MAX), Err(Error::PassphraseReadFailed { .. })));
+        assert!(!PASSPHRASE_FD_TAKEN.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_parse_plaintext_keys_file() {
+        assert!(EncryptedKeystore::parse(KEYS).unwrap().is_none());
+        assert!(matches!(EncryptedKeystore::parse(b"not json"), Err(Error::InvalidFormat { .. })));
+    }
+}
@@ -1,2 +1,7 @@
 pub mod persistence;
-pub mod near;
\ No newline at end of file
+pub mod near;
+pub mod event;
+pub mod auth;
+pub mod rate_limit;
+pub mod price;
+pub mod keystore;
@@ -0,0 +1,204 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use crate::config::circuit_breaker::CircuitBreakerConfig;
+use crate::config::retry::ErrorClass;
+use crate::domain::model::near::{CircuitBreakerStatus, CircuitState};
+use super::endpoint::is_endpoint_failure;
+
+#[derive(Debug)]
+enum State {
+    Closed { consecutive_failures: u32 },
+    Open { until: Instant },
+    HalfOpen { in_flight: u32 },
+}
+
+/// Stops rpc calls after `failure_threshold` consecutive calls failed on every endpoint,
+/// so callers fail fast instead of paying the full retry budget each.
+/// After `open_secs` a few trial calls go through: one success closes it, one failure opens it again.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    state: Mutex<State>,
+    opened_count: AtomicU64,
+    rejected_count: AtomicU64,
+}
+
+impl CircuitBreaker {
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(State::Closed { consecutive_failures: 0 }),
+            opened_count: AtomicU64::new(0),
+            rejected_count: AtomicU64::new(0),
+        }
+    }
+
+    /// A permit for one call, `Err` with the time left while the breaker is open or its trial calls are taken.
+    pub fn acquire(&self) -> Result<BreakerPermit<'_>, Duration> {
+        let mut state = self.state.lock().expect("circuit breaker lock poisoned");
+        if let Some(retry_in) = self.rejects(&state) {
+            self.rejected_count.fetch_add(1, Ordering::Relaxed);
+            return Err(retry_in);
+        }
+        if matches!(*state, State::Open { .. }) {
+            tracing::info!("[rpc] circuit breaker half-open, letting trial calls through");
+            *state = State::HalfOpen { in_flight: 0 };
+        }
+
+        let trial = matches!(*state, State::HalfOpen { .. });
+        if let State::HalfOpen { in_flight } = &mut *state {
+            *in_flight += 1;
+        }
+        Ok(BreakerPermit { breaker: self, trial, done: false })
+    }
+
+    /// Whether a call would be let through right now, without taking a permit.
+    pub fn check(&self) -> Result<(), Duration> {
+        let state = self.state.lock().expect("circuit breaker lock poisoned");
+        match self.rejects(&state) {
+            Some(retry_in) => {
+                self.rejected_count.fetch_add(1, Ordering::Relaxed);
+                Err(retry_in)
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn rejects(&self, state: &State) -> Option<Duration> {
+        match *state {
+            State::Closed { .. } => None,
+            State::Open { until } => until.checked_duration_since(Instant::now()).filter(|retry_in| !retry_in.is_zero()),
+            State::HalfOpen { in_flight } if in_flight >= self.config.half_open_max_calls.max(1) => Some(Duration::ZERO),
+            State::HalfOpen { .. } => None,
+        }
+    }
+
+    pub fn status(&self) -> CircuitBreakerStatus {
+        let state = self.state.lock().expect("circuit breaker lock poisoned");
+        let (circuit_state, consecutive_failures, retry_in_secs) = match *state {
+            State::Closed { consecutive_failures } => (CircuitState::Closed, consecutive_failures, None),
+            State::Open { until } => {
+                (CircuitState::Open, self.config.failure_threshold, Some(until.saturating_duration_since(Instant::now()).as_secs()))
+            }
+            State::HalfOpen { .. } => (CircuitState::HalfOpen, self.config.failure_threshold, None),
+        };
+        CircuitBreakerStatus {
+            state: circuit_state,
+            consecutive_failures,
+            retry_in_secs,
+            opened_count: self.opened_count.load(Ordering::Relaxed),
+            rejected_count: self.rejected_count.load(Ordering::Relaxed),
+        }
+    }
+
+    fn on_success(&self, trial: bool) {
+        let mut state = self.state.lock().expect("circuit breaker lock poisoned");
+        if trial {
+            tracing::info!("[rpc] circuit breaker closed, trial call succeeded");
+        }
+        // --- a late success of a call started before the breaker opened closes it just the same
+        *state = State::Closed { consecutive_failures: 0 };
+    }
+
+    fn on_failure(&self, trial: bool) {
+        let mut state = self.state.lock().expect("circuit breaker lock poisoned");
+        let open = match *state {
+            State::Closed { consecutive_failures } => {
+                let consecutive_failures = consecutive_failures + 1;
+                *state = State::Closed { consecutive_failures };
+                consecutive_failures >= self.config.failure_threshold
+            }
+            State::HalfOpen { .. } => trial,
+            State::Open { .. } => false,
+        };
+        if open {
+            tracing::error!("[rpc] circuit breaker open for {}s, rpc calls fail fast", self.config.open_secs);
+            *state = State::Open { until: Instant::now() + self.config.open_duration() };
+            self.opened_count.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn release(&self, trial: bool) {
+        if !trial {
+            return;
+        }
+        let mut state = self.state.lock().expect("circuit breaker lock poisoned");
+        if let State::HalfOpen { in_flight } = &mut *state {
+            *in_flight = in_flight.saturating_sub(1);
+        }
+    }
+}
+
+/// One call let through by the breaker. Dropped without an outcome, e.g. a cancelled call, it counts as neither.
+pub struct BreakerPermit<'a> {
+    breaker: &'a CircuitBreaker,
+    trial: bool,
+    done: bool,
+}
+
+impl BreakerPermit<'_> {
+    /// Records how the call ended. Only errors of the endpoints count as failures,
+    /// an answer of any kind shows the provider is up.
+    pub fn record(mut self, error_class: Option<ErrorClass>) {
+        self.done = true;
+        self.breaker.release(self.trial);
+        match error_class {
+            Some(class) if is_endpoint_failure(class) => self.breaker.on_failure(self.trial),
+            _ => self.breaker.on_success(self.trial),
+        }
+    }
+}
+
+impl Drop for BreakerPermit<'_> {
+    fn drop(&mut self) {
+        if !self.done {
+            self.breaker.release(self.trial);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker(open_secs: u64) -> CircuitBreaker {
+        CircuitBreaker::new(CircuitBreakerConfig { failure_threshold: 2, open_secs, half_open_max_calls: 1 })
+    }
+
+    #[test]
+    fn test_opens_after_consecutive_failures() {
+        let breaker = breaker(60);
+
+        breaker.acquire().unwrap().record(Some(ErrorClass::Transport));
+        breaker.acquire().unwrap().record(Some(ErrorClass::Rejected));
+        breaker.acquire().unwrap().record(Some(ErrorClass::Timeout));
+        assert_eq!(breaker.status().state, CircuitState::Closed);
+        assert_eq!(breaker.status().consecutive_failures, 1);
+
+        breaker.acquire().unwrap().record(Some(ErrorClass::ServerError));
+        assert_eq!(breaker.status().state, CircuitState::Open);
+        assert!(breaker.acquire().is_err());
+        assert_eq!((breaker.status().opened_count, breaker.status().rejected_count), (1, 1));
+    }
+
+    #[test]
+    fn test_half_open_trial_calls() {
+        let breaker = breaker(0);
+        breaker.acquire().unwrap().record(Some(ErrorClass::Transport));
+        breaker.acquire().unwrap().record(Some(ErrorClass::Transport));
+
+        // --- one trial call at a time, a failed trial opens it again
+        let trial = breaker.acquire().unwrap();
+        assert_eq!(breaker.status().state, CircuitState::HalfOpen);
+        assert!(breaker.acquire().is_err());
+        trial.record(Some(ErrorClass::RateLimited));
+        assert_eq!(breaker.status().opened_count, 2);
+
+        // --- a dropped trial frees its slot, a successful one closes the breaker
+        drop(breaker.acquire().unwrap());
+        breaker.acquire().unwrap().record(None);
+        assert_eq!(breaker.status().state, CircuitState::Closed);
+        assert_eq!(breaker.status().consecutive_failures, 0);
+    }
+}
@@ -0,0 +1,217 @@
+use std::fmt::Debug;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use near_jsonrpc_client::errors::JsonRpcError;
+use near_jsonrpc_client::methods::status::RpcStatusRequest;
+use near_jsonrpc_client::methods::RpcMethod;
+use near_jsonrpc_client::JsonRpcClient;
+use tokio::time::MissedTickBehavior;
+use crate::config::retry::ErrorClass;
+use crate::domain::model::near::RpcEndpointStatus;
+use super::retry::{classify, HandlerErrorClass};
+
+#[derive(Debug, Clone)]
+struct EndpointHealth {
+    reachable: bool,
+    lagging: bool,
+    latency_ms: Option<u64>,
+    block_height: Option<u64>,
+    block_lag: Option<u64>,
+    last_error: Option<String>,
+}
+
+impl EndpointHealth {
+    fn is_healthy(&self) -> bool {
+        self.reachable && !self.lagging
+    }
+}
+
+impl Default for EndpointHealth {
+    fn default() -> Self {
+        Self { reachable: true, lagging: false, latency_ms: None, block_height: None, block_lag: None, last_error: None }
+    }
+}
+
+pub struct RpcEndpoint {
+    url: String,
+    priority: u32,
+    client: JsonRpcClient,
+    health: RwLock<EndpointHealth>,
+}
+
+impl Debug for RpcEndpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RpcEndpoint({}, priority {})", self.url, self.priority)
+    }
+}
+
+impl RpcEndpoint {
+    pub fn new(url: String, priority: u32, client: JsonRpcClient) -> Self {
+        Self { url, priority, client, health: RwLock::new(EndpointHealth::default()) }
+    }
+
+    fn health(&self) -> EndpointHealth {
+        self.health.read().expect("endpoint health lock poisoned").clone()
+    }
+
+    fn set_reachable(&self, reachable: bool, last_error: Option<String>) {
+        let mut health = self.health.write().expect("endpoint health lock poisoned");
+        health.reachable = reachable;
+        if last_error.is_some() {
+            health.last_error = last_error;
+        }
+    }
+}
+
+/// Rpc endpoints in priority order. Calls go to the first healthy one and fail over to the next on errors
+/// that say nothing about the request itself: timeouts, transport errors, 5xx and 429.
+#[derive(Debug)]
+pub struct RpcEndpointPool {
+    endpoints: Vec<RpcEndpoint>,
+    max_block_lag: u64,
+}
+
+impl RpcEndpointPool {
+    pub fn new(mut endpoints: Vec<RpcEndpoint>, max_block_lag: u64) -> Self {
+        assert!(!endpoints.is_empty(), "at least one rpc endpoint is required");
+        endpoints.sort_by_key(|endpoint| endpoint.priority);
+        Self { endpoints, max_block_lag }
+    }
+
+    /// Healthy endpoints by priority, then the unhealthy ones as a last resort.
+    fn ordered(&self) -> Vec<&RpcEndpoint> {
+        let (healthy, unhealthy): (Vec<&RpcEndpoint>, Vec<&RpcEndpoint>) = self.endpoints
+            .iter()
+            .partition(|endpoint| endpoint.health().is_healthy());
+        healthy.into_iter().chain(unhealthy).collect()
+    }
+
+    /// Sends `request()` to one endpoint after the other until one answers.
+    /// Only for requests that are safe to send twice: view calls, and `send_tx` of the same signed transaction.
+    pub async fn call<M, F>(&self, request: &F) -> Result<M::Response, JsonRpcError<M::Error>>
+    where
+        M: RpcMethod,
+        M::Error: HandlerErrorClass + Debug,
+        F: Fn() -> M,
+    {
+        let mut last_err = None;
+        for endpoint in self.ordered() {
+            match endpoint.client.call(request()).await {
+                Ok(response) => {
+                    endpoint.set_reachable(true, None);
+                    return Ok(response);
+                }
+                Err(err) => {
+                    let class = classify(&err);
+                    if !is_endpoint_failure(class) {
+                        return Err(err);
+                    }
+                    tracing::warn!("rpc {} failed ({class}), trying the next endpoint: {err:?}", endpoint.url);
+                    endpoint.set_reachable(false, Some(format!("{class}: {err:?}")));
+                    last_err = Some(err);
+                }
+            }
+        }
+        Err(last_err.expect("the pool has endpoints"))
+    }
+
+    /// Asks every endpoint for its `status`. Endpoints that do not answer, are syncing,
+    /// or are more than `max_block_lag` blocks behind the highest one are moved to the back.
+    pub async fn probe(&self) {
+        let mut probes = Vec::with_capacity(self.endpoints.len());
+        for endpoint in self.endpoints.iter() {
+            let started = Instant::now();
+            let status = endpoint.client.call(RpcStatusRequest).await;
+            probes.push((endpoint, started.elapsed(), status));
+        }
+
+        let highest = probes
+            .iter()
+            .filter_map(|(_, _, status)| status.as_ref().ok().map(|status| status.sync_info.latest_block_height))
+            .max();
+
+        for (endpoint, latency, status) in probes {
+            let mut health = endpoint.health.write().expect("endpoint health lock poisoned");
+            match status {
+                Ok(status) => {
+                    let height = status.sync_info.latest_block_height;
+                    let block_lag = highest.map(|highest| highest.saturating_sub(height));
+                    health.reachable = true;
+                    health.lagging = status.sync_info.syncing || block_lag.is_some_and(|lag| lag > self.max_block_lag);
+                    health.latency_ms = Some(latency.as_millis() as u64);
+                    health.block_height = Some(height);
+                    health.block_lag = block_lag;
+                    health.last_error = None;
+                }
+                Err(err) => {
+                    tracing::warn!("rpc {} probe failed: {err:?}", endpoint.url);
+                    health.reachable = false;
+                    health.latency_ms = None;
+                    health.last_error = Some(format!("probe: {err:?}"));
+                }
+            }
+        }
+    }
+
+    /// Probes the endpoints every `interval`.
+    pub async fn run_probes(self: Arc<Self>, interval: Duration) {
+        let mut interval = tokio::time::interval(interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            self.probe().await;
+        }
+    }
+
+    pub fn statuses(&self) -> Vec<RpcEndpointStatus> {
+        self.endpoints
+            .iter()
+            .map(|endpoint| {
+                let health = endpoint.health();
+                RpcEndpointStatus {
+                    url: endpoint.url.clone(),
+                    priority: endpoint.priority,
+                    healthy: health.is_healthy(),
+                    reachable: health.reachable,
+                    lagging: health.lagging,
+                    latency_ms: health.latency_ms,
+                    block_height: health.block_height,
+                    block_lag: health.block_lag,
+                    last_error: health.last_error,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Errors of the endpoint rather than the request, another endpoint may well answer.
+pub fn is_endpoint_failure(class: ErrorClass) -> bool {
+    matches!(class, ErrorClass::Timeout | ErrorClass::Transport | ErrorClass::ServerError | ErrorClass::RateLimited)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn endpoint(url: &str, priority: u32) -> RpcEndpoint {
+        RpcEndpoint::new(url.to_string(), priority, JsonRpcClient::connect(url))
+    }
+
+    #[test]
+    fn test_ordered_endpoints() {
+        let pool = RpcEndpointPool::new(
+            vec![endpoint("http://backup:3030", 1), endpoint("http://primary:3030", 0), endpoint("http://spare:3030", 2)],
+            30,
+        );
+        let urls = |pool: &RpcEndpointPool| pool.ordered().iter().map(|endpoint| endpoint.url.clone()).collect::<Vec<String>>();
+        assert_eq!(urls(&pool), vec!["http://primary:3030", "http://backup:3030", "http://spare:3030"]);
+
+        pool.endpoints[0].set_reachable(false, Some("timeout".to_string()));
+        assert_eq!(urls(&pool), vec!["http://backup:3030", "http://spare:3030", "http://primary:3030"]);
+
+        pool.endpoints[0].set_reachable(true, None);
+        pool.endpoints[1].health.write().unwrap().lagging = true;
+        assert_eq!(urls(&pool), vec!["http://primary:3030", "http://spare:3030", "http://backup:3030"]);
+        assert!(!pool.statuses()[1].healthy);
+    }
+}
@@ -27,9 +27,18 @@ pub enum Error {
 	CheckStorageDepositFailed { 
         message: String,
     },
+	ViewCallFailed {
+		message: String,
+	},
 	InternalServerError {
 		message: String,
-	}
+	},
+
+	// --- 503
+	/// the rpc circuit breaker is open, nothing was sent
+	RpcUnavailable {
+		retry_in_secs: u64,
+	},
 }
 
 impl core::fmt::Display for Error {
@@ -72,10 +81,18 @@ impl Error {
 				StatusCode::INTERNAL_SERVER_ERROR,
 				message.to_string(),
 			),
+			Self::ViewCallFailed { message } => (
+				StatusCode::BAD_GATEWAY,
+				message.to_string(),
+			),
 			Self::InternalServerError { message } => (
 				StatusCode::INTERNAL_SERVER_ERROR,
 				message.to_string(),
 			),
+			Self::RpcUnavailable { retry_in_secs } => (
+				StatusCode::SERVICE_UNAVAILABLE,
+				format!("Near rpc is unavailable, retry in {retry_in_secs}s"),
+			),
 		}
 	}
 }
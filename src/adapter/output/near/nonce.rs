@@ -0,0 +1,275 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use near_crypto::{InMemorySigner, PublicKey};
+use near_primitives::hash::CryptoHash;
+use crate::domain::model::near::SignerKeyUsage;
+use super::error::Result;
+
+/// New transactions reuse a fetched block hash this long, well inside the ~24h validity window.
+pub const BLOCK_HASH_TTL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Default)]
+struct KeyState {
+    /// last nonce handed out, `None` until fetched from the chain
+    nonce: tokio::sync::Mutex<Option<u64>>,
+    in_flight: AtomicUsize,
+    /// transactions leased the key since start
+    leased: AtomicU64,
+    idle: tokio::sync::Notify,
+    /// shared by every send on the key, exclusive for a send whose later nonces are used by its receipts
+    sending: tokio::sync::RwLock<()>,
+}
+
+/// Nonces of the access keys the relayer signs with. Each key's nonce is fetched once and then
+/// handed out locally, so concurrent transactions on a key never collide.
+#[derive(Debug, Default)]
+pub struct NonceManager {
+    keys: Mutex<HashMap<PublicKey, Arc<KeyState>>>,
+    cursor: AtomicUsize,
+}
+
+impl NonceManager {
+    /// Leases the key with the fewest transactions in flight, ties go round robin.
+    /// Keys no longer in `signers` are forgotten once their transactions ended.
+    pub fn lease(&self, signers: &[Arc<InMemorySigner>]) -> Option<KeyLease> {
+        let mut keys = self.keys.lock().expect("nonce lock poisoned");
+        keys.retain(|public_key, state| {
+            signers.iter().any(|signer| &signer.public_key == public_key) || state.in_flight.load(Ordering::SeqCst) > 0
+        });
+
+        let start = self.cursor.fetch_add(1, Ordering::Relaxed);
+        let candidates: Vec<(&Arc<InMemorySigner>, Arc<KeyState>)> = (0..signers.len())
+            .map(|i| &signers[(start + i) % signers.len()])
+            .map(|signer| (signer, Arc::clone(keys.entry(signer.public_key.clone()).or_default())))
+            .collect();
+        let (signer, state) = candidates
+            .into_iter()
+            .min_by_key(|(_, state)| state.in_flight.load(Ordering::SeqCst))?;
+
+        Some(KeyLease::new(Arc::clone(signer), state))
+    }
+
+    /// Leases `signer` itself, for transactions that must be signed by a given key.
+    pub fn lease_key(&self, signer: Arc<InMemorySigner>) -> KeyLease {
+        let state = Arc::clone(self.keys.lock().expect("nonce lock poisoned").entry(signer.public_key.clone()).or_default());
+        KeyLease::new(signer, state)
+    }
+
+    /// Waits up to `timeout` for the transactions in flight on `public_keys` to end, `false` when some are still running.
+    pub async fn drained(&self, public_keys: &[PublicKey], timeout: Duration) -> bool {
+        let states: Vec<Arc<KeyState>> = {
+            let keys = self.keys.lock().expect("nonce lock poisoned");
+            public_keys.iter().filter_map(|public_key| keys.get(public_key).cloned()).collect()
+        };
+        tokio::time::timeout(timeout, async {
+            for state in states {
+                loop {
+                    // --- registered before the check, so the last lease's notify is not missed
+                    let idle = state.idle.notified();
+                    if state.in_flight.load(Ordering::SeqCst) == 0 {
+                        break;
+                    }
+                    idle.await;
+                }
+            }
+        })
+        .await
+        .is_ok()
+    }
+
+    /// Transactions per key, for the keys leased at least once.
+    pub fn usage(&self) -> Vec<SignerKeyUsage> {
+        self.keys.lock().expect("nonce lock poisoned")
+            .iter()
+            .map(|(public_key, state)| SignerKeyUsage {
+                public_key: public_key.to_string(),
+                in_flight: state.in_flight.load(Ordering::SeqCst) as u64,
+                transactions: state.leased.load(Ordering::Relaxed),
+            })
+            .collect()
+    }
+}
+
+/// A key picked for one transaction, counted as in flight until dropped.
+pub struct KeyLease {
+    signer: Arc<InMemorySigner>,
+    state: Arc<KeyState>,
+}
+
+impl KeyLease {
+    fn new(signer: Arc<InMemorySigner>, state: Arc<KeyState>) -> Self {
+        state.in_flight.fetch_add(1, Ordering::SeqCst);
+        state.leased.fetch_add(1, Ordering::Relaxed);
+        Self { signer, state }
+    }
+
+    pub fn signer(&self) -> &InMemorySigner {
+        &self.signer
+    }
+
+    /// Held over a send. `exclusive` waits for the sends in flight on the key and keeps new ones out until dropped.
+    pub async fn send_guard(&self, exclusive: bool) -> SendGuard<'_> {
+        if exclusive {
+            SendGuard { _shared: None, _exclusive: Some(self.state.sending.write().await) }
+        } else {
+            SendGuard { _shared: Some(self.state.sending.read().await), _exclusive: None }
+        }
+    }
+
+    /// Reserves `count` consecutive nonces and returns the first. `fetch` runs only while the nonce is unknown.
+    pub async fn reserve<F>(&self, count: u64, fetch: F) -> Result<u64>
+    where
+        F: Future<Output = Result<u64>>,
+    {
+        let mut nonce = self.state.nonce.lock().await;
+        let last = match *nonce {
+            Some(last) => last,
+            None => fetch.await?,
+        };
+        *nonce = Some(last + count);
+        Ok(last + 1)
+    }
+
+    /// After an InvalidNonce, continues from the nonce the chain reported.
+    pub async fn resync(&self, ak_nonce: u64) {
+        let mut nonce = self.state.nonce.lock().await;
+        *nonce = Some(nonce.map_or(ak_nonce, |last| last.max(ak_nonce)));
+    }
+
+    /// Forgets the nonce, the next `reserve` fetches it again.
+    pub async fn invalidate(&self) {
+        *self.state.nonce.lock().await = None;
+    }
+}
+
+pub struct SendGuard<'a> {
+    _shared: Option<tokio::sync::RwLockReadGuard<'a, ()>>,
+    _exclusive: Option<tokio::sync::RwLockWriteGuard<'a, ()>>,
+}
+
+impl Drop for KeyLease {
+    fn drop(&mut self) {
+        if self.state.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.state.idle.notify_waiters();
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct BlockHashCache {
+    latest: tokio::sync::Mutex<Option<(CryptoHash, Instant)>>,
+}
+
+impl BlockHashCache {
+    /// `fetch` runs when the cached hash is older than `BLOCK_HASH_TTL`.
+    pub async fn get<F>(&self, fetch: F) -> Result<CryptoHash>
+    where
+        F: Future<Output = Result<CryptoHash>>,
+    {
+        let mut latest = self.latest.lock().await;
+        if let Some((block_hash, fetched_at)) = *latest {
+            if fetched_at.elapsed() < BLOCK_HASH_TTL {
+                return Ok(block_hash);
+            }
+        }
+        let block_hash = fetch.await?;
+        *latest = Some((block_hash, Instant::now()));
+        Ok(block_hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use near_crypto::KeyType;
+    use super::*;
+
+    fn signer(seed: &str) -> Arc<InMemorySigner> {
+        Arc::new(InMemorySigner::from_seed("payout.testnet".parse().unwrap(), KeyType::ED25519, seed))
+    }
+
+    async fn fetched(nonce: u64) -> Result<u64> {
+        Ok(nonce)
+    }
+
+    async fn cached() -> Result<u64> {
+        panic!("nonce is cached")
+    }
+
+    #[test]
+    fn test_lease_least_busy_key() {
+        let nonces = NonceManager::default();
+        let signers = vec![signer("a"), signer("b")];
+
+        let first = nonces.lease(&signers).unwrap();
+        let second = nonces.lease(&signers).unwrap();
+        assert_ne!(first.signer().public_key, second.signer().public_key);
+
+        drop(first);
+        let third = nonces.lease(&signers).unwrap();
+        assert_ne!(third.signer().public_key, second.signer().public_key);
+        let usage = nonces.usage();
+        assert_eq!(usage.iter().map(|key| key.transactions).sum::<u64>(), 3);
+        assert_eq!(usage.iter().map(|key| key.in_flight).sum::<u64>(), 2);
+        assert!(nonces.lease(&[]).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_drain_removed_key() {
+        let nonces = NonceManager::default();
+        let (a, b) = (signer("a"), signer("b"));
+        let lease = nonces.lease_key(Arc::clone(&a));
+
+        // --- a is out of the key set, its lease is still counted
+        let _other = nonces.lease(&[Arc::clone(&b)]).unwrap();
+        assert!(!nonces.drained(&[a.public_key.clone()], Duration::from_millis(10)).await);
+        assert!(nonces.drained(&[signer("c").public_key.clone()], Duration::from_millis(10)).await);
+
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            drop(lease);
+        });
+        assert!(nonces.drained(&[a.public_key.clone()], Duration::from_secs(1)).await);
+        drop(nonces.lease(&[Arc::clone(&b)]));
+        assert!(nonces.usage().iter().all(|key| key.public_key != a.public_key.to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_exclusive_send_waits_for_the_key() {
+        let nonces = NonceManager::default();
+        let a = signer("a");
+        let (sending, delegated, next) = (nonces.lease_key(Arc::clone(&a)), nonces.lease_key(Arc::clone(&a)), nonces.lease_key(a));
+
+        let shared = sending.send_guard(false).await;
+        assert!(tokio::time::timeout(Duration::from_millis(10), delegated.send_guard(true)).await.is_err());
+        drop(shared);
+
+        let exclusive = delegated.send_guard(true).await;
+        assert_eq!(delegated.reserve(2, fetched(10)).await.unwrap(), 11);
+        // --- no transaction takes the next nonce of the key before the delegate action ran
+        assert!(tokio::time::timeout(Duration::from_millis(10), next.send_guard(false)).await.is_err());
+        drop(exclusive);
+        let _shared = next.send_guard(false).await;
+        assert_eq!(next.reserve(1, cached()).await.unwrap(), 13);
+    }
+
+    #[tokio::test]
+    async fn test_reserve_and_resync_nonce() {
+        let nonces = NonceManager::default();
+        let lease = nonces.lease(&[signer("a")]).unwrap();
+
+        assert_eq!(lease.reserve(1, fetched(10)).await.unwrap(), 11);
+        assert_eq!(lease.reserve(2, cached()).await.unwrap(), 12);
+        assert_eq!(lease.reserve(1, cached()).await.unwrap(), 14);
+
+        lease.resync(20).await;
+        assert_eq!(lease.reserve(1, cached()).await.unwrap(), 21);
+        lease.resync(5).await;
+        assert_eq!(lease.reserve(1, cached()).await.unwrap(), 22);
+
+        lease.invalidate().await;
+        assert_eq!(lease.reserve(1, fetched(30)).await.unwrap(), 31);
+    }
+}
@@ -0,0 +1,250 @@
+use std::fmt::Debug;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+use near_jsonrpc_client::errors::{JsonRpcError, JsonRpcServerError, JsonRpcServerResponseStatusError};
+use near_jsonrpc_primitives::types::blocks::RpcBlockError;
+use near_jsonrpc_primitives::types::query::RpcQueryError;
+use near_jsonrpc_primitives::types::status::RpcStatusError;
+use near_jsonrpc_primitives::types::transactions::RpcTransactionError;
+use near_primitives::errors::InvalidTxError;
+use serde::Serialize;
+use crate::config::retry::{ErrorClass, RetryDecision, RetryPolicy};
+
+/// Class of the method specific errors a node answers with.
+pub trait HandlerErrorClass {
+    fn error_class(&self) -> ErrorClass;
+}
+
+impl HandlerErrorClass for RpcTransactionError {
+    fn error_class(&self) -> ErrorClass {
+        match self {
+            RpcTransactionError::TimeoutError => ErrorClass::Timeout,
+            RpcTransactionError::InternalError { .. } => ErrorClass::ServerError,
+            RpcTransactionError::InvalidTransaction { context: InvalidTxError::InvalidNonce { .. } } => ErrorClass::InvalidNonce,
+            RpcTransactionError::InvalidTransaction { context: InvalidTxError::InvalidSignature } => ErrorClass::InvalidSignature,
+            _ => ErrorClass::Rejected,
+        }
+    }
+}
+
+impl HandlerErrorClass for RpcQueryError {
+    fn error_class(&self) -> ErrorClass {
+        match self {
+            RpcQueryError::NoSyncedBlocks | RpcQueryError::UnavailableShard { .. } | RpcQueryError::InternalError { .. } => {
+                ErrorClass::ServerError
+            }
+            _ => ErrorClass::Rejected,
+        }
+    }
+}
+
+impl HandlerErrorClass for RpcStatusError {
+    fn error_class(&self) -> ErrorClass {
+        match self {
+            RpcStatusError::NodeIsSyncing | RpcStatusError::NoNewBlocks { .. } | RpcStatusError::InternalError { .. } => {
+                ErrorClass::ServerError
+            }
+            _ => ErrorClass::Rejected,
+        }
+    }
+}
+
+impl HandlerErrorClass for RpcBlockError {
+    fn error_class(&self) -> ErrorClass {
+        match self {
+            RpcBlockError::NotSyncedYet | RpcBlockError::InternalError { .. } => ErrorClass::ServerError,
+            _ => ErrorClass::Rejected,
+        }
+    }
+}
+
+pub fn classify<E: HandlerErrorClass>(err: &JsonRpcError<E>) -> ErrorClass {
+    match err {
+        JsonRpcError::TransportError(_) => ErrorClass::Transport,
+        JsonRpcError::ServerError(JsonRpcServerError::HandlerError(handler_error)) => handler_error.error_class(),
+        JsonRpcError::ServerError(JsonRpcServerError::InternalError { .. })
+        | JsonRpcError::ServerError(JsonRpcServerError::NonContextualError(_)) => ErrorClass::ServerError,
+        JsonRpcError::ServerError(JsonRpcServerError::ResponseStatusError(status_error)) => match status_error {
+            JsonRpcServerResponseStatusError::TooManyRequests => ErrorClass::RateLimited,
+            JsonRpcServerResponseStatusError::TimeoutError => ErrorClass::Timeout,
+            JsonRpcServerResponseStatusError::ServiceUnavailable => ErrorClass::ServerError,
+            JsonRpcServerResponseStatusError::Unexpected { status } if status.is_server_error() => ErrorClass::ServerError,
+            _ => ErrorClass::Rejected,
+        },
+        JsonRpcError::ServerError(JsonRpcServerError::RequestValidationError(_)) => ErrorClass::Rejected,
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RetryCount {
+    pub class: ErrorClass,
+    /// retries started
+    pub retried: u64,
+    /// calls that failed with retries left in the decision table, but out of attempts or time
+    pub exhausted: u64,
+}
+
+/// Retry counters per error class, since start.
+#[derive(Debug, Default)]
+pub struct RetryStats {
+    retried: [AtomicU64; ErrorClass::ALL.len()],
+    exhausted: [AtomicU64; ErrorClass::ALL.len()],
+}
+
+impl RetryStats {
+    pub fn record_retry(&self, class: ErrorClass) {
+        self.retried[class_index(class)].fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_exhausted(&self, class: ErrorClass) {
+        self.exhausted[class_index(class)].fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> Vec<RetryCount> {
+        ErrorClass::ALL
+            .iter()
+            .enumerate()
+            .map(|(i, class)| RetryCount {
+                class: *class,
+                retried: self.retried[i].load(Ordering::Relaxed),
+                exhausted: self.exhausted[i].load(Ordering::Relaxed),
+            })
+            .collect()
+    }
+}
+
+fn class_index(class: ErrorClass) -> usize {
+    ErrorClass::ALL.iter().position(|c| *c == class).expect("every class is listed")
+}
+
+/// Runs rpc calls under a `RetryPolicy`, counting the retries.
+#[derive(Debug, Default)]
+pub struct Retrier {
+    policy: RetryPolicy,
+    stats: RetryStats,
+}
+
+impl Retrier {
+    pub fn new(policy: RetryPolicy) -> Self {
+        Self { policy, stats: RetryStats::default() }
+    }
+
+    pub fn policy(&self) -> &RetryPolicy {
+        &self.policy
+    }
+
+    pub fn stats(&self) -> &RetryStats {
+        &self.stats
+    }
+
+    /// Calls `call` until it succeeds, fails with a class the policy does not retry, or runs out of attempts or time.
+    /// Nonce errors are never retried here, sending the same signed transaction again can not fix them.
+    pub async fn run<T, E, F, Fut>(&self, description: &str, mut call: F) -> Result<T, JsonRpcError<E>>
+    where
+        E: HandlerErrorClass + Debug,
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, JsonRpcError<E>>>,
+    {
+        let started = Instant::now();
+        let mut attempt = 1;
+
+        loop {
+            let err = match call().await {
+                Ok(result) => return Ok(result),
+                Err(err) => err,
+            };
+            let class = classify(&err);
+            if class == ErrorClass::InvalidNonce || self.policy.decision(class) == RetryDecision::Fail {
+                return Err(err);
+            }
+
+            let delay = self.policy.backoff(attempt);
+            if attempt >= self.policy.max_attempts || started.elapsed() + delay > self.policy.max_elapsed() {
+                self.stats.record_exhausted(class);
+                tracing::warn!("{description} gave up after {attempt} attempts ({class}): {err:?}");
+                return Err(err);
+            }
+
+            self.stats.record_retry(class);
+            tracing::warn!("{description} failed ({class}), retry {attempt}/{} in {delay:?}: {err:?}", self.policy.max_attempts.saturating_sub(1));
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Whether a transaction rejected with `class` is signed again, the caller resyncs the nonce in between.
+    pub fn resign(&self, class: ErrorClass, attempt: u32) -> bool {
+        let resign = self.policy.decision(class) == RetryDecision::Retry && attempt < self.policy.max_attempts;
+        if resign {
+            self.stats.record_retry(class);
+        } else if self.policy.decision(class) == RetryDecision::Retry {
+            self.stats.record_exhausted(class);
+        }
+        resign
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicU32;
+    use super::*;
+
+    fn status_error(status_error: JsonRpcServerResponseStatusError) -> JsonRpcError<RpcQueryError> {
+        JsonRpcError::ServerError(JsonRpcServerError::ResponseStatusError(status_error))
+    }
+
+    fn fast_policy() -> RetryPolicy {
+        RetryPolicy { max_attempts: 3, initial_backoff_ms: 1, max_backoff_ms: 1, jitter: 0.0, ..Default::default() }
+    }
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(classify(&status_error(JsonRpcServerResponseStatusError::TooManyRequests)), ErrorClass::RateLimited);
+        assert_eq!(classify(&status_error(JsonRpcServerResponseStatusError::TimeoutError)), ErrorClass::Timeout);
+        assert_eq!(classify(&status_error(JsonRpcServerResponseStatusError::BadRequest)), ErrorClass::Rejected);
+
+        let timeout: JsonRpcError<RpcTransactionError> = JsonRpcError::ServerError(JsonRpcServerError::HandlerError(RpcTransactionError::TimeoutError));
+        assert_eq!(classify(&timeout), ErrorClass::Timeout);
+        let invalid_nonce: JsonRpcError<RpcTransactionError> = JsonRpcError::ServerError(JsonRpcServerError::HandlerError(
+            RpcTransactionError::InvalidTransaction { context: InvalidTxError::InvalidNonce { tx_nonce: 1, ak_nonce: 2 } },
+        ));
+        assert_eq!(classify(&invalid_nonce), ErrorClass::InvalidNonce);
+    }
+
+    #[tokio::test]
+    async fn test_run_retries_transient_errors() {
+        let retrier = Retrier::new(fast_policy());
+        let calls = AtomicU32::new(0);
+
+        let result = retrier.run("test", || async {
+            match calls.fetch_add(1, Ordering::SeqCst) {
+                0 => Err(status_error(JsonRpcServerResponseStatusError::ServiceUnavailable)),
+                _ => Ok(7),
+            }
+        }).await;
+        assert_eq!(result.unwrap(), 7);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let calls = AtomicU32::new(0);
+        let result: Result<(), _> = retrier.run("test", || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(status_error(JsonRpcServerResponseStatusError::TooManyRequests))
+        }).await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        let calls = AtomicU32::new(0);
+        let result: Result<(), _> = retrier.run("test", || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(status_error(JsonRpcServerResponseStatusError::BadRequest))
+        }).await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let server_error = retrier.stats().snapshot().into_iter().find(|count| count.class == ErrorClass::ServerError).unwrap();
+        assert_eq!(server_error.retried, 1);
+        let rate_limited = retrier.stats().snapshot().into_iter().find(|count| count.class == ErrorClass::RateLimited).unwrap();
+        assert_eq!((rate_limited.retried, rate_limited.exhausted), (2, 1));
+    }
+}
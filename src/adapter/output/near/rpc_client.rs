@@ -3,55 +3,140 @@ use std::sync::Arc;
 use bigdecimal::ToPrimitive;
 use async_trait::async_trait;
 use near_fetch::signer::KeyRotatingSigner;
-use near_fetch::Client;
-use near_fetch::Error as NearFetchError;
 use near_jsonrpc_client::errors::JsonRpcError;
 use near_jsonrpc_client::errors::JsonRpcServerError;
-use near_fetch::Error::RpcTransactionError as ParentRpcTransactionError;
 use near_jsonrpc_primitives::types::transactions::RpcTransactionError::InvalidTransaction;
 use near_primitives::errors::ActionError as TxActionError;
 use near_primitives::errors::ActionErrorKind;
 use near_primitives::errors::InvalidTxError;
 use near_primitives::action::delegate::{DelegateAction, NonDelegateAction, SignedDelegateAction};
-use near_primitives::action::{Action, FunctionCallAction, TransferAction};
+use near_primitives::action::{Action, AddKeyAction, DeleteKeyAction, FunctionCallAction, TransferAction};
+use near_primitives::account::{AccessKey, AccessKeyPermission};
 use near_primitives::borsh::BorshDeserialize;
 use near_primitives::errors::TxExecutionError;
 use near_primitives::signable_message::{SignableMessage, SignableMessageType};
-use near_primitives::types::{AccountId, BlockHeight};
+use near_primitives::transaction::Transaction;
+use near_primitives::types::{AccountId, BlockHeight, BlockReference, Finality, FunctionArgs, Nonce};
+use near_primitives::views::QueryRequest;
+use near_jsonrpc_client::methods::block::RpcBlockRequest;
+use near_jsonrpc_client::methods::query::RpcQueryRequest;
+use near_jsonrpc_client::methods::send_tx::RpcSendTransactionRequest;
+use near_jsonrpc_client::methods::status::RpcStatusRequest;
+use near_jsonrpc_client::methods::tx::RpcTransactionStatusRequest;
+use near_jsonrpc_client::methods::RpcMethod;
+use near_jsonrpc_primitives::types::transactions::{RpcTransactionError, TransactionInfo};
+use near_primitives::hash::CryptoHash;
+use near_primitives::views::AccessKeyPermissionView;
+use near_crypto::{InMemorySigner, PublicKey};
+use near_jsonrpc_primitives::types::query::QueryResponseKind;
 use near_primitives::views::ExecutionStatusView;
 use near_primitives::views::{FinalExecutionOutcomeView, TxExecutionStatus};
 use serde_json::json;
+use tracing::Instrument;
 use near_fetch::signer::ExposeAccountId;
 use super::error::{Result, Error};
+use super::nonce::{BlockHashCache, KeyLease, NonceManager};
+use super::endpoint::RpcEndpointPool;
+use super::retry::{classify, HandlerErrorClass, Retrier, RetryStats};
+use super::circuit_breaker::CircuitBreaker;
 use crate::domain::model::near::TransactionResultResponse;
 use crate::domain::model::near::TransferActionType;
+use crate::domain::model::near::FtMetadata;
+use crate::domain::model::near::{AccessKeyInfo, AccountBalance, CircuitBreakerStatus, OnChainTransactionStatus, RpcNodeStatus, SignerKeyUsage, ACCESS_KEY_FULL_ACCESS};
 use crate::port::output::rpc_client::RpcClient;
 use crate::config::near::KeyRotatingSignerWrapper;
+use crate::config::retry::{ErrorClass, RetryPolicy};
+use crate::config::circuit_breaker::CircuitBreakerConfig;
+use crate::config::metrics::metrics;
 
 #[derive(Debug, Clone)]
 pub struct NearRpcManager {
-    pub client: Client,
+    pub endpoints: Arc<RpcEndpointPool>,
     pub signer: KeyRotatingSignerWrapper,
     pub whitelisted_contracts: Vec<String>,
     pub whitelisted_senders: Vec<String>,
+    nonces: Arc<NonceManager>,
+    block_hashes: Arc<BlockHashCache>,
+    retrier: Arc<Retrier>,
+    breaker: Arc<CircuitBreaker>,
 }
 
 impl NearRpcManager {
-    pub fn new(client: Client, 
+    pub fn new(endpoints: Arc<RpcEndpointPool>,
         signer: KeyRotatingSignerWrapper,
         whitelisted_contracts: Vec<String>,
         whitelisted_senders: Vec<String>,
+        retry_policy: RetryPolicy,
+        circuit_breaker: CircuitBreakerConfig,
     ) -> Self {
-        Self { client, signer, whitelisted_contracts, whitelisted_senders }
+        Self {
+            endpoints,
+            signer,
+            whitelisted_contracts,
+            whitelisted_senders,
+            nonces: Arc::new(NonceManager::default()),
+            block_hashes: Arc::new(BlockHashCache::default()),
+            retrier: Arc::new(Retrier::new(retry_policy)),
+            breaker: Arc::new(CircuitBreaker::new(circuit_breaker)),
+        }
+    }
+
+    pub fn retry_stats(&self) -> &RetryStats {
+        self.retrier.stats()
+    }
+
+    pub fn signer_key_usage(&self) -> Vec<SignerKeyUsage> {
+        self.nonces.usage()
+    }
+
+    /// Waits up to `timeout` for the transactions signed with `public_keys` to end, `false` when some are still running.
+    pub async fn signer_keys_drained(&self, public_keys: &[PublicKey], timeout: std::time::Duration) -> bool {
+        self.nonces.drained(public_keys, timeout).await
+    }
+
+    pub fn circuit_breaker_status(&self) -> CircuitBreakerStatus {
+        self.breaker.status()
+    }
+
+    /// `RpcUnavailable` while the circuit breaker would fail calls fast, for callers to check before taking on work.
+    pub fn ensure_available(&self) -> Result<()> {
+        self.breaker.check().map_err(rpc_unavailable)
+    }
+
+    /// Sends `request()` to the healthiest endpoint with failover, retried under the retry policy.
+    /// `request` must be safe to send twice, see `RpcEndpointPool::call`.
+    /// `RpcUnavailable` when the circuit breaker is open, the request was then not sent at all.
+    async fn call<M, F>(&self, description: &str, request: F) -> Result<core::result::Result<M::Response, JsonRpcError<M::Error>>>
+    where
+        M: RpcMethod,
+        M::Error: HandlerErrorClass + std::fmt::Debug,
+        F: Fn() -> M,
+    {
+        let permit = self.breaker.acquire().map_err(|retry_in| {
+            tracing::warn!("{description} not sent, rpc circuit breaker is open");
+            rpc_unavailable(retry_in)
+        })?;
+        let method = request().method_name().to_string();
+        let started = std::time::Instant::now();
+        // --- one span over the retries and failover, a child of the claim or request that made the call
+        let span = tracing::info_span!("near.rpc", rpc.method = %method, description);
+        let result = self.retrier.run(description, || self.endpoints.call(&request)).instrument(span).await;
+        let error_class = result.as_ref().err().map(classify);
+        metrics().rpc_call_duration.with_label_values(&[method.as_str()]).observe(started.elapsed().as_secs_f64());
+        if let Some(class) = error_class {
+            metrics().rpc_call_errors.with_label_values(&[method.as_str(), class.to_string().as_str()]).inc();
+        }
+        permit.record(error_class);
+        Ok(result)
     }
 
     fn signer(&self) -> Arc<KeyRotatingSigner> {
         self.signer.inner()
     }
 
-    fn handle_transaction_error(&self, err: NearFetchError) -> Error {
+    fn handle_transaction_error(&self, err: JsonRpcError<RpcTransactionError>) -> Error {
         match err {
-            ParentRpcTransactionError(JsonRpcError::ServerError(JsonRpcServerError::HandlerError(InvalidTransaction { context }))) => {
+            JsonRpcError::ServerError(JsonRpcServerError::HandlerError(InvalidTransaction { context })) => {
                 match context {
                     InvalidTxError::InvalidNonce { .. } => {
                         tracing::warn!("Invalid nonce: {context:?}");
@@ -72,19 +157,105 @@ impl NearRpcManager {
         }
     }
 
+    /// The signer key with the fewest transactions in flight.
+    fn lease_signer(&self) -> Result<KeyLease> {
+        self.nonces.lease(&self.signer.signers())
+            .ok_or_else(|| Error::InternalServerError { message: "no signer keys loaded".to_string() })
+    }
+
     async fn execute_actions(
         &self,
         receiver_id: &AccountId,
         actions: Vec<Action>,
         wait_until: Option<TxExecutionStatus>,
     ) -> Result<FinalExecutionOutcomeView> {
-        let signer = &*self.signer();
-        self.client.send_tx(
-            signer,
-            receiver_id,
-            actions,
-            wait_until,
-        ).await.map_err(|err| self.handle_transaction_error(err))
+        let lease = self.lease_signer()?;
+        self.send_transaction(&lease, 1, wait_until, |_| (receiver_id.clone(), actions.clone())).await
+    }
+
+    /// Signs with the leased key and sends. `build` gets the first of `nonce_count` nonces reserved for it.
+    /// With more than one nonce the key is held exclusively until the outcome, so no other transaction
+    /// moves the key past the later nonces before the receipts using them execute.
+    /// Transient rpc errors resend the same signed transaction under the retry policy, so it can not go through twice.
+    /// On InvalidNonce the key resyncs to the chain's nonce and the transaction is signed and sent again right away.
+    async fn send_transaction<F>(
+        &self,
+        lease: &KeyLease,
+        nonce_count: u64,
+        wait_until: Option<TxExecutionStatus>,
+        build: F,
+    ) -> Result<FinalExecutionOutcomeView>
+    where
+        F: Fn(Nonce) -> (AccountId, Vec<Action>),
+    {
+        let signer = lease.signer();
+        let _sending = lease.send_guard(nonce_count > 1).await;
+        let mut attempt = 0;
+
+        loop {
+            attempt += 1;
+            let nonce = lease.reserve(nonce_count, self.fetch_nonce(signer)).await?;
+            let block_hash = self.block_hashes.get(self.fetch_block_hash()).await?;
+            let (receiver_id, actions) = build(nonce);
+            let transaction = Transaction {
+                signer_id: signer.account_id.clone(),
+                public_key: signer.public_key.clone(),
+                nonce,
+                receiver_id,
+                block_hash,
+                actions,
+            };
+            let signed_transaction = transaction.sign(signer);
+            let wait_until = wait_until.clone().unwrap_or(TxExecutionStatus::ExecutedOptimistic);
+            let tx_hash = signed_transaction.get_hash();
+            let description = format!("send_tx {tx_hash}");
+            let span = tracing::info_span!("near.send_tx", tx_hash = %tx_hash, signer = %signer.public_key, nonce, attempt);
+            let result = self.call(&description, || RpcSendTransactionRequest {
+                signed_transaction: signed_transaction.clone(),
+                wait_until: wait_until.clone(),
+            }).instrument(span).await?;
+
+            match result {
+                Ok(response) => {
+                    return response.final_execution_outcome
+                        .map(|outcome| outcome.into_outcome())
+                        .ok_or_else(|| Error::TransactionNotExecuted {
+                            message: format!("no execution outcome, status {:?}", response.final_execution_status),
+                        });
+                }
+                Err(JsonRpcError::ServerError(JsonRpcServerError::HandlerError(InvalidTransaction {
+                    context: InvalidTxError::InvalidNonce { tx_nonce, ak_nonce },
+                }))) if self.retrier.resign(ErrorClass::InvalidNonce, attempt) => {
+                    tracing::warn!("Invalid nonce {tx_nonce} of {}, resyncing to {ak_nonce}", signer.public_key);
+                    lease.resync(ak_nonce).await;
+                }
+                Err(JsonRpcError::ServerError(JsonRpcServerError::HandlerError(InvalidTransaction {
+                    context: InvalidTxError::NonceTooLarge { tx_nonce, upper_bound },
+                }))) if self.retrier.resign(ErrorClass::InvalidNonce, attempt) => {
+                    tracing::warn!("Nonce {tx_nonce} of {} is above {upper_bound}, fetching it again", signer.public_key);
+                    lease.invalidate().await;
+                }
+                Err(err) => return Err(self.handle_transaction_error(err)),
+            }
+        }
+    }
+
+    async fn fetch_nonce(&self, signer: &InMemorySigner) -> Result<Nonce> {
+        let description = format!("view_access_key of {} {}", signer.account_id, signer.public_key);
+        let request = QueryRequest::ViewAccessKey { account_id: signer.account_id.clone(), public_key: signer.public_key.clone() };
+        match self.query(request, &description).await? {
+            QueryResponseKind::AccessKey(access_key) => Ok(access_key.nonce),
+            _ => Err(Error::ViewCallFailed { message: format!("unexpected {description} response") }),
+        }
+    }
+
+    async fn fetch_block_hash(&self) -> Result<CryptoHash> {
+        self.call("block", || RpcBlockRequest { block_reference: BlockReference::Finality(Finality::Final) }).await?
+            .map(|block| block.header.hash)
+            .map_err(|e| {
+                tracing::warn!("block failed: {e:?}");
+                Error::ViewCallFailed { message: format!("block failed: {e}") }
+            })
     }
 
     async fn process_transfer_action_internal(
@@ -126,33 +297,23 @@ impl NearRpcManager {
         }
     }
 
-    async fn create_signed_delegate_action(&self, receiver_id: AccountId, actions: Vec<Action>) -> Result<SignedDelegateAction> {
-        let signer = &*self.signer();
-        let relayer_account_id: AccountId = signer.account_id().clone();
-        let public_key = signer.public_key();
-        let (nonce, _block_hash, _) = self
-            .client
-            .fetch_nonce(&relayer_account_id, &public_key.clone())
-            .await
-            .unwrap();
-
-        let delegate_action = DelegateAction {
-            sender_id: relayer_account_id.clone(),
-            receiver_id: receiver_id,
-            actions: actions
-                .into_iter()
-                .map(|a| NonDelegateAction::try_from(a).unwrap())
-                .collect(),
-            nonce: nonce + 3,
-            max_block_height: 2000000000 as BlockHeight,
-            public_key: public_key.clone(),
-        };
+    /// Relays `actions` as a delegate action of the relayer itself. The outer transaction takes the first of
+    /// two reserved nonces and the delegate action the second, the key is held exclusively until the delegate
+    /// receipt executed, so the second nonce is still ahead of the key then.
+    async fn process_delegated_transfer(&self, receiver_id: AccountId, actions: Vec<Action>) -> Result<TransactionResultResponse> {
+        let lease = self.lease_signer()?;
+        let relayer_account_id = lease.signer().account_id.clone();
+        self.validate_delegate_accounts(&relayer_account_id, &receiver_id)?;
+        let actions: Vec<NonDelegateAction> = actions
+            .into_iter()
+            .map(|a| NonDelegateAction::try_from(a).unwrap())
+            .collect();
 
-        let signable = SignableMessage::new(&delegate_action, SignableMessageType::DelegateAction);
-        Ok(SignedDelegateAction {
-            signature: signable.sign(signer),
-            delegate_action,
-        })
+        let execution = self.send_transaction(&lease, 2, Some(TxExecutionStatus::ExecutedOptimistic), |nonce| {
+            let signed_delegate_action = create_signed_delegate_action(lease.signer(), receiver_id.clone(), actions.clone(), nonce + 1);
+            (relayer_account_id.clone(), vec![Action::Delegate(Box::new(signed_delegate_action))])
+        }).await?;
+        self.transaction_result_response(execution).await
     }
 
     async fn transaction_result_response(
@@ -216,23 +377,33 @@ impl NearRpcManager {
     }
 
 
-    async fn validate_signed_delegate_action(&self, signed_delegate_action: SignedDelegateAction) -> Result<()> {
+    fn validate_delegate_accounts(&self, sender_id: &AccountId, delegated_action_receiver_id: &AccountId) -> Result<()> {
         if self.whitelisted_contracts.is_empty() && self.whitelisted_senders.is_empty() {
             return Ok(());
         }
     
-        let sender_id = signed_delegate_action.delegate_action.sender_id;
         if !self.whitelisted_senders.is_empty() && !self.whitelisted_senders.contains(&sender_id.to_string()) {
             return Err(Error::NotWhitelisted { message: format!("delegated_action sender {sender_id} is not whitelisted") });
         }
     
-        let delegated_action_receiver_id = signed_delegate_action.delegate_action.receiver_id;
         if !self.whitelisted_contracts.is_empty() && !self.whitelisted_contracts.contains(&delegated_action_receiver_id.to_string()) {
             return Err(Error::NotWhitelisted { message: format!("delegated_action receiver {delegated_action_receiver_id} is not whitelisted") });
         }
         Ok(())
     }
 
+    async fn query(&self, request: QueryRequest, description: &str) -> Result<QueryResponseKind> {
+        self.call(description, || RpcQueryRequest {
+            block_reference: BlockReference::Finality(Finality::Final),
+            request: request.clone(),
+        }).await?
+            .map(|response| response.kind)
+            .map_err(|e| {
+                tracing::warn!("{description} failed: {e:?}");
+                Error::ViewCallFailed { message: format!("{description} failed: {e}") }
+            })
+    }
+
     pub async fn send_storage_deposit(
         &self,
         contract_id: AccountId,
@@ -253,6 +424,71 @@ impl NearRpcManager {
         let actions = vec![Action::FunctionCall(Box::new(storage_deposit_action))];
         self.execute_actions(&contract_id, actions, Some(TxExecutionStatus::ExecutedOptimistic)).await
     }
+
+    /// Adds `public_keys` to the account of `signer` in one batch, `signer` must be a full-access key.
+    pub async fn add_access_keys(
+        &self,
+        signer: &InMemorySigner,
+        public_keys: &[PublicKey],
+        permission: AccessKeyPermission,
+    ) -> Result<TransactionResultResponse> {
+        let actions = public_keys
+            .iter()
+            .map(|public_key| Action::AddKey(Box::new(AddKeyAction {
+                public_key: public_key.clone(),
+                access_key: AccessKey { nonce: 0, permission: permission.clone() },
+            })))
+            .collect();
+        self.send_account_actions(signer, actions).await
+    }
+
+    /// Deletes `public_keys` from the account of `signer` in one batch, `signer` must be a full-access key.
+    pub async fn delete_access_keys(
+        &self,
+        signer: &InMemorySigner,
+        public_keys: &[PublicKey],
+    ) -> Result<TransactionResultResponse> {
+        let actions = public_keys
+            .iter()
+            .map(|public_key| Action::DeleteKey(Box::new(DeleteKeyAction { public_key: public_key.clone() })))
+            .collect();
+        self.send_account_actions(signer, actions).await
+    }
+
+    /// Signs with `signer` instead of the least busy key, for changes to the relayer account itself.
+    async fn send_account_actions(&self, signer: &InMemorySigner, actions: Vec<Action>) -> Result<TransactionResultResponse> {
+        let lease = self.nonces.lease_key(Arc::new(signer.clone()));
+        let execution = self.send_transaction(&lease, 1, Some(TxExecutionStatus::Final), |_| {
+            (signer.account_id.clone(), actions.clone())
+        }).await?;
+        self.transaction_result_response(execution).await
+    }
+}
+
+fn create_signed_delegate_action(
+    signer: &InMemorySigner,
+    receiver_id: AccountId,
+    actions: Vec<NonDelegateAction>,
+    nonce: Nonce,
+) -> SignedDelegateAction {
+    let delegate_action = DelegateAction {
+        sender_id: signer.account_id.clone(),
+        receiver_id,
+        actions,
+        nonce,
+        max_block_height: 2000000000 as BlockHeight,
+        public_key: signer.public_key.clone(),
+    };
+
+    let signable = SignableMessage::new(&delegate_action, SignableMessageType::DelegateAction);
+    SignedDelegateAction {
+        signature: signable.sign(signer),
+        delegate_action,
+    }
+}
+
+fn rpc_unavailable(retry_in: std::time::Duration) -> Error {
+    Error::RpcUnavailable { retry_in_secs: retry_in.as_secs().max(1) }
 }
 
 #[async_trait]
@@ -283,8 +519,7 @@ impl RpcClient for NearRpcManager {
         let (receiver_id, actions) = self.create_transfer_actions(transfer_action_type).await?;
 
         if is_delegated {
-            let signed_delegate_action = self.create_signed_delegate_action(receiver_id, actions).await?;
-            self.process_signed_delegate_action(&signed_delegate_action, None).await
+            self.process_delegated_transfer(receiver_id, actions).await
         } else {
             self.process_transfer_action_internal(receiver_id, actions).await
         }
@@ -300,9 +535,107 @@ impl RpcClient for NearRpcManager {
             signed_delegate_action
         );
     
-        self.validate_signed_delegate_action(signed_delegate_action.clone()).await?;
+        let delegate_action = &signed_delegate_action.delegate_action;
+        self.validate_delegate_accounts(&delegate_action.sender_id, &delegate_action.receiver_id)?;
         let receiver_id: &AccountId = &signed_delegate_action.delegate_action.sender_id;
         let actions: Vec<Action> = vec![Action::Delegate(Box::new(signed_delegate_action.clone()))];
         self.process_transfer_action_internal(receiver_id.clone(), actions).await
     }
-}
\ No newline at end of file
+
+    async fn ft_metadata(&self, contract_id: &str) -> Result<FtMetadata> {
+        let account_id = AccountId::from_str(contract_id)
+            .map_err(|e| Error::InternalServerError { message: format!("invalid contract id {contract_id}: {e}") })?;
+        let request = QueryRequest::CallFunction {
+            account_id,
+            method_name: "ft_metadata".to_string(),
+            args: FunctionArgs::from(b"{}".to_vec()),
+        };
+
+        match self.query(request, &format!("ft_metadata of {contract_id}")).await? {
+            QueryResponseKind::CallResult(call_result) => serde_json::from_slice::<FtMetadata>(&call_result.result)
+                .map_err(|e| Error::ViewCallFailed { message: format!("invalid ft_metadata of {contract_id}: {e}") }),
+            _ => Err(Error::ViewCallFailed { message: format!("unexpected ft_metadata response of {contract_id}") }),
+        }
+    }
+
+    async fn node_status(&self) -> Result<RpcNodeStatus> {
+        let status = self.call("status", || RpcStatusRequest).await?.map_err(|e| {
+            tracing::warn!("status failed: {e:?}");
+            Error::ViewCallFailed { message: format!("status failed: {e}") }
+        })?;
+
+        Ok(RpcNodeStatus {
+            chain_id: status.chain_id,
+            latest_block_height: status.sync_info.latest_block_height,
+            latest_block_time: status.sync_info.latest_block_time.to_rfc3339(),
+            syncing: status.sync_info.syncing,
+        })
+    }
+
+    async fn view_account(&self, account_id: &str) -> Result<AccountBalance> {
+        let account_id = AccountId::from_str(account_id)
+            .map_err(|e| Error::InternalServerError { message: format!("invalid account id {account_id}: {e}") })?;
+        let description = format!("view_account of {account_id}");
+
+        match self.query(QueryRequest::ViewAccount { account_id: account_id.clone() }, &description).await? {
+            QueryResponseKind::ViewAccount(account) => Ok(AccountBalance {
+                account_id: account_id.to_string(),
+                amount: account.amount.to_string(),
+                locked: account.locked.to_string(),
+                storage_usage: account.storage_usage,
+            }),
+            _ => Err(Error::ViewCallFailed { message: format!("unexpected {description} response") }),
+        }
+    }
+
+    async fn view_access_key(&self, account_id: &str, public_key: &str) -> Result<AccessKeyInfo> {
+        let account_id = AccountId::from_str(account_id)
+            .map_err(|e| Error::InternalServerError { message: format!("invalid account id {account_id}: {e}") })?;
+        let public_key = PublicKey::from_str(public_key)
+            .map_err(|e| Error::InternalServerError { message: format!("invalid public key {public_key}: {e}") })?;
+        let description = format!("view_access_key of {account_id} {public_key}");
+
+        let request = QueryRequest::ViewAccessKey { account_id: account_id.clone(), public_key: public_key.clone() };
+        match self.query(request, &description).await? {
+            QueryResponseKind::AccessKey(access_key) => Ok(AccessKeyInfo {
+                account_id: account_id.to_string(),
+                public_key: public_key.to_string(),
+                nonce: access_key.nonce,
+                permission: match access_key.permission {
+                    AccessKeyPermissionView::FullAccess => ACCESS_KEY_FULL_ACCESS.to_string(),
+                    AccessKeyPermissionView::FunctionCall { receiver_id, .. } => format!("FUNCTION_CALL({receiver_id})"),
+                },
+            }),
+            _ => Err(Error::ViewCallFailed { message: format!("unexpected {description} response") }),
+        }
+    }
+
+    async fn transaction_status(&self, transaction_hash: &str) -> Result<OnChainTransactionStatus> {
+        let tx_hash = CryptoHash::from_str(transaction_hash)
+            .map_err(|e| Error::InternalServerError { message: format!("invalid transaction hash {transaction_hash}: {e}") })?;
+        let sender_account_id = self.signer().account_id().clone();
+        let description = format!("tx status of {transaction_hash}");
+        let result = self.call(&description, || RpcTransactionStatusRequest {
+            transaction_info: TransactionInfo::TransactionId { tx_hash, sender_account_id: sender_account_id.clone() },
+            wait_until: TxExecutionStatus::None,
+        }).await?;
+
+        match result {
+            Ok(response) => match response.final_execution_outcome {
+                Some(outcome) => match self.transaction_result_response(outcome.into_outcome()).await {
+                    Ok(result) if !result.has_errors => Ok(OnChainTransactionStatus::Succeeded),
+                    Ok(result) => Ok(OnChainTransactionStatus::Failed(result.error_details.join(", "))),
+                    Err(e) => Ok(OnChainTransactionStatus::Failed(e.to_string())),
+                },
+                None => Ok(OnChainTransactionStatus::Pending),
+            },
+            Err(JsonRpcError::ServerError(JsonRpcServerError::HandlerError(RpcTransactionError::UnknownTransaction { .. }))) => {
+                Ok(OnChainTransactionStatus::NotFound)
+            }
+            Err(e) => {
+                tracing::warn!("tx status of {transaction_hash} failed: {e:?}");
+                Err(Error::ViewCallFailed { message: format!("tx status of {transaction_hash} failed: {e}") })
+            }
+        }
+    }
+}
@@ -4,6 +4,7 @@ use std::path::{Path, PathBuf};
 use tracing::info;
 
 use deadpool_diesel::postgres::{Manager, Pool};
+use crate::adapter::output::persistence::db::migration;
 
 // NOTE: Hardcode to prevent deployed system db update.
 const SQL_RECREATE_DB_FILE_NAME: &str = "00-recreate-db.sql";
@@ -14,6 +15,7 @@ pub async fn init_local_db(database_url: &str, admin_database_url: &str) -> Resu
     let sql_dir = get_sql_dir()?;
     
     recreate_db(admin_database_url, &sql_dir).await?;
+    run_migrations(database_url).await?;
     execute_sql_files(database_url, &sql_dir).await?;
 
     Ok(())
@@ -36,6 +38,15 @@ async fn recreate_db(admin_database_url: &str, sql_dir: &Path) -> Result<(), Box
     pexec(&root_db, &sql_recreate_db_file).await
 }
 
+async fn run_migrations(database_url: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let app_db = new_db_pool(database_url)?;
+    let conn = app_db.get().await?;
+    let versions = conn.interact(|conn| migration::run_pending(conn)).await??;
+    info!("{:} - migrations applied: {}", "[LOCAL-ONLY]", versions.len());
+    Ok(())
+}
+
+/// Dev seed data, applied after the migrations.
 async fn execute_sql_files(database_url: &str, sql_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
     let app_db = new_db_pool(database_url)?;
     let mut paths: Vec<PathBuf> = fs::read_dir(sql_dir)?
@@ -11,6 +11,7 @@ pub enum Error {
     QueryError(String),
     PoolError(String),
     BuildError(String),
+    MigrationError(String),
 }
 
 pub fn adapt_db_error<T: PgError>(error: T) -> Error {
@@ -23,6 +24,8 @@ impl core::fmt::Display for Error {
     }
 }
 
+impl std::error::Error for Error {}
+
 impl Error {
     pub fn client_status_and_error(&self) -> (axum::http::StatusCode, String) {
         match self {
@@ -30,6 +33,7 @@ impl Error {
             Error::QueryError(message) => (axum::http::StatusCode::INTERNAL_SERVER_ERROR, message.clone()),
             Error::PoolError(message) => (axum::http::StatusCode::INTERNAL_SERVER_ERROR, message.clone()),
             Error::BuildError(message) => (axum::http::StatusCode::INTERNAL_SERVER_ERROR, message.clone()),
+            Error::MigrationError(message) => (axum::http::StatusCode::INTERNAL_SERVER_ERROR, message.clone()),
         }
     }
 }
@@ -0,0 +1,149 @@
+use diesel::migration::{MigrationConnection, MigrationSource};
+use diesel::pg::{Pg, PgConnection};
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Text};
+use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+use crate::port::output::DbManager;
+use super::error::{Error, Result, adapt_db_error};
+
+/// `migrations/*` compiled into the binary. The schema lives here, dev seed data stays in `scripts/dev_initial`.
+pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+
+/// `pg_advisory_lock` key held while migrations run, so instances starting together with
+/// `MIGRATE_ON_START` and a `migrate` command apply them one after the other.
+const MIGRATION_LOCK_KEY: i64 = 0x7061_796d_656e_7400; // "payment\0"
+
+#[derive(Debug, Clone)]
+pub struct MigrationStatus {
+    pub version: String,
+    pub name: String,
+    pub applied: bool,
+}
+
+fn migration_error(error: impl core::fmt::Display) -> Error {
+    Error::MigrationError(error.to_string())
+}
+
+/// Runs `f` holding the migration lock, waiting for whoever holds it first.
+/// The lock belongs to the session, so it is released before the pooled connection is reused.
+fn with_migration_lock<T>(conn: &mut PgConnection, f: impl FnOnce(&mut PgConnection) -> Result<T>) -> Result<T> {
+    diesel::sql_query("SELECT pg_advisory_lock($1)")
+        .bind::<BigInt, _>(MIGRATION_LOCK_KEY)
+        .execute(conn)
+        .map_err(adapt_db_error)?;
+
+    let result = f(conn);
+
+    let unlocked = diesel::sql_query("SELECT pg_advisory_unlock($1)")
+        .bind::<BigInt, _>(MIGRATION_LOCK_KEY)
+        .execute(conn)
+        .map_err(adapt_db_error);
+    let value = result?;
+    unlocked?;
+    Ok(value)
+}
+
+/// Runs every pending migration, each in its own transaction. Returns the applied versions.
+/// Another process migrating the same database is waited for, its versions are then no longer pending.
+pub fn run_pending(conn: &mut PgConnection) -> Result<Vec<String>> {
+    with_migration_lock(conn, |conn| {
+        conn.run_pending_migrations(MIGRATIONS)
+            .map(|versions| versions.iter().map(ToString::to_string).collect())
+            .map_err(migration_error)
+    })
+}
+
+pub fn status(conn: &mut PgConnection) -> Result<Vec<MigrationStatus>> {
+    let applied: Vec<String> = conn.applied_migrations()
+        .map_err(migration_error)?
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    let migrations = MigrationSource::<Pg>::migrations(&MIGRATIONS).map_err(migration_error)?;
+
+    Ok(migrations.iter()
+        .map(|migration| {
+            let version = migration.name().version().to_string();
+            MigrationStatus {
+                applied: applied.contains(&version),
+                name: migration.name().to_string(),
+                version,
+            }
+        })
+        .collect())
+}
+
+/// Marks every migration up to and including `up_to_version` as applied without running it.
+/// For databases that were built from the `scripts/dev_initial` files before migrations existed.
+pub fn baseline(conn: &mut PgConnection, up_to_version: &str) -> Result<Vec<String>> {
+    let versions: Vec<String> = MigrationSource::<Pg>::migrations(&MIGRATIONS)
+        .map_err(migration_error)?
+        .iter()
+        .map(|migration| migration.name().version().to_string())
+        .collect();
+    if !versions.iter().any(|version| version == up_to_version) {
+        return Err(Error::MigrationError(format!("unknown migration version: {}", up_to_version)));
+    }
+
+    let baselined: Vec<String> = versions.into_iter().filter(|version| version.as_str() <= up_to_version).collect();
+    with_migration_lock(conn, |conn| {
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            conn.setup()?;
+            for version in &baselined {
+                diesel::sql_query("INSERT INTO __diesel_schema_migrations (version) VALUES ($1) ON CONFLICT DO NOTHING")
+                    .bind::<Text, _>(version)
+                    .execute(conn)?;
+            }
+            Ok(())
+        })
+        .map_err(adapt_db_error)
+    })?;
+
+    Ok(baselined)
+}
+
+pub async fn run_pending_migrations(db_manager: &impl DbManager) -> Result<Vec<String>> {
+    let conn = db_manager.get_connection().await?;
+    conn.interact(|conn| run_pending(conn)).await?
+}
+
+pub async fn migration_status(db_manager: &impl DbManager) -> Result<Vec<MigrationStatus>> {
+    let conn = db_manager.get_connection().await?;
+    conn.interact(|conn| status(conn)).await?
+}
+
+pub async fn baseline_migrations(db_manager: &impl DbManager, up_to_version: String) -> Result<Vec<String>> {
+    let conn = db_manager.get_connection().await?;
+    conn.interact(move |conn| baseline(conn, &up_to_version)).await?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapter::output::persistence::db::_dev_utils;
+    use serial_test::serial;
+
+    #[serial]
+    #[tokio::test]
+    async fn test_migrations_applied() -> Result<()> {
+        let db_manager = _dev_utils::init_test().await;
+
+        let versions = run_pending_migrations(&db_manager).await?;
+        assert!(versions.is_empty());
+
+        // --- concurrent runs wait for each other's lock instead of racing
+        let (first, second) = tokio::join!(run_pending_migrations(&db_manager), run_pending_migrations(&db_manager));
+        assert!(first?.is_empty());
+        assert!(second?.is_empty());
+
+        let statuses = migration_status(&db_manager).await?;
+        assert!(!statuses.is_empty());
+        assert!(statuses.iter().all(|status| status.applied));
+
+        let baselined = baseline_migrations(&db_manager, statuses[0].version.clone()).await?;
+        assert_eq!(baselined, vec![statuses[0].version.clone()]);
+        assert!(baseline_migrations(&db_manager, "1999-01-01-000000".to_string()).await.is_err());
+
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 pub mod error;
 pub mod schema;
 pub mod postgres;
+pub mod migration;
 pub mod _dev_utils;
 
 pub use self::error::Error;
\ No newline at end of file
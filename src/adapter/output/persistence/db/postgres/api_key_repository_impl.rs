@@ -0,0 +1,172 @@
+use axum::async_trait;
+use chrono::NaiveDateTime;
+use deadpool_diesel::postgres::Object;
+use diesel::prelude::*;
+use uuid::Uuid;
+use crate::domain::model::api_key::{ApiKey, ApiKeyResponse, NewApiKey};
+use crate::domain::model::audit_log::{AuditActor, NewAuditLog, ACTION_API_KEY_CREATE, ACTION_API_KEY_REVOKE, ACTION_API_KEY_ROTATE, ENTITY_API_KEY};
+use crate::port::output::api_key_repository::ApiKeyRepository;
+use super::{Error, Result, adapt_db_error, api_key};
+use super::TracedInteract;
+use super::audit_log_repository_impl::insert_audit_log;
+
+#[derive(Clone, Debug)]
+pub struct PostgresApiKeyRepository;
+
+// key_hash is left out of audit rows
+fn audit_value(api_key: &ApiKey) -> ApiKeyResponse {
+    ApiKeyResponse::from(api_key.clone())
+}
+
+#[async_trait]
+impl ApiKeyRepository for PostgresApiKeyRepository {
+    async fn insert(&self, conn: Object, new_api_key: NewApiKey, actor: AuditActor) -> Result<ApiKey> {
+        conn.traced_interact("api_key.insert", move |conn| {
+            conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                let inserted_key = diesel::insert_into(api_key::table)
+                    .values(new_api_key)
+                    .returning(ApiKey::as_select())
+                    .get_result::<ApiKey>(conn)?;
+
+                insert_audit_log(conn, NewAuditLog::new(
+                    &actor,
+                    ACTION_API_KEY_CREATE,
+                    ENTITY_API_KEY,
+                    inserted_key.id,
+                    None,
+                    Some(&audit_value(&inserted_key)),
+                ))?;
+                Ok(inserted_key)
+            })
+        })
+        .await?
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
+
+    async fn get(&self, conn: Object, api_key_id: Uuid) -> Result<ApiKey> {
+        conn.traced_interact("api_key.get", move |conn| {
+            api_key::table
+                .find(api_key_id)
+                .select(ApiKey::as_select())
+                .get_result::<ApiKey>(conn)
+        })
+        .await?
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
+
+    async fn get_by_prefix(&self, conn: Object, key_prefix: String) -> Result<ApiKey> {
+        conn.traced_interact("api_key.get_by_prefix", move |conn| {
+            api_key::table
+                .filter(api_key::key_prefix.eq(key_prefix))
+                .select(ApiKey::as_select())
+                .first::<ApiKey>(conn)
+        })
+        .await?
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
+
+    async fn list(&self, conn: Object) -> Result<Vec<ApiKey>> {
+        conn.traced_interact("api_key.list", |conn| {
+            api_key::table
+                .order(api_key::created_date.desc())
+                .select(ApiKey::as_select())
+                .load::<ApiKey>(conn)
+        })
+        .await?
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
+
+    async fn rotate(&self, conn: Object, api_key_id: Uuid, new_api_key: NewApiKey, old_key_expires_date: NaiveDateTime, actor: AuditActor) -> Result<ApiKey> {
+        conn.traced_interact("api_key.rotate", move |conn| {
+            conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                let old_key = api_key::table
+                    .find(api_key_id)
+                    .for_update()
+                    .select(ApiKey::as_select())
+                    .get_result::<ApiKey>(conn)?;
+
+                // never extend the lifetime of a key that would expire before the grace period ends
+                let expires_date = old_key.expires_date.map_or(old_key_expires_date, |date| date.min(old_key_expires_date));
+                let expiring_key = diesel::update(api_key::table)
+                    .filter(api_key::id.eq(api_key_id))
+                    .set((
+                        api_key::expires_date.eq(Some(expires_date)),
+                        api_key::updated_date.eq(chrono::Utc::now().naive_utc()),
+                    ))
+                    .returning(ApiKey::as_select())
+                    .get_result::<ApiKey>(conn)?;
+
+                let rotated_key = diesel::insert_into(api_key::table)
+                    .values(new_api_key)
+                    .returning(ApiKey::as_select())
+                    .get_result::<ApiKey>(conn)?;
+
+                insert_audit_log(conn, NewAuditLog::new(
+                    &actor,
+                    ACTION_API_KEY_ROTATE,
+                    ENTITY_API_KEY,
+                    api_key_id,
+                    Some(&audit_value(&old_key)),
+                    Some(&audit_value(&expiring_key)),
+                ))?;
+                insert_audit_log(conn, NewAuditLog::new(
+                    &actor,
+                    ACTION_API_KEY_CREATE,
+                    ENTITY_API_KEY,
+                    rotated_key.id,
+                    None,
+                    Some(&audit_value(&rotated_key)),
+                ))?;
+                Ok(rotated_key)
+            })
+        })
+        .await?
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
+
+    async fn revoke(&self, conn: Object, api_key_id: Uuid, actor: AuditActor) -> Result<ApiKey> {
+        conn.traced_interact("api_key.revoke", move |conn| {
+            conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                let target_key = api_key::table
+                    .find(api_key_id)
+                    .for_update()
+                    .select(ApiKey::as_select())
+                    .get_result::<ApiKey>(conn)?;
+
+                let now = chrono::Utc::now().naive_utc();
+                let revoked_key = diesel::update(api_key::table)
+                    .filter(api_key::id.eq(api_key_id))
+                    .set((
+                        api_key::revoked_date.eq(Some(target_key.revoked_date.unwrap_or(now))),
+                        api_key::updated_date.eq(now),
+                    ))
+                    .returning(ApiKey::as_select())
+                    .get_result::<ApiKey>(conn)?;
+
+                insert_audit_log(conn, NewAuditLog::new(
+                    &actor,
+                    ACTION_API_KEY_REVOKE,
+                    ENTITY_API_KEY,
+                    api_key_id,
+                    Some(&audit_value(&target_key)),
+                    Some(&audit_value(&revoked_key)),
+                ))?;
+                Ok(revoked_key)
+            })
+        })
+        .await?
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
+
+    async fn touch_last_used(&self, conn: Object, api_key_id: Uuid) -> Result<()> {
+        conn.traced_interact("api_key.touch_last_used", move |conn| {
+            diesel::update(api_key::table)
+                .filter(api_key::id.eq(api_key_id))
+                .set(api_key::last_used_date.eq(Some(chrono::Utc::now().naive_utc())))
+                .execute(conn)
+        })
+        .await?
+        .map(|_| ())
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
+}
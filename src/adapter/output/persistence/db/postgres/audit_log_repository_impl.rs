@@ -0,0 +1,100 @@
+use axum::async_trait;
+use deadpool_diesel::postgres::Object;
+use diesel::prelude::*;
+use crate::domain::model::audit_log::{AuditLog, AuditLogFilter, NewAuditLog};
+use crate::port::output::audit_log_repository::AuditLogRepository;
+use super::{Error, Result, adapt_db_error, audit_log};
+use super::TracedInteract;
+
+/// Appends an audit row on an already open connection.
+/// Call it inside the same `conn.transaction` as the change it describes.
+pub fn insert_audit_log(conn: &mut PgConnection, new_audit_log: NewAuditLog) -> QueryResult<usize> {
+    diesel::insert_into(audit_log::table)
+        .values(new_audit_log)
+        .execute(conn)
+}
+
+#[derive(Clone, Debug)]
+pub struct PostgresAuditLogRepository;
+
+#[async_trait]
+impl AuditLogRepository for PostgresAuditLogRepository {
+    async fn insert(&self, conn: Object, new_audit_log: NewAuditLog) -> Result<AuditLog> {
+        conn.traced_interact("audit_log.insert", move |conn| {
+            diesel::insert_into(audit_log::table)
+                .values(new_audit_log)
+                .returning(AuditLog::as_select())
+                .get_result::<AuditLog>(conn)
+        })
+        .await?
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
+
+    async fn list(&self, conn: Object, filter: AuditLogFilter) -> Result<Vec<AuditLog>> {
+        conn.traced_interact("audit_log.list", move |conn| {
+            let mut query = audit_log::table.into_boxed();
+            if let Some(entity_type) = filter.entity_type {
+                query = query.filter(audit_log::entity_type.eq(entity_type));
+            }
+            if let Some(entity_id) = filter.entity_id {
+                query = query.filter(audit_log::entity_id.eq(entity_id));
+            }
+            if let Some(actor_id) = filter.actor_id {
+                query = query.filter(audit_log::actor_id.eq(actor_id));
+            }
+            query
+                .order(audit_log::created_date.desc())
+                .limit(filter.limit)
+                .select(AuditLog::as_select())
+                .load::<AuditLog>(conn)
+        })
+        .await?
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapter::output::persistence::db::_dev_utils;
+    use crate::domain::model::audit_log::{AuditActor, ACTOR_TYPE_ADMIN};
+    use crate::port::output::DbManager;
+    use diesel::sql_query;
+    use serial_test::serial;
+    use uuid::Uuid;
+
+    #[serial]
+    #[tokio::test]
+    async fn test_insert_is_append_only() -> Result<()> {
+        let db_manager = _dev_utils::init_test().await;
+        let repo = PostgresAuditLogRepository;
+
+        let entity_id = Uuid::new_v4();
+        let actor = AuditActor::new("00000000-0000-0000-0000-000000000001", ACTOR_TYPE_ADMIN, Some(Uuid::new_v4()));
+        let inserted = repo.insert(
+            db_manager.get_connection().await?,
+            NewAuditLog::new(&actor, "test.action", "TEST", entity_id, None, Some(&"after")),
+        ).await?;
+
+        // --- UPDATE and DELETE are rejected by the table trigger
+        let id = inserted.id;
+        let (updated, deleted) = db_manager.get_connection().await?.interact(move |conn| {
+            let updated = sql_query(format!("UPDATE audit_log SET action = 'tampered' WHERE id = '{id}'")).execute(conn);
+            let deleted = sql_query(format!("DELETE FROM audit_log WHERE id = '{id}'")).execute(conn);
+            (updated, deleted)
+        }).await?;
+        assert!(updated.is_err());
+        assert!(deleted.is_err());
+
+        let logs = repo.list(db_manager.get_connection().await?, AuditLogFilter {
+            entity_id: Some(entity_id.to_string()),
+            limit: 10,
+            ..Default::default()
+        }).await?;
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].action, "test.action");
+        assert_eq!(logs[0].request_id, actor.request_id);
+
+        Ok(())
+    }
+}
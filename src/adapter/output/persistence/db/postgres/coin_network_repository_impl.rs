@@ -3,8 +3,12 @@ use deadpool_diesel::postgres::Object;
 use diesel::prelude::*;
 use uuid::Uuid;
 use crate::{adapter::output::persistence::db::schema::{coin, network}, domain::model::{coin::Coin, coin_network::CoinNetwork, network::Network}};
+use crate::domain::model::audit_log::{AuditActor, NewAuditLog, ACTION_COIN_NETWORK_CREATE, ACTION_COIN_NETWORK_DEPRECATE, ACTION_COIN_NETWORK_METADATA_CHECK, ACTION_COIN_NETWORK_SET_ACTIVE, ACTION_COIN_NETWORK_UPDATE, ENTITY_COIN_NETWORK};
+use crate::domain::model::coin_network::NewCoinNetwork;
 use crate::port::output::coin_network_repository::CoinNetworkRepository;
 use super::{Error, Result, adapt_db_error, coin_network};
+use super::TracedInteract;
+use super::audit_log_repository_impl::insert_audit_log;
 
 #[derive(Clone, Debug)]
 pub struct PostgresCoinNetworkRepository;
@@ -12,7 +16,7 @@ pub struct PostgresCoinNetworkRepository;
 #[async_trait]
 impl CoinNetworkRepository for PostgresCoinNetworkRepository {
     async fn get_with_coin_and_network(&self, conn: Object, coin_network_id: Uuid) -> Result<(CoinNetwork, Coin, Network)> {
-        let result = conn.interact(move |conn| {
+        let result = conn.traced_interact("coin_network.get_with_coin_and_network", move |conn| {
             coin_network::table
                 .inner_join(coin::table.on(coin_network::coin_id.eq(coin::id)))
                 .inner_join(network::table.on(coin_network::network_id.eq(network::id)))
@@ -26,12 +30,23 @@ impl CoinNetworkRepository for PostgresCoinNetworkRepository {
         Ok(result.map_err(|e| Error::from(e))?)
     }
 
-    async fn list_all(&self, conn: Object) -> Result<Vec<(CoinNetwork, Coin, Network)>>{
-        let result = conn.interact(move |conn| {
-            coin_network::table
+    async fn list_all(&self, conn: Object, include_inactive: bool) -> Result<Vec<(CoinNetwork, Coin, Network)>>{
+        let result = conn.traced_interact("coin_network.list_all", move |conn| {
+            let mut query = coin_network::table
                 .inner_join(coin::table.on(coin_network::coin_id.eq(coin::id)))
                 .inner_join(network::table.on(coin_network::network_id.eq(network::id)))
                 .select((coin_network::all_columns, coin::all_columns, network::all_columns))
+                .into_boxed();
+
+            if !include_inactive {
+                query = query
+                    .filter(coin_network::is_active.eq(true))
+                    .filter(coin_network::deprecated_date.is_null())
+                    .filter(coin::is_active.eq(true))
+                    .filter(network::is_active.eq(true));
+            }
+
+            query
                 .load::<(CoinNetwork, Coin, Network)>(conn)
                 .map_err(adapt_db_error)
         })
@@ -40,13 +55,24 @@ impl CoinNetworkRepository for PostgresCoinNetworkRepository {
         Ok(result.map_err(|e| Error::from(e))?)
     }
 
-    async fn list_all_by_network_code(&self, conn: Object, network_code: String) -> Result<Vec<(CoinNetwork, Coin, Network)>> {
-        conn.interact(move |conn| {
-            coin::table
+    async fn list_all_by_network_code(&self, conn: Object, network_code: String, include_inactive: bool) -> Result<Vec<(CoinNetwork, Coin, Network)>> {
+        conn.traced_interact("coin_network.list_all_by_network_code", move |conn| {
+            let mut query = coin::table
                 .inner_join(coin_network::table.on(coin_network::coin_id.eq(coin::id)))
                 .inner_join(network::table.on(network::id.eq(coin_network::network_id)))
                 .filter(network::code.ilike(network_code))
                 .select((coin_network::all_columns, coin::all_columns, network::all_columns))
+                .into_boxed();
+
+            if !include_inactive {
+                query = query
+                    .filter(coin_network::is_active.eq(true))
+                    .filter(coin_network::deprecated_date.is_null())
+                    .filter(coin::is_active.eq(true))
+                    .filter(network::is_active.eq(true));
+            }
+
+            query
                 .load::<(CoinNetwork, Coin, Network)>(conn)
                 .map_err(adapt_db_error)
         })
@@ -54,7 +80,7 @@ impl CoinNetworkRepository for PostgresCoinNetworkRepository {
     }
 
     async fn list_all_by_ids(&self, conn: Object, coin_network_ids: Vec<Uuid>) -> Result<Vec<(CoinNetwork, Coin, Network)>> {
-        let result = conn.interact(move |conn| {
+        let result = conn.traced_interact("coin_network.list_all_by_ids", move |conn| {
             coin_network::table
                 .inner_join(coin::table.on(coin_network::coin_id.eq(coin::id)))
                 .inner_join(network::table.on(coin_network::network_id.eq(network::id)))
@@ -69,7 +95,7 @@ impl CoinNetworkRepository for PostgresCoinNetworkRepository {
     }
 
     async fn list_by_coin_id(&self, conn: Object, coin_id: Uuid) -> Result<Vec<CoinNetwork>> {
-        conn.interact(move |conn| {
+        conn.traced_interact("coin_network.list_by_coin_id", move |conn| {
             coin_network::table
                 .filter(coin_network::coin_id.eq(coin_id))
                 .load::<CoinNetwork>(conn)
@@ -77,6 +103,152 @@ impl CoinNetworkRepository for PostgresCoinNetworkRepository {
         .await?
         .map_err(|e| Error::from(adapt_db_error(e)))
     }
+
+    async fn create(&self, conn: Object, new_coin_network: NewCoinNetwork, actor: AuditActor) -> Result<CoinNetwork> {
+        conn.traced_interact("coin_network.create", move |conn| {
+            conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                let created_coin_network = diesel::insert_into(coin_network::table)
+                    .values(new_coin_network)
+                    .get_result::<CoinNetwork>(conn)?;
+
+                insert_audit_log(conn, NewAuditLog::new(
+                    &actor,
+                    ACTION_COIN_NETWORK_CREATE,
+                    ENTITY_COIN_NETWORK,
+                    created_coin_network.id,
+                    None,
+                    Some(&created_coin_network),
+                ))?;
+                Ok(created_coin_network)
+            })
+        })
+        .await?
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
+
+    async fn update_contract_address(&self, conn: Object, coin_network_id: Uuid, contract_address: Option<String>, actor: AuditActor) -> Result<CoinNetwork> {
+        conn.traced_interact("coin_network.update_contract_address", move |conn| {
+            conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                let target_coin_network = coin_network::table
+                    .find(coin_network_id)
+                    .for_update()
+                    .get_result::<CoinNetwork>(conn)?;
+
+                let updated_coin_network = diesel::update(coin_network::table.find(coin_network_id))
+                    .set((
+                        coin_network::contract_address.eq(contract_address),
+                        coin_network::updated_date.eq(chrono::Utc::now().naive_utc()),
+                    ))
+                    .get_result::<CoinNetwork>(conn)?;
+
+                insert_audit_log(conn, NewAuditLog::new(
+                    &actor,
+                    ACTION_COIN_NETWORK_UPDATE,
+                    ENTITY_COIN_NETWORK,
+                    coin_network_id,
+                    Some(&target_coin_network),
+                    Some(&updated_coin_network),
+                ))?;
+                Ok(updated_coin_network)
+            })
+        })
+        .await?
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
+
+    async fn set_active(&self, conn: Object, coin_network_id: Uuid, is_active: bool, actor: AuditActor) -> Result<CoinNetwork> {
+        conn.traced_interact("coin_network.set_active", move |conn| {
+            conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                let target_coin_network = coin_network::table
+                    .find(coin_network_id)
+                    .for_update()
+                    .get_result::<CoinNetwork>(conn)?;
+
+                let updated_coin_network = diesel::update(coin_network::table.find(coin_network_id))
+                    .set((
+                        coin_network::is_active.eq(is_active),
+                        coin_network::updated_date.eq(chrono::Utc::now().naive_utc()),
+                    ))
+                    .get_result::<CoinNetwork>(conn)?;
+
+                insert_audit_log(conn, NewAuditLog::new(
+                    &actor,
+                    ACTION_COIN_NETWORK_SET_ACTIVE,
+                    ENTITY_COIN_NETWORK,
+                    coin_network_id,
+                    Some(&target_coin_network),
+                    Some(&updated_coin_network),
+                ))?;
+                Ok(updated_coin_network)
+            })
+        })
+        .await?
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
+
+    async fn deprecate(&self, conn: Object, coin_network_id: Uuid, actor: AuditActor) -> Result<CoinNetwork> {
+        conn.traced_interact("coin_network.deprecate", move |conn| {
+            conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                let target_coin_network = coin_network::table
+                    .find(coin_network_id)
+                    .for_update()
+                    .get_result::<CoinNetwork>(conn)?;
+
+                let now = chrono::Utc::now().naive_utc();
+                let deprecated_coin_network = diesel::update(coin_network::table.find(coin_network_id))
+                    .set((
+                        coin_network::is_active.eq(false),
+                        coin_network::deprecated_date.eq(Some(now)),
+                        coin_network::updated_date.eq(now),
+                    ))
+                    .get_result::<CoinNetwork>(conn)?;
+
+                insert_audit_log(conn, NewAuditLog::new(
+                    &actor,
+                    ACTION_COIN_NETWORK_DEPRECATE,
+                    ENTITY_COIN_NETWORK,
+                    coin_network_id,
+                    Some(&target_coin_network),
+                    Some(&deprecated_coin_network),
+                ))?;
+                Ok(deprecated_coin_network)
+            })
+        })
+        .await?
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
+
+    async fn record_metadata_check(&self, conn: Object, coin_network_id: Uuid, metadata_mismatch: Option<String>, actor: AuditActor) -> Result<CoinNetwork> {
+        conn.traced_interact("coin_network.record_metadata_check", move |conn| {
+            conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                let target_coin_network = coin_network::table
+                    .find(coin_network_id)
+                    .for_update()
+                    .get_result::<CoinNetwork>(conn)?;
+
+                let checked_coin_network = diesel::update(coin_network::table.find(coin_network_id))
+                    .set((
+                        coin_network::metadata_mismatch.eq(metadata_mismatch),
+                        coin_network::metadata_checked_date.eq(Some(chrono::Utc::now().naive_utc())),
+                    ))
+                    .get_result::<CoinNetwork>(conn)?;
+
+                if target_coin_network.metadata_mismatch != checked_coin_network.metadata_mismatch {
+                    insert_audit_log(conn, NewAuditLog::new(
+                        &actor,
+                        ACTION_COIN_NETWORK_METADATA_CHECK,
+                        ENTITY_COIN_NETWORK,
+                        coin_network_id,
+                        Some(&target_coin_network),
+                        Some(&checked_coin_network),
+                    ))?;
+                }
+                Ok(checked_coin_network)
+            })
+        })
+        .await?
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
 }
 
 
@@ -84,6 +256,9 @@ impl CoinNetworkRepository for PostgresCoinNetworkRepository {
 mod tests {
     use super::*;
     use crate::{adapter::output::persistence::db::_dev_utils, port::output::DbManager};
+    use crate::adapter::output::persistence::db::postgres::coin_repository_impl::PostgresCoinRepository;
+    use crate::domain::model::coin::NewCoinPayload;
+    use crate::port::output::coin_repository::CoinRepository;
     use uuid::Uuid;
     use serial_test::serial;
 
@@ -109,7 +284,7 @@ mod tests {
         let db_manager = _dev_utils::init_test().await;
         let repo = PostgresCoinNetworkRepository;
 
-        let result = repo.list_all(db_manager.get_connection().await?).await?;
+        let result = repo.list_all(db_manager.get_connection().await?, true).await?;
         assert!(result.len() >= 3);
 
         Ok(())
@@ -121,7 +296,7 @@ mod tests {
         let db_manager = _dev_utils::init_test().await;
         let repo = PostgresCoinNetworkRepository;
 
-        let result = repo.list_all_by_network_code(db_manager.get_connection().await?, "NEAR".to_string()).await?;
+        let result = repo.list_all_by_network_code(db_manager.get_connection().await?, "NEAR".to_string(), true).await?;
         assert!(result.len() >= 3);
 
         Ok(())
@@ -157,4 +332,42 @@ mod tests {
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    #[serial]
+    #[tokio::test]
+    async fn test_deprecate_hides_from_list() -> Result<()> {
+        let db_manager = _dev_utils::init_test().await;
+        let repo = PostgresCoinNetworkRepository;
+
+        let coin = PostgresCoinRepository.insert(db_manager.get_connection().await?, NewCoinPayload {
+            name: "Deprecated Coin".to_string(),
+            symbol: "DPR".to_string(),
+            decimals: 6,
+            coin_type: "FT".to_string(),
+        }).await?;
+        let network_id = Uuid::parse_str("86b82d0f-80d5-4406-ae5a-e387db79ca32").unwrap();
+
+        let coin_network = repo.create(db_manager.get_connection().await?, NewCoinNetwork {
+            id: Uuid::new_v4(),
+            coin_id: coin.id,
+            network_id,
+            contract_address: Some("deprecated.testnet".to_string()),
+        }, AuditActor::system()).await?;
+        let listed = repo.list_all(db_manager.get_connection().await?, false).await?;
+        assert!(listed.iter().any(|(listed, _, _)| listed.id == coin_network.id));
+
+        let deprecated = repo.deprecate(db_manager.get_connection().await?, coin_network.id, AuditActor::system()).await?;
+        assert!(!deprecated.is_active);
+        assert!(deprecated.is_deprecated());
+
+        // --- hidden by default, still readable by id for existing claims
+        let listed = repo.list_all(db_manager.get_connection().await?, false).await?;
+        assert!(!listed.iter().any(|(listed, _, _)| listed.id == coin_network.id));
+        let listed = repo.list_all(db_manager.get_connection().await?, true).await?;
+        assert!(listed.iter().any(|(listed, _, _)| listed.id == coin_network.id));
+        let (fetched, _, _) = repo.get_with_coin_and_network(db_manager.get_connection().await?, coin_network.id).await?;
+        assert_eq!(fetched.deprecated_date, deprecated.deprecated_date);
+
+        Ok(())
+    }
+}
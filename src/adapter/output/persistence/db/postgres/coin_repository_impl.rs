@@ -2,8 +2,11 @@ use axum::async_trait;
 use deadpool_diesel::postgres::Object;
 use diesel::prelude::*;
 use uuid::Uuid;
-use crate::{domain::model::coin::{Coin, CoinType, NewCoin, NewCoinPayload}, port::output::coin_repository::CoinRepository};
+use crate::{domain::model::coin::{Coin, CoinChangeset, CoinType, NewCoin, NewCoinPayload}, port::output::coin_repository::CoinRepository};
+use crate::domain::model::audit_log::{AuditActor, NewAuditLog, ACTION_COIN_CREATE, ACTION_COIN_SET_ACTIVE, ACTION_COIN_UPDATE, ENTITY_COIN};
 use super::{Error, Result, adapt_db_error, coin};
+use super::TracedInteract;
+use super::audit_log_repository_impl::insert_audit_log;
 
 #[derive(Clone, Debug)]
 pub struct PostgresCoinRepository;
@@ -19,7 +22,7 @@ impl CoinRepository for PostgresCoinRepository {
             coin_type: CoinType::from(new_coin_payload.coin_type),
         };
 
-        conn.interact(move |conn| {
+        conn.traced_interact("coin.insert", move |conn| {
             diesel::insert_into(coin::table)
                 .values(new_coin)
                 .get_result::<Coin>(conn)
@@ -29,7 +32,7 @@ impl CoinRepository for PostgresCoinRepository {
     }
 
     async fn list(&self, conn: Object) -> Result<Vec<Coin>> {
-        conn.interact(move |conn| {
+        conn.traced_interact("coin.list", move |conn| {
             coin::table.load::<Coin>(conn)
         })
         .await?
@@ -37,7 +40,7 @@ impl CoinRepository for PostgresCoinRepository {
     }
 
     async fn get(&self, conn: Object, coin_id: Uuid) -> Result<Coin> {
-        conn.interact(move |conn| {
+        conn.traced_interact("coin.get", move |conn| {
             coin::table
                 .find(coin_id)
                 .get_result::<Coin>(conn)
@@ -46,6 +49,84 @@ impl CoinRepository for PostgresCoinRepository {
         .map_err(|e| Error::from(adapt_db_error(e)))
     }
 
+    async fn create(&self, conn: Object, new_coin: NewCoin, actor: AuditActor) -> Result<Coin> {
+        conn.traced_interact("coin.create", move |conn| {
+            conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                let created_coin = diesel::insert_into(coin::table)
+                    .values(new_coin)
+                    .get_result::<Coin>(conn)?;
+
+                insert_audit_log(conn, NewAuditLog::new(
+                    &actor,
+                    ACTION_COIN_CREATE,
+                    ENTITY_COIN,
+                    created_coin.id,
+                    None,
+                    Some(&created_coin),
+                ))?;
+                Ok(created_coin)
+            })
+        })
+        .await?
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
+
+    async fn update(&self, conn: Object, coin_id: Uuid, changeset: CoinChangeset, actor: AuditActor) -> Result<Coin> {
+        conn.traced_interact("coin.update", move |conn| {
+            conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                let target_coin = coin::table
+                    .find(coin_id)
+                    .for_update()
+                    .get_result::<Coin>(conn)?;
+
+                let updated_coin = diesel::update(coin::table.find(coin_id))
+                    .set(changeset)
+                    .get_result::<Coin>(conn)?;
+
+                insert_audit_log(conn, NewAuditLog::new(
+                    &actor,
+                    ACTION_COIN_UPDATE,
+                    ENTITY_COIN,
+                    coin_id,
+                    Some(&target_coin),
+                    Some(&updated_coin),
+                ))?;
+                Ok(updated_coin)
+            })
+        })
+        .await?
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
+
+    async fn set_active(&self, conn: Object, coin_id: Uuid, is_active: bool, actor: AuditActor) -> Result<Coin> {
+        conn.traced_interact("coin.set_active", move |conn| {
+            conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                let target_coin = coin::table
+                    .find(coin_id)
+                    .for_update()
+                    .get_result::<Coin>(conn)?;
+
+                let updated_coin = diesel::update(coin::table.find(coin_id))
+                    .set((
+                        coin::is_active.eq(is_active),
+                        coin::updated_date.eq(chrono::Utc::now().naive_utc()),
+                    ))
+                    .get_result::<Coin>(conn)?;
+
+                insert_audit_log(conn, NewAuditLog::new(
+                    &actor,
+                    ACTION_COIN_SET_ACTIVE,
+                    ENTITY_COIN,
+                    coin_id,
+                    Some(&target_coin),
+                    Some(&updated_coin),
+                ))?;
+                Ok(updated_coin)
+            })
+        })
+        .await?
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
 }
 
 #[cfg(test)]
@@ -111,4 +192,35 @@ mod tests {
 
         Ok(())
     }
+
+    #[serial]
+    #[tokio::test]
+    async fn test_update_and_disable_coin() -> Result<()> {
+        let db_manager = _dev_utils::init_test().await;
+        let repo = PostgresCoinRepository;
+
+        let created_coin = repo.create(db_manager.get_connection().await?, NewCoin {
+            id: Uuid::new_v4(),
+            name: "Admin Coin".to_string(),
+            symbol: "ADM".to_string(),
+            decimals: 6,
+            coin_type: CoinType::FT,
+        }, AuditActor::system()).await?;
+        assert!(created_coin.is_active);
+
+        let updated_coin = repo.update(db_manager.get_connection().await?, created_coin.id, CoinChangeset {
+            name: None,
+            symbol: Some("ADM2".to_string()),
+            decimals: Some(18),
+            updated_date: chrono::Utc::now().naive_utc(),
+        }, AuditActor::system()).await?;
+        assert_eq!(updated_coin.name, created_coin.name);
+        assert_eq!(updated_coin.symbol, "ADM2");
+        assert_eq!(updated_coin.decimals, 18);
+
+        let disabled_coin = repo.set_active(db_manager.get_connection().await?, created_coin.id, false, AuditActor::system()).await?;
+        assert!(!disabled_coin.is_active);
+
+        Ok(())
+    }
 }
\ No newline at end of file
@@ -6,6 +6,7 @@ use uuid::Uuid;
 use crate::{domain::model::detailed_posting::DetailedPosting, port::output::detailed_posting_repository::DetailedPostingRepository};
 
 use super::{Error, Result, adapt_db_error, detailed_posting};
+use super::TracedInteract;
 
 #[derive(Clone, Debug)]
 pub struct PostgresDetailedPostingRepository;
@@ -13,7 +14,7 @@ pub struct PostgresDetailedPostingRepository;
 #[async_trait]
 impl DetailedPostingRepository for PostgresDetailedPostingRepository {
     async fn get(&self, conn: Object, detail_id: Uuid) -> Result<DetailedPosting>{
-        conn.interact(move |conn| {
+        conn.traced_interact("detailed_posting.get", move |conn| {
             detailed_posting::table
                 .filter(detailed_posting::detail_id.eq(detail_id))
                 .get_result::<DetailedPosting>(conn)
@@ -6,6 +6,7 @@ use crate::domain::model::mission_submit::MissionSubmit;
 use crate::port::output::mission_submit_repository::MissionSubmitRepository;
 
 use super::{Error, Result, adapt_db_error, mission_submit};
+use super::TracedInteract;
 
 #[derive(Clone, Debug)]
 pub struct PostgresMissionSubmitRepository;
@@ -13,7 +14,7 @@ pub struct PostgresMissionSubmitRepository;
 #[async_trait]
 impl MissionSubmitRepository for PostgresMissionSubmitRepository {
     async fn get(&self, conn: Object, user_id: Uuid, mission_id: Uuid) -> Result<MissionSubmit> {
-        conn.interact(move |conn| {
+        conn.traced_interact("mission.get", move |conn| {
             mission_submit::table
                 .filter(mission_submit::usr_id.eq(user_id))
                 .filter(mission_submit::mission_id.eq(mission_id))
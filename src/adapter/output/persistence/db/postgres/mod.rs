@@ -1,10 +1,12 @@
 
 use axum::async_trait;
 use deadpool_diesel::postgres::{Manager, Object, Pool};
-use deadpool_diesel::Runtime;
+use deadpool_diesel::{InteractError, Runtime};
+use diesel::{PgConnection, RunQueryDsl};
+use tracing::Instrument;
 use crate::port::output::DbManager;
 use super::error::{Result, Error, adapt_db_error};
-use super::schema::{tb_ldm_usr, coin, network, coin_network, reward_claim, mission_submit, detailed_posting};
+use super::schema::{tb_ldm_usr, coin, network, coin_network, reward_claim, mission_submit, detailed_posting, outbox_event, webhook_delivery, audit_log, api_key, rate_limit_bucket};
 pub mod user_repository_impl;
 pub mod coin_repository_impl;
 pub mod network_repository_impl;
@@ -12,6 +14,11 @@ pub mod coin_network_repository_impl;
 pub mod reward_claim_repository_impl;
 pub mod mission_repository_impl;
 pub mod detailed_posting_repository_impl;
+pub mod outbox_event_repository_impl;
+pub mod webhook_delivery_repository_impl;
+pub mod audit_log_repository_impl;
+pub mod api_key_repository_impl;
+pub mod rate_limit_store_impl;
 
 #[derive(Clone)]
 pub struct PostgresDbManager {
@@ -38,6 +45,26 @@ impl PostgresDbManager {
             db_pool: pool,
         })
     }
+
+    pub fn pool_status(&self) -> DbPoolStatus {
+        let status = self.db_pool.status();
+        DbPoolStatus {
+            max_size: status.max_size as i64,
+            size: status.size as i64,
+            available: status.available as i64,
+            waiting: status.waiting as i64,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DbPoolStatus {
+    pub max_size: i64,
+    pub size: i64,
+    /// idle connections
+    pub available: i64,
+    /// tasks waiting for a connection
+    pub waiting: i64,
 }
 
 #[async_trait]
@@ -46,3 +73,34 @@ impl DbManager for PostgresDbManager {
         self.db_pool.get().await.map_err(|e| Error::from(adapt_db_error(e))) 
     }
 }
+
+/// `interact` inside a `db` span, a child of the caller's span: the query runs on a blocking thread
+/// that would otherwise not show in the trace. `query` names it, `<table>.<operation>`.
+#[async_trait]
+pub trait TracedInteract {
+    async fn traced_interact<F, R>(&self, query: &'static str, f: F) -> core::result::Result<R, InteractError>
+    where
+        F: FnOnce(&mut PgConnection) -> R + Send + 'static,
+        R: Send + 'static;
+}
+
+#[async_trait]
+impl TracedInteract for Object {
+    async fn traced_interact<F, R>(&self, query: &'static str, f: F) -> core::result::Result<R, InteractError>
+    where
+        F: FnOnce(&mut PgConnection) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let span = tracing::info_span!("db", db.system = "postgresql", db.query = query);
+        self.interact(f).instrument(span).await
+    }
+}
+
+/// A `SELECT 1` round trip on a pooled connection.
+pub async fn ping(db_manager: &impl DbManager) -> Result<()> {
+    let conn = db_manager.get_connection().await?;
+    conn.traced_interact("ping", |conn| diesel::sql_query("SELECT 1").execute(conn))
+        .await?
+        .map(|_| ())
+        .map_err(adapt_db_error)
+}
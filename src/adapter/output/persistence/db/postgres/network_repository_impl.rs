@@ -3,8 +3,11 @@ use deadpool_diesel::postgres::Object;
 use diesel::prelude::*;
 use uuid::Uuid;
 use crate::domain::model::network::{Network, NewNetwork, NewNetworkPayload};
+use crate::domain::model::audit_log::{AuditActor, NewAuditLog, ACTION_NETWORK_CREATE, ACTION_NETWORK_SET_ACTIVE, ACTION_NETWORK_UPDATE, ENTITY_NETWORK};
 use crate::port::output::network_repository::NetworkRepository;
 use super::{Error, Result, adapt_db_error, network};
+use super::TracedInteract;
+use super::audit_log_repository_impl::insert_audit_log;
 
 #[derive(Clone, Debug)]
 pub struct PostgresNetworkRepository;
@@ -18,7 +21,7 @@ impl NetworkRepository for PostgresNetworkRepository {
             code: new_network_payload.code,
         };
 
-        conn.interact(move |conn| {
+        conn.traced_interact("network.insert", move |conn| {
             diesel::insert_into(network::table)
                 .values(new_network)
                 .get_result::<Network>(conn)
@@ -28,7 +31,7 @@ impl NetworkRepository for PostgresNetworkRepository {
     }
 
     async fn get(&self, conn: Object, id: Uuid) -> Result<Network> {
-        conn.interact(move |conn| {
+        conn.traced_interact("network.get", move |conn| {
             network::table.find(id).get_result::<Network>(conn)
         })
         .await?
@@ -36,12 +39,105 @@ impl NetworkRepository for PostgresNetworkRepository {
     }
 
     async fn list(&self, conn: Object) -> Result<Vec<Network>> {
-        conn.interact(move |conn| {
+        conn.traced_interact("network.list", move |conn| {
             network::table.load::<Network>(conn)
         })
         .await?
         .map_err(|e| Error::from(adapt_db_error(e)))
     }
+
+    async fn find_by_code(&self, conn: Object, code: String) -> Result<Option<Network>> {
+        conn.traced_interact("network.find_by_code", move |conn| {
+            network::table
+                .filter(network::code.ilike(code))
+                .first::<Network>(conn)
+                .optional()
+        })
+        .await?
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
+
+    async fn create(&self, conn: Object, new_network: NewNetwork, actor: AuditActor) -> Result<Network> {
+        conn.traced_interact("network.create", move |conn| {
+            conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                let created_network = diesel::insert_into(network::table)
+                    .values(new_network)
+                    .get_result::<Network>(conn)?;
+
+                insert_audit_log(conn, NewAuditLog::new(
+                    &actor,
+                    ACTION_NETWORK_CREATE,
+                    ENTITY_NETWORK,
+                    created_network.id,
+                    None,
+                    Some(&created_network),
+                ))?;
+                Ok(created_network)
+            })
+        })
+        .await?
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
+
+    async fn update_name(&self, conn: Object, id: Uuid, name: String, actor: AuditActor) -> Result<Network> {
+        conn.traced_interact("network.update_name", move |conn| {
+            conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                let target_network = network::table
+                    .find(id)
+                    .for_update()
+                    .get_result::<Network>(conn)?;
+
+                let updated_network = diesel::update(network::table.find(id))
+                    .set((
+                        network::name.eq(name),
+                        network::updated_date.eq(chrono::Utc::now().naive_utc()),
+                    ))
+                    .get_result::<Network>(conn)?;
+
+                insert_audit_log(conn, NewAuditLog::new(
+                    &actor,
+                    ACTION_NETWORK_UPDATE,
+                    ENTITY_NETWORK,
+                    id,
+                    Some(&target_network),
+                    Some(&updated_network),
+                ))?;
+                Ok(updated_network)
+            })
+        })
+        .await?
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
+
+    async fn set_active(&self, conn: Object, id: Uuid, is_active: bool, actor: AuditActor) -> Result<Network> {
+        conn.traced_interact("network.set_active", move |conn| {
+            conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                let target_network = network::table
+                    .find(id)
+                    .for_update()
+                    .get_result::<Network>(conn)?;
+
+                let updated_network = diesel::update(network::table.find(id))
+                    .set((
+                        network::is_active.eq(is_active),
+                        network::updated_date.eq(chrono::Utc::now().naive_utc()),
+                    ))
+                    .get_result::<Network>(conn)?;
+
+                insert_audit_log(conn, NewAuditLog::new(
+                    &actor,
+                    ACTION_NETWORK_SET_ACTIVE,
+                    ENTITY_NETWORK,
+                    id,
+                    Some(&target_network),
+                    Some(&updated_network),
+                ))?;
+                Ok(updated_network)
+            })
+        })
+        .await?
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
 }
 
 #[cfg(test)]
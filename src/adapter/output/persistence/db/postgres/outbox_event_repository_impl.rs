@@ -0,0 +1,160 @@
+use axum::async_trait;
+use chrono::NaiveDateTime;
+use deadpool_diesel::postgres::Object;
+use diesel::prelude::*;
+use uuid::Uuid;
+use crate::domain::model::audit_log::{AuditActor, NewAuditLog, ACTION_OUTBOX_EVENT_REQUEUE, ENTITY_OUTBOX_EVENT};
+use crate::domain::model::outbox_event::{NewOutboxEvent, OutboxEvent, OutboxEventStatus};
+use crate::port::output::outbox_event_repository::OutboxEventRepository;
+use super::{Error, Result, adapt_db_error, outbox_event};
+use super::TracedInteract;
+use super::audit_log_repository_impl::insert_audit_log;
+
+/// Appends an outbox event on an already open connection.
+/// Call it inside the same `conn.transaction` as the state change it describes.
+pub fn insert_outbox_event(conn: &mut PgConnection, new_event: NewOutboxEvent) -> QueryResult<usize> {
+    diesel::insert_into(outbox_event::table)
+        .values(new_event)
+        .execute(conn)
+}
+
+#[derive(Clone, Debug)]
+pub struct PostgresOutboxEventRepository;
+
+#[async_trait]
+impl OutboxEventRepository for PostgresOutboxEventRepository {
+    async fn get(&self, conn: Object, event_id: Uuid) -> Result<OutboxEvent> {
+        conn.traced_interact("outbox_event.get", move |conn| {
+            outbox_event::table
+                .find(event_id)
+                .select(OutboxEvent::as_select())
+                .get_result::<OutboxEvent>(conn)
+        })
+        .await?
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
+
+    async fn claim_due(&self, conn: Object, limit: i64, lease_secs: i64) -> Result<Vec<OutboxEvent>> {
+        conn.traced_interact("outbox_event.claim_due", move |conn| {
+            conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                let now = chrono::Utc::now().naive_utc();
+                let due_ids: Vec<Uuid> = outbox_event::table
+                    .filter(outbox_event::status.eq(OutboxEventStatus::Pending))
+                    .filter(outbox_event::next_attempt_date.le(now))
+                    .order(outbox_event::created_date.asc())
+                    .limit(limit)
+                    .select(outbox_event::id)
+                    .for_update()
+                    .skip_locked()
+                    .load::<Uuid>(conn)?;
+
+                if due_ids.is_empty() {
+                    return Ok(vec![]);
+                }
+
+                let mut events = diesel::update(outbox_event::table)
+                    .filter(outbox_event::id.eq_any(due_ids))
+                    .set((
+                        outbox_event::next_attempt_date.eq(now + chrono::Duration::seconds(lease_secs)),
+                        outbox_event::updated_date.eq(now),
+                    ))
+                    .returning(OutboxEvent::as_select())
+                    .get_results::<OutboxEvent>(conn)?;
+                events.sort_by_key(|event| event.created_date);
+                Ok(events)
+            })
+        })
+        .await?
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
+
+    async fn mark_delivered(&self, conn: Object, event_id: Uuid) -> Result<OutboxEvent> {
+        conn.traced_interact("outbox_event.mark_delivered", move |conn| {
+            let now = chrono::Utc::now().naive_utc();
+            diesel::update(outbox_event::table)
+                .filter(outbox_event::id.eq(event_id))
+                .set((
+                    outbox_event::status.eq(OutboxEventStatus::Delivered),
+                    outbox_event::attempts.eq(outbox_event::attempts + 1),
+                    outbox_event::last_error.eq(None::<String>),
+                    outbox_event::delivered_date.eq(Some(now)),
+                    outbox_event::updated_date.eq(now),
+                ))
+                .returning(OutboxEvent::as_select())
+                .get_result::<OutboxEvent>(conn)
+        })
+        .await?
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
+
+    async fn mark_retry(&self, conn: Object, event_id: Uuid, attempts: i32, next_attempt_date: NaiveDateTime, last_error: String) -> Result<OutboxEvent> {
+        conn.traced_interact("outbox_event.mark_retry", move |conn| {
+            diesel::update(outbox_event::table)
+                .filter(outbox_event::id.eq(event_id))
+                .set((
+                    outbox_event::attempts.eq(attempts),
+                    outbox_event::next_attempt_date.eq(next_attempt_date),
+                    outbox_event::last_error.eq(Some(last_error)),
+                    outbox_event::updated_date.eq(chrono::Utc::now().naive_utc()),
+                ))
+                .returning(OutboxEvent::as_select())
+                .get_result::<OutboxEvent>(conn)
+        })
+        .await?
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
+
+    async fn mark_dead_letter(&self, conn: Object, event_id: Uuid, attempts: i32, last_error: String) -> Result<OutboxEvent> {
+        conn.traced_interact("outbox_event.mark_dead_letter", move |conn| {
+            diesel::update(outbox_event::table)
+                .filter(outbox_event::id.eq(event_id))
+                .set((
+                    outbox_event::status.eq(OutboxEventStatus::DeadLetter),
+                    outbox_event::attempts.eq(attempts),
+                    outbox_event::last_error.eq(Some(last_error)),
+                    outbox_event::updated_date.eq(chrono::Utc::now().naive_utc()),
+                ))
+                .returning(OutboxEvent::as_select())
+                .get_result::<OutboxEvent>(conn)
+        })
+        .await?
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
+
+    async fn requeue(&self, conn: Object, event_id: Uuid, actor: AuditActor) -> Result<OutboxEvent> {
+        conn.traced_interact("outbox_event.requeue", move |conn| {
+            conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                let target_event = outbox_event::table
+                    .find(event_id)
+                    .for_update()
+                    .select(OutboxEvent::as_select())
+                    .get_result::<OutboxEvent>(conn)?;
+
+                let now = chrono::Utc::now().naive_utc();
+                let requeued_event = diesel::update(outbox_event::table)
+                    .filter(outbox_event::id.eq(event_id))
+                    .set((
+                        outbox_event::status.eq(OutboxEventStatus::Pending),
+                        outbox_event::attempts.eq(0),
+                        outbox_event::next_attempt_date.eq(now),
+                        outbox_event::delivered_date.eq(None::<NaiveDateTime>),
+                        outbox_event::updated_date.eq(now),
+                    ))
+                    .returning(OutboxEvent::as_select())
+                    .get_result::<OutboxEvent>(conn)?;
+
+                insert_audit_log(conn, NewAuditLog::new(
+                    &actor,
+                    ACTION_OUTBOX_EVENT_REQUEUE,
+                    ENTITY_OUTBOX_EVENT,
+                    event_id,
+                    Some(&target_event),
+                    Some(&requeued_event),
+                ))?;
+                Ok(requeued_event)
+            })
+        })
+        .await?
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
+}
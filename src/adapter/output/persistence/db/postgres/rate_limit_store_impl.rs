@@ -0,0 +1,94 @@
+use std::sync::Arc;
+use axum::async_trait;
+use diesel::prelude::*;
+use crate::domain::model::rate_limit::{take_token, RateLimitBucket, RateLimitDecision, TokenBucketConfig};
+use crate::port::output::rate_limit_store::RateLimitStore;
+use crate::port::output::DbManager;
+use super::{Error, PostgresDbManager, Result, adapt_db_error, rate_limit_bucket};
+use super::TracedInteract;
+
+/// Token buckets shared by every instance through the `rate_limit_bucket` table.
+/// The bucket row is locked while a token is taken, so concurrent requests for one key are serialized.
+#[derive(Clone)]
+pub struct PostgresRateLimitStore {
+    db_manager: Arc<PostgresDbManager>,
+}
+
+impl PostgresRateLimitStore {
+    pub fn new(db_manager: Arc<PostgresDbManager>) -> Self {
+        Self { db_manager }
+    }
+}
+
+#[async_trait]
+impl RateLimitStore for PostgresRateLimitStore {
+    async fn try_acquire(&self, key: &str, bucket: &TokenBucketConfig) -> Result<RateLimitDecision> {
+        let conn = self.db_manager.get_connection().await?;
+        let key = key.to_string();
+        let bucket = *bucket;
+
+        conn.traced_interact("rate_limit.try_acquire", move |conn| {
+            conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                let now = chrono::Utc::now().naive_utc();
+                diesel::insert_into(rate_limit_bucket::table)
+                    .values(RateLimitBucket { bucket_key: key.clone(), tokens: bucket.capacity, updated_date: now })
+                    .on_conflict_do_nothing()
+                    .execute(conn)?;
+
+                let current = rate_limit_bucket::table
+                    .find(&key)
+                    .for_update()
+                    .select(RateLimitBucket::as_select())
+                    .get_result::<RateLimitBucket>(conn)?;
+
+                let elapsed_secs = (now - current.updated_date).num_milliseconds() as f64 / 1000.0;
+                let (remaining, decision) = take_token(current.tokens, elapsed_secs, &bucket);
+
+                diesel::update(rate_limit_bucket::table.find(&key))
+                    .set((
+                        rate_limit_bucket::tokens.eq(remaining),
+                        rate_limit_bucket::updated_date.eq(now.max(current.updated_date)),
+                    ))
+                    .execute(conn)?;
+                Ok(decision)
+            })
+        })
+        .await?
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
+
+    async fn purge_idle(&self, idle_secs: u64) -> Result<usize> {
+        let conn = self.db_manager.get_connection().await?;
+        let idle_since = chrono::Utc::now().naive_utc() - chrono::Duration::seconds(idle_secs as i64);
+
+        conn.traced_interact("rate_limit.purge_idle", move |conn| {
+            diesel::delete(rate_limit_bucket::table.filter(rate_limit_bucket::updated_date.lt(idle_since)))
+                .execute(conn)
+        })
+        .await?
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapter::output::persistence::db::_dev_utils;
+    use serial_test::serial;
+    use uuid::Uuid;
+
+    #[serial]
+    #[tokio::test]
+    async fn test_try_acquire() -> Result<()> {
+        let db_manager = _dev_utils::init_test().await;
+        let store = PostgresRateLimitStore::new(Arc::new(db_manager.clone()));
+        let bucket = TokenBucketConfig { capacity: 2.0, refill_per_sec: 0.01 };
+        let key = format!("test:user:{}", Uuid::new_v4());
+
+        assert_eq!(store.try_acquire(&key, &bucket).await?, RateLimitDecision::Allowed);
+        assert_eq!(store.try_acquire(&key, &bucket).await?, RateLimitDecision::Allowed);
+        assert!(matches!(store.try_acquire(&key, &bucket).await?, RateLimitDecision::Limited { .. }));
+
+        Ok(())
+    }
+}
@@ -4,21 +4,38 @@ use axum::async_trait;
 use deadpool_diesel::postgres::Object;
 use diesel::prelude::*;
 use uuid::Uuid;
-use crate::{adapter::output::persistence::db::schema::reward_claim_detail, domain::model::{reward_claim::{NewRewardClaim, ResourceType, RewardClaim, RewardClaimStatus, UpdateRewardClaimStatus}, reward_claim_detail::{NewRewardClaimDetail, RewardClaimDetail}}};
+use crate::{adapter::output::persistence::db::schema::reward_claim_detail, domain::model::{audit_log::{AuditActor, NewAuditLog, ACTION_REWARD_CLAIM_CREATE, ACTION_REWARD_CLAIM_PRICE_SNAPSHOT, ACTION_REWARD_CLAIM_UPDATE_STATUS, ENTITY_REWARD_CLAIM}, outbox_event::{NewOutboxEvent, OutboxEventType}, price::RewardClaimValuation, reward_claim::{NewRewardClaim, ResourceType, RewardClaim, RewardClaimFilter, RewardClaimStatus, UpdateRewardClaimStatus}, reward_claim_detail::{NewRewardClaimDetail, RewardClaimDetail}}};
 use crate::port::output::reward_claim_repository::RewardClaimRepository;
 use super::{Error, Result, adapt_db_error, reward_claim};
+use super::TracedInteract;
+use super::outbox_event_repository_impl::insert_outbox_event;
+use super::audit_log_repository_impl::insert_audit_log;
 
 #[derive(Clone, Debug)]
 pub struct PostgresRewardClaimRepository;
 
 #[async_trait]
 impl RewardClaimRepository for PostgresRewardClaimRepository {
-    async fn insert(&self, conn: Object, new_reward_claim: NewRewardClaim) -> Result<RewardClaim> {
-        conn.interact(move |conn| {
-            diesel::insert_into(reward_claim::table)
-                .values(new_reward_claim)
-                .returning(RewardClaim::as_select()) 
-                .get_result::<RewardClaim>(conn)
+    async fn insert(&self, conn: Object, new_reward_claim: NewRewardClaim, actor: AuditActor) -> Result<RewardClaim> {
+        conn.traced_interact("reward_claim.insert", move |conn| {
+            conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                let inserted_claim = diesel::insert_into(reward_claim::table)
+                    .values(new_reward_claim)
+                    .returning(RewardClaim::as_select()) 
+                    .get_result::<RewardClaim>(conn)?;
+
+                let event_type = OutboxEventType::from_claim_transition(None, &inserted_claim.reward_claim_status);
+                insert_outbox_event(conn, NewOutboxEvent::from_reward_claim(event_type, &inserted_claim))?;
+                insert_audit_log(conn, NewAuditLog::new(
+                    &actor,
+                    ACTION_REWARD_CLAIM_CREATE,
+                    ENTITY_REWARD_CLAIM,
+                    inserted_claim.id,
+                    None,
+                    Some(&inserted_claim),
+                ))?;
+                Ok(inserted_claim)
+            })
         })
         .await?
         .map_err(|e| Error::from(adapt_db_error(e)))
@@ -31,7 +48,7 @@ impl RewardClaimRepository for PostgresRewardClaimRepository {
         resource_id: Uuid,
         user_id: Uuid
     ) -> Result<RewardClaim> {
-        conn.interact(move |conn| {
+        conn.traced_interact("reward_claim.get_by_resource_and_user", move |conn| {
             reward_claim::table
                 .filter(reward_claim::resource_type.eq(resource_type))
                 .filter(reward_claim::resource_id.eq(resource_id))
@@ -45,7 +62,7 @@ impl RewardClaimRepository for PostgresRewardClaimRepository {
     }
 
     async fn insert_detail(&self, conn: Object, new_reward_claim_detail: NewRewardClaimDetail) -> Result<RewardClaimDetail> {
-        conn.interact(move |conn| {
+        conn.traced_interact("reward_claim.insert_detail", move |conn| {
             diesel::insert_into(reward_claim_detail::table)
                 .values(new_reward_claim_detail)
                 .get_result::<RewardClaimDetail>(conn)
@@ -54,8 +71,53 @@ impl RewardClaimRepository for PostgresRewardClaimRepository {
         .map_err(|e| Error::from(adapt_db_error(e)))
     }
 
+    async fn list_details(&self, conn: Object, reward_claim_id: Uuid) -> Result<Vec<RewardClaimDetail>> {
+        conn.traced_interact("reward_claim.list_details", move |conn| {
+            reward_claim_detail::table
+                .filter(reward_claim_detail::reward_claim_id.eq(reward_claim_id))
+                .order(reward_claim_detail::created_date.desc())
+                .select(RewardClaimDetail::as_select())
+                .load::<RewardClaimDetail>(conn)
+        })
+        .await?
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
+
+    async fn get(&self, conn: Object, reward_claim_id: Uuid) -> Result<RewardClaim> {
+        conn.traced_interact("reward_claim.get", move |conn| {
+            reward_claim::table
+                .filter(reward_claim::id.eq(reward_claim_id))
+                .select(RewardClaim::as_select())
+                .first::<RewardClaim>(conn)
+        })
+        .await?
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
+
+    async fn list(&self, conn: Object, filter: RewardClaimFilter) -> Result<Vec<RewardClaim>> {
+        conn.traced_interact("reward_claim.list", move |conn| {
+            let mut query = reward_claim::table.into_boxed();
+            if let Some(status) = filter.status {
+                query = query.filter(reward_claim::reward_claim_status.eq(status));
+            }
+            if let Some(user_id) = filter.user_id {
+                query = query.filter(reward_claim::user_id.eq(user_id));
+            }
+            if let Some(updated_after) = filter.updated_after {
+                query = query.filter(reward_claim::updated_date.ge(updated_after));
+            }
+            query
+                .order(reward_claim::updated_date.desc())
+                .limit(filter.limit)
+                .select(RewardClaim::as_select())
+                .load::<RewardClaim>(conn)
+        })
+        .await?
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
+
     async fn list_all_by_user(&self, conn: Object, user_id: Uuid) -> Result<Vec<(RewardClaim, RewardClaimDetail)>> {
-        let result = conn.interact(move |conn| {
+        let result = conn.traced_interact("reward_claim.list_all_by_user", move |conn| {
             reward_claim::table
                 .filter(reward_claim::user_id.eq(user_id))
                 .inner_join(reward_claim_detail::table)
@@ -79,8 +141,8 @@ impl RewardClaimRepository for PostgresRewardClaimRepository {
         }
     }
 
-    async fn update_status(&self, conn: Object, reward_claim_id: Uuid, status: RewardClaimStatus, retryable: bool) -> Result<RewardClaim>{
-        conn.interact(move |conn| {
+    async fn update_status(&self, conn: Object, reward_claim_id: Uuid, status: RewardClaimStatus, retryable: bool, actor: AuditActor) -> Result<RewardClaim>{
+        conn.traced_interact("reward_claim.update_status", move |conn| {
             conn.transaction(|conn| {
                 let target_claim = reward_claim::table
                     .filter(reward_claim::id.eq(reward_claim_id))
@@ -101,11 +163,53 @@ impl RewardClaimRepository for PostgresRewardClaimRepository {
                     updated_date: chrono::Utc::now().naive_utc(),
                 };
 
-                diesel::update(reward_claim::table)
+                let updated_claim = diesel::update(reward_claim::table)
                     .filter(reward_claim::id.eq(target_claim.id))
                     .set(&changes)
                     .returning(RewardClaim::as_select())
-                    .get_result::<RewardClaim>(conn)
+                    .get_result::<RewardClaim>(conn)?;
+
+                let event_type = OutboxEventType::from_claim_transition(Some(&target_claim.reward_claim_status), &updated_claim.reward_claim_status);
+                insert_outbox_event(conn, NewOutboxEvent::from_reward_claim(event_type, &updated_claim))?;
+                insert_audit_log(conn, NewAuditLog::new(
+                    &actor,
+                    ACTION_REWARD_CLAIM_UPDATE_STATUS,
+                    ENTITY_REWARD_CLAIM,
+                    updated_claim.id,
+                    Some(&target_claim),
+                    Some(&updated_claim),
+                ))?;
+                Ok(updated_claim)
+            })
+        })
+        .await?
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
+
+    async fn update_valuation(&self, conn: Object, reward_claim_id: Uuid, valuation: RewardClaimValuation, actor: AuditActor) -> Result<RewardClaim> {
+        conn.traced_interact("reward_claim.update_valuation", move |conn| {
+            conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                let target_claim = reward_claim::table
+                    .filter(reward_claim::id.eq(reward_claim_id))
+                    .for_update()
+                    .select(RewardClaim::as_select())
+                    .first::<RewardClaim>(conn)?;
+
+                let updated_claim = diesel::update(reward_claim::table)
+                    .filter(reward_claim::id.eq(target_claim.id))
+                    .set(&valuation)
+                    .returning(RewardClaim::as_select())
+                    .get_result::<RewardClaim>(conn)?;
+
+                insert_audit_log(conn, NewAuditLog::new(
+                    &actor,
+                    ACTION_REWARD_CLAIM_PRICE_SNAPSHOT,
+                    ENTITY_REWARD_CLAIM,
+                    updated_claim.id,
+                    Some(&target_claim),
+                    Some(&updated_claim),
+                ))?;
+                Ok(updated_claim)
             })
         })
         .await?
@@ -120,6 +224,7 @@ mod tests {
     use crate::adapter::output::persistence::db::_dev_utils;
     use crate::domain::model::reward_claim::{ResourceType, RewardClaimStatus};
     use crate::domain::model::reward_claim_detail::NewRewardClaimDetail;
+    use crate::domain::model::price::FiatRates;
     use crate::port::output::reward_claim_repository::RewardClaimRepository;
     use crate::port::output::DbManager;
     use bigdecimal::BigDecimal;
@@ -144,7 +249,7 @@ mod tests {
             reward_claim_status: RewardClaimStatus::TransactionApproved,
         };
 
-        let inserted_claim = repo.insert(db_manager.get_connection().await?, new_reward_claim.clone()).await?;
+        let inserted_claim = repo.insert(db_manager.get_connection().await?, new_reward_claim.clone(), AuditActor::system()).await?;
         let fetched_claim = repo.get_by_resource_and_user(db_manager.get_connection().await?, resource_type.clone(), new_reward_claim.resource_id, new_reward_claim.user_id).await?;
         assert_eq!(fetched_claim.id, inserted_claim.id);
         assert_eq!(fetched_claim.resource_id, inserted_claim.resource_id);
@@ -176,7 +281,7 @@ mod tests {
             reward_claim_status: RewardClaimStatus::TransactionApproved,
         };
         
-        let inserted_claim = repo.insert(db_manager.get_connection().await?, new_reward_claim.clone()).await?;
+        let inserted_claim = repo.insert(db_manager.get_connection().await?, new_reward_claim.clone(), AuditActor::system()).await?;
 
         let new_reward_claim_detail = NewRewardClaimDetail {
             id: Uuid::new_v4(),
@@ -215,7 +320,7 @@ mod tests {
             reward_claim_status: RewardClaimStatus::Ready,
         };
 
-        let result_1 = repo.insert(db_manager.get_connection().await?, new_reward_claim_1).await;
+        let result_1 = repo.insert(db_manager.get_connection().await?, new_reward_claim_1, AuditActor::system()).await;
         assert!(result_1.is_ok());
 
         let new_reward_claim_2 = NewRewardClaim {
@@ -229,7 +334,7 @@ mod tests {
             reward_claim_status: RewardClaimStatus::Ready,
         };
 
-        let result_2 = repo.insert(db_manager.get_connection().await?, new_reward_claim_2).await;
+        let result_2 = repo.insert(db_manager.get_connection().await?, new_reward_claim_2, AuditActor::system()).await;
         assert!(result_2.is_ok()); 
 
         let new_reward_claim_3 = NewRewardClaim {
@@ -243,7 +348,7 @@ mod tests {
             reward_claim_status: RewardClaimStatus::Ready,
         };
 
-        let result_3 = repo.insert(db_manager.get_connection().await?, new_reward_claim_3).await;
+        let result_3 = repo.insert(db_manager.get_connection().await?, new_reward_claim_3, AuditActor::system()).await;
         assert!(result_3.is_err()); 
 
 
@@ -299,8 +404,8 @@ mod tests {
             reward_claim_status: RewardClaimStatus::TransactionApproved,
         };
 
-        let inserted_claim_1 = repo.insert(db_manager.get_connection().await?, new_reward_claim_1.clone()).await?;
-        let inserted_claim_2 = repo.insert(db_manager.get_connection().await?, new_reward_claim_2.clone()).await?;
+        let inserted_claim_1 = repo.insert(db_manager.get_connection().await?, new_reward_claim_1.clone(), AuditActor::system()).await?;
+        let inserted_claim_2 = repo.insert(db_manager.get_connection().await?, new_reward_claim_2.clone(), AuditActor::system()).await?;
 
         let new_reward_claim_detail_1_1 = NewRewardClaimDetail {
             id: Uuid::new_v4(),
@@ -379,12 +484,98 @@ mod tests {
             reward_claim_status: RewardClaimStatus::Ready,
         };
 
-        let inserted_claim = repo.insert(db_manager.get_connection().await?, new_reward_claim.clone()).await?;
-        let updated_claim = repo.update_status(db_manager.get_connection().await?, inserted_claim.id, RewardClaimStatus::TransactionApproved, false).await?;
+        let inserted_claim = repo.insert(db_manager.get_connection().await?, new_reward_claim.clone(), AuditActor::system()).await?;
+        let updated_claim = repo.update_status(db_manager.get_connection().await?, inserted_claim.id, RewardClaimStatus::TransactionApproved, false, AuditActor::system()).await?;
         
         assert_eq!(updated_claim.reward_claim_status, RewardClaimStatus::TransactionApproved);
         assert_ne!(updated_claim.updated_date, inserted_claim.updated_date);
 
         Ok(())
     }
+
+    #[serial]
+    #[tokio::test]
+    async fn test_update_valuation() -> Result<()> {
+        let db_manager = _dev_utils::init_test().await;
+        let repo = PostgresRewardClaimRepository;
+
+        let new_reward_claim = NewRewardClaim {
+            id: Uuid::new_v4(),
+            resource_id: Uuid::new_v4(),
+            resource_type: ResourceType::Mission,
+            coin_network_id: Uuid::new_v4(),
+            amount: BigDecimal::from(1_500_000),
+            user_id: Uuid::new_v4(),
+            user_address: "test_address".to_string(),
+            reward_claim_status: RewardClaimStatus::TransactionApproved,
+        };
+
+        let inserted_claim = repo.insert(db_manager.get_connection().await?, new_reward_claim.clone(), AuditActor::system()).await?;
+        assert!(inserted_claim.usd_value.is_none());
+
+        let rates = FiatRates { usd: BigDecimal::from(2), krw: BigDecimal::from(2700), source: "static".to_string() };
+        let valuation = RewardClaimValuation::new(&inserted_claim.amount, 6, rates);
+        let updated_claim = repo.update_valuation(db_manager.get_connection().await?, inserted_claim.id, valuation, AuditActor::system()).await?;
+
+        assert_eq!(updated_claim.usd_rate, Some(BigDecimal::from(2)));
+        assert_eq!(updated_claim.usd_value, Some(BigDecimal::from(3)));
+        assert_eq!(updated_claim.krw_value, Some(BigDecimal::from(4050)));
+        assert_eq!(updated_claim.price_source.as_deref(), Some("static"));
+        assert!(updated_claim.priced_date.is_some());
+        assert_eq!(updated_claim.reward_claim_status, RewardClaimStatus::TransactionApproved);
+
+        Ok(())
+    }
+
+    #[serial]
+    #[tokio::test]
+    async fn test_get_and_list() -> Result<()> {
+        let db_manager = _dev_utils::init_test().await;
+        let repo = PostgresRewardClaimRepository;
+        let user_id = Uuid::new_v4();
+
+        let new_reward_claim = |status: RewardClaimStatus| NewRewardClaim {
+            id: Uuid::new_v4(),
+            resource_id: Uuid::new_v4(),
+            resource_type: ResourceType::Mission,
+            coin_network_id: Uuid::new_v4(),
+            amount: BigDecimal::from(10000),
+            user_id,
+            user_address: "test_address".to_string(),
+            reward_claim_status: status,
+        };
+        let failed_claim = repo.insert(db_manager.get_connection().await?, new_reward_claim(RewardClaimStatus::TransactionFailed), AuditActor::system()).await?;
+        repo.insert(db_manager.get_connection().await?, new_reward_claim(RewardClaimStatus::TransactionApproved), AuditActor::system()).await?;
+
+        let fetched_claim = repo.get(db_manager.get_connection().await?, failed_claim.id).await?;
+        assert_eq!(fetched_claim.id, failed_claim.id);
+        assert!(repo.get(db_manager.get_connection().await?, Uuid::new_v4()).await.is_err());
+
+        let all_claims = repo.list(db_manager.get_connection().await?, RewardClaimFilter { user_id: Some(user_id), limit: 10, ..Default::default() }).await?;
+        assert_eq!(all_claims.len(), 2);
+
+        let failed_claims = repo.list(db_manager.get_connection().await?, RewardClaimFilter {
+            status: Some(RewardClaimStatus::TransactionFailed),
+            user_id: Some(user_id),
+            limit: 10,
+            ..Default::default()
+        }).await?;
+        assert_eq!(failed_claims.len(), 1);
+        assert_eq!(failed_claims[0].id, failed_claim.id);
+
+        for transaction_hash in ["test_hash_1", "test_hash_2"] {
+            repo.insert_detail(db_manager.get_connection().await?, NewRewardClaimDetail {
+                id: Uuid::new_v4(),
+                reward_claim_id: failed_claim.id,
+                transaction_hash: transaction_hash.to_string(),
+                sended_user_id: user_id,
+                sended_user_address: "sended_address".to_string(),
+            }).await?;
+        }
+        let details = repo.list_details(db_manager.get_connection().await?, failed_claim.id).await?;
+        assert_eq!(details.len(), 2);
+        assert_eq!(details[0].transaction_hash, "test_hash_2");
+
+        Ok(())
+    }
 }
\ No newline at end of file
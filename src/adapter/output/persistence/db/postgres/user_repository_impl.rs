@@ -5,6 +5,7 @@ use uuid::Uuid;
 use crate::domain::model::user::{NewUser, NewUserPayload, User};
 use crate::port::output::UserRepository;
 use super::{Error, Result, adapt_db_error, tb_ldm_usr};
+use super::TracedInteract;
 
 #[derive(Clone, Debug)]
 pub struct PostgresUserRepository;
@@ -19,7 +20,7 @@ impl UserRepository for PostgresUserRepository {
             phn_nmb: new_user_payload.phn_nmb,
         };
 
-        conn.interact(move |conn| {
+        conn.traced_interact("user.insert", move |conn| {
             diesel::insert_into(tb_ldm_usr::table)
                 .values(new_user)
                 .get_result::<User>(conn)
@@ -30,7 +31,7 @@ impl UserRepository for PostgresUserRepository {
     }
 
     async fn get(&self, conn: Object, user_id: Uuid) -> Result<User> {
-        conn.interact(move |conn| {
+        conn.traced_interact("user.get", move |conn| {
             tb_ldm_usr::table
                 .find(user_id)
                 .get_result::<User>(conn)
@@ -0,0 +1,60 @@
+use axum::async_trait;
+use deadpool_diesel::postgres::Object;
+use diesel::prelude::*;
+use uuid::Uuid;
+use crate::domain::model::webhook_delivery::{NewWebhookDelivery, WebhookDelivery};
+use crate::port::output::webhook_delivery_repository::WebhookDeliveryRepository;
+use super::{Error, Result, adapt_db_error, webhook_delivery};
+use super::TracedInteract;
+
+#[derive(Clone, Debug)]
+pub struct PostgresWebhookDeliveryRepository;
+
+#[async_trait]
+impl WebhookDeliveryRepository for PostgresWebhookDeliveryRepository {
+    async fn insert(&self, conn: Object, new_delivery: NewWebhookDelivery) -> Result<WebhookDelivery> {
+        conn.traced_interact("webhook_delivery.insert", move |conn| {
+            diesel::insert_into(webhook_delivery::table)
+                .values(new_delivery)
+                .returning(WebhookDelivery::as_select())
+                .get_result::<WebhookDelivery>(conn)
+        })
+        .await?
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
+
+    async fn get(&self, conn: Object, delivery_id: Uuid) -> Result<WebhookDelivery> {
+        conn.traced_interact("webhook_delivery.get", move |conn| {
+            webhook_delivery::table
+                .find(delivery_id)
+                .select(WebhookDelivery::as_select())
+                .get_result::<WebhookDelivery>(conn)
+        })
+        .await?
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
+
+    async fn list_by_event(&self, conn: Object, event_id: Uuid) -> Result<Vec<WebhookDelivery>> {
+        conn.traced_interact("webhook_delivery.list_by_event", move |conn| {
+            webhook_delivery::table
+                .filter(webhook_delivery::event_id.eq(event_id))
+                .order(webhook_delivery::created_date.desc())
+                .select(WebhookDelivery::as_select())
+                .load::<WebhookDelivery>(conn)
+        })
+        .await?
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
+
+    async fn list_recent(&self, conn: Object, limit: i64) -> Result<Vec<WebhookDelivery>> {
+        conn.traced_interact("webhook_delivery.list_recent", move |conn| {
+            webhook_delivery::table
+                .order(webhook_delivery::created_date.desc())
+                .limit(limit)
+                .select(WebhookDelivery::as_select())
+                .load::<WebhookDelivery>(conn)
+        })
+        .await?
+        .map_err(|e| Error::from(adapt_db_error(e)))
+    }
+}
@@ -5,6 +5,10 @@ pub mod sql_types {
     #[diesel(postgres_type(name = "coin_type"))]
     pub struct CoinType;
 
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "outbox_event_status"))]
+    pub struct OutboxEventStatus;
+
     #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
     #[diesel(postgres_type(name = "resource_type"))]
     pub struct ResourceType;
@@ -14,6 +18,49 @@ pub mod sql_types {
     pub struct RewardClaimStatus;
 }
 
+diesel::table! {
+    api_key (id) {
+        id -> Uuid,
+        #[max_length = 100]
+        name -> Varchar,
+        #[max_length = 16]
+        key_prefix -> Varchar,
+        #[max_length = 64]
+        key_hash -> Varchar,
+        scopes -> Array<Text>,
+        expires_date -> Nullable<Timestamp>,
+        revoked_date -> Nullable<Timestamp>,
+        last_used_date -> Nullable<Timestamp>,
+        rotated_from -> Nullable<Uuid>,
+        #[max_length = 100]
+        created_by -> Varchar,
+        created_date -> Timestamp,
+        updated_date -> Timestamp,
+    }
+}
+
+diesel::table! {
+    audit_log (id) {
+        id -> Uuid,
+        request_id -> Nullable<Uuid>,
+        #[max_length = 100]
+        actor_id -> Varchar,
+        #[max_length = 20]
+        actor_type -> Varchar,
+        #[max_length = 100]
+        action -> Varchar,
+        #[max_length = 50]
+        entity_type -> Varchar,
+        #[max_length = 100]
+        entity_id -> Varchar,
+        before_value -> Nullable<Jsonb>,
+        after_value -> Nullable<Jsonb>,
+        created_date -> Timestamp,
+        #[max_length = 100]
+        on_behalf_of -> Nullable<Varchar>,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use super::sql_types::CoinType;
@@ -28,6 +75,7 @@ diesel::table! {
         decimals -> Int4,
         created_date -> Timestamp,
         updated_date -> Timestamp,
+        is_active -> Bool,
     }
 }
 
@@ -40,6 +88,11 @@ diesel::table! {
         contract_address -> Nullable<Varchar>,
         created_date -> Timestamp,
         updated_date -> Timestamp,
+        is_active -> Bool,
+        deprecated_date -> Nullable<Timestamp>,
+        #[max_length = 300]
+        metadata_mismatch -> Nullable<Varchar>,
+        metadata_checked_date -> Nullable<Timestamp>,
     }
 }
 
@@ -95,6 +148,38 @@ diesel::table! {
         code -> Varchar,
         created_date -> Timestamp,
         updated_date -> Timestamp,
+        is_active -> Bool,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::OutboxEventStatus;
+
+    outbox_event (id) {
+        id -> Uuid,
+        #[max_length = 50]
+        aggregate_type -> Varchar,
+        aggregate_id -> Uuid,
+        #[max_length = 50]
+        event_type -> Varchar,
+        payload -> Jsonb,
+        status -> OutboxEventStatus,
+        attempts -> Int4,
+        next_attempt_date -> Timestamp,
+        last_error -> Nullable<Text>,
+        delivered_date -> Nullable<Timestamp>,
+        created_date -> Timestamp,
+        updated_date -> Timestamp,
+    }
+}
+
+diesel::table! {
+    rate_limit_bucket (bucket_key) {
+        #[max_length = 200]
+        bucket_key -> Varchar,
+        tokens -> Float8,
+        updated_date -> Timestamp,
     }
 }
 
@@ -115,6 +200,13 @@ diesel::table! {
         updated_date -> Timestamp,
         resource_type -> ResourceType,
         resource_id -> Uuid,
+        usd_rate -> Nullable<Numeric>,
+        krw_rate -> Nullable<Numeric>,
+        usd_value -> Nullable<Numeric>,
+        krw_value -> Nullable<Numeric>,
+        #[max_length = 50]
+        price_source -> Nullable<Varchar>,
+        priced_date -> Nullable<Timestamp>,
     }
 }
 
@@ -152,6 +244,23 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    webhook_delivery (id) {
+        id -> Uuid,
+        event_id -> Uuid,
+        #[max_length = 50]
+        endpoint_name -> Varchar,
+        #[max_length = 255]
+        endpoint_url -> Varchar,
+        attempt -> Int4,
+        success -> Bool,
+        status_code -> Nullable<Int4>,
+        error -> Nullable<Text>,
+        duration_ms -> Int4,
+        created_date -> Timestamp,
+    }
+}
+
 diesel::joinable!(coin_network -> coin (coin_id));
 diesel::joinable!(coin_network -> network (network_id));
 diesel::joinable!(mission -> tb_ldm_usr (usr_id));
@@ -159,16 +268,22 @@ diesel::joinable!(mission_submit -> mission (mission_id));
 diesel::joinable!(mission_submit -> tb_ldm_usr (usr_id));
 diesel::joinable!(reward_claim_detail -> reward_claim (reward_claim_id));
 diesel::joinable!(tb_ldm_usr_rgh -> tb_ldm_usr (id));
+diesel::joinable!(webhook_delivery -> outbox_event (event_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    api_key,
+    audit_log,
     coin,
     coin_network,
     detailed_posting,
     mission,
     mission_submit,
     network,
+    outbox_event,
+    rate_limit_bucket,
     reward_claim,
     reward_claim_detail,
     tb_ldm_usr,
     tb_ldm_usr_rgh,
+    webhook_delivery,
 );
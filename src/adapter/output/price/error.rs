@@ -0,0 +1,22 @@
+use serde::Serialize;
+use serde_with::serde_as;
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[serde_as]
+#[derive(Debug, Serialize, Clone)]
+pub enum Error {
+	SymbolNotSupported { symbol: String },
+	InvalidRate { value: String },
+	RequestFailed { message: String },
+	InvalidResponse { message: String },
+	InvalidConfig { message: String },
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for Error {}
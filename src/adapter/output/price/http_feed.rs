@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use async_trait::async_trait;
+use bigdecimal::BigDecimal;
+use serde_json::Value;
+use crate::config::price_feed::HttpPriceFeedConfig;
+use crate::domain::model::price::FiatRates;
+use crate::port::output::price_feed::PriceFeed;
+use super::error::{Error, Result};
+use super::parse_rate;
+
+const SYMBOL_PLACEHOLDER: &str = "{symbol}";
+
+/// GETs `url` and reads the rates at `usd_pointer` and `krw_pointer`, as numbers or numeric strings.
+/// Works with most public price APIs, e.g. coingecko:
+/// `url = ".../simple/price?ids={symbol}&vs_currencies=usd,krw"`, `usd_pointer = "/{symbol}/usd"`.
+pub struct HttpPriceFeed {
+    name: String,
+    url: String,
+    usd_pointer: String,
+    krw_pointer: String,
+    symbol_ids: HashMap<String, String>,
+    api_key_header: String,
+    api_key: Option<String>,
+    client: reqwest::Client,
+    cache_ttl: Duration,
+    cache: Mutex<HashMap<String, (FiatRates, Instant)>>,
+}
+
+impl HttpPriceFeed {
+    pub fn new(config: &HttpPriceFeedConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(config.timeout_ms))
+            .build()
+            .expect("Failed to build http client");
+
+        Self {
+            name: config.name.clone(),
+            url: config.url.clone(),
+            usd_pointer: config.usd_pointer.clone(),
+            krw_pointer: config.krw_pointer.clone(),
+            symbol_ids: config.symbol_ids.iter().map(|(symbol, id)| (symbol.to_uppercase(), id.clone())).collect(),
+            api_key_header: config.api_key_header.clone(),
+            api_key: config.api_key(),
+            client,
+            cache_ttl: Duration::from_secs(config.cache_secs),
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn provider_id(&self, symbol: &str) -> String {
+        self.symbol_ids
+            .get(&symbol.to_uppercase())
+            .cloned()
+            .unwrap_or_else(|| symbol.to_lowercase())
+    }
+
+    fn cached(&self, symbol: &str) -> Option<FiatRates> {
+        let cache = self.cache.lock().unwrap();
+        cache.get(symbol)
+            .filter(|(_, cached_at)| cached_at.elapsed() < self.cache_ttl)
+            .map(|(rates, _)| rates.clone())
+    }
+
+    fn store(&self, symbol: &str, rates: &FiatRates) {
+        if self.cache_ttl.is_zero() {
+            return;
+        }
+        let mut cache = self.cache.lock().unwrap();
+        cache.insert(symbol.to_string(), (rates.clone(), Instant::now()));
+    }
+}
+
+fn read_rate(body: &Value, pointer: &str) -> Result<BigDecimal> {
+    match body.pointer(pointer) {
+        Some(Value::Number(number)) => parse_rate(&number.to_string()),
+        Some(Value::String(text)) => parse_rate(text),
+        Some(other) => Err(Error::InvalidResponse { message: format!("{pointer} is not a number: {other}") }),
+        None => Err(Error::InvalidResponse { message: format!("no value at {pointer}") }),
+    }
+}
+
+#[async_trait]
+impl PriceFeed for HttpPriceFeed {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn get_rates(&self, symbol: &str) -> Result<FiatRates> {
+        let symbol = symbol.to_uppercase();
+        if let Some(rates) = self.cached(&symbol) {
+            return Ok(rates);
+        }
+
+        let provider_id = self.provider_id(&symbol);
+        let mut request = self.client.get(self.url.replace(SYMBOL_PLACEHOLDER, &provider_id));
+        if let Some(api_key) = &self.api_key {
+            request = request.header(self.api_key_header.as_str(), api_key);
+        }
+        let body = request
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| Error::RequestFailed { message: e.to_string() })?
+            .json::<Value>()
+            .await
+            .map_err(|e| Error::InvalidResponse { message: e.to_string() })?;
+
+        let rates = FiatRates {
+            usd: read_rate(&body, &self.usd_pointer.replace(SYMBOL_PLACEHOLDER, &provider_id))?,
+            krw: read_rate(&body, &self.krw_pointer.replace(SYMBOL_PLACEHOLDER, &provider_id))?,
+            source: self.name.clone(),
+        };
+        self.store(&symbol, &rates);
+        Ok(rates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use axum::{extract::Path, routing::get, Json, Router};
+    use serde_json::json;
+    use super::*;
+
+    /// Serves `{"<id>": {"usd": 5.2, "krw": "7000"}}` on `/price/:id`, `unknown` has no rates.
+    async fn serve_stub(hits: Arc<AtomicUsize>) -> String {
+        let app = Router::new().route("/price/:id", get(move |Path(id): Path<String>| {
+            let hits = Arc::clone(&hits);
+            async move {
+                hits.fetch_add(1, Ordering::SeqCst);
+                if id == "unknown" {
+                    return Json(json!({}));
+                }
+                Json(json!({ id: { "usd": 5.2, "krw": "7000" } }))
+            }
+        }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{addr}")
+    }
+
+    fn stub_config(base_url: &str) -> HttpPriceFeedConfig {
+        HttpPriceFeedConfig {
+            name: "stub".to_string(),
+            url: format!("{base_url}/price/{{symbol}}"),
+            usd_pointer: "/{symbol}/usd".to_string(),
+            krw_pointer: "/{symbol}/krw".to_string(),
+            symbol_ids: HashMap::from([("NEAR".to_string(), "near-protocol".to_string())]),
+            api_key_env: None,
+            api_key_header: "x-api-key".to_string(),
+            timeout_ms: 3_000,
+            cache_secs: 60,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_http_feed_reads_rates_and_caches() -> Result<()> {
+        let hits = Arc::new(AtomicUsize::new(0));
+        let feed = HttpPriceFeed::new(&stub_config(&serve_stub(Arc::clone(&hits)).await));
+
+        let rates = feed.get_rates("near").await?;
+        assert_eq!(rates.usd, BigDecimal::from_str("5.2").unwrap());
+        assert_eq!(rates.krw, BigDecimal::from(7000));
+        assert_eq!(rates.source, "stub");
+
+        feed.get_rates("NEAR").await?;
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+        assert!(matches!(feed.get_rates("unknown").await, Err(Error::InvalidResponse { .. })));
+        Ok(())
+    }
+}
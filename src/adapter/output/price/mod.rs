@@ -0,0 +1,17 @@
+pub mod error;
+pub mod static_feed;
+pub mod http_feed;
+
+use std::str::FromStr;
+use bigdecimal::BigDecimal;
+use self::error::{Error, Result};
+
+/// Rates must be positive, a zero price would silently value every reward at nothing.
+pub fn parse_rate(value: &str) -> Result<BigDecimal> {
+    let rate = BigDecimal::from_str(value.trim())
+        .map_err(|_| Error::InvalidRate { value: value.to_string() })?;
+    if rate <= BigDecimal::from(0) {
+        return Err(Error::InvalidRate { value: value.to_string() });
+    }
+    Ok(rate)
+}
@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+use async_trait::async_trait;
+use bigdecimal::BigDecimal;
+use ::config::{Config, File as ConfigFile};
+use crate::config::price_feed::{PriceFeedConfig, StaticRateConfig};
+use crate::domain::model::price::FiatRates;
+use crate::port::output::price_feed::PriceFeed;
+use super::error::{Error, Result};
+use super::parse_rate;
+
+pub const STATIC_FEED_NAME: &str = "static";
+
+/// Fixed rates keyed by upper case coin symbol.
+pub struct StaticPriceFeed {
+    rates: HashMap<String, (BigDecimal, BigDecimal)>,
+}
+
+impl StaticPriceFeed {
+    pub fn new(rates: HashMap<String, (BigDecimal, BigDecimal)>) -> Self {
+        Self {
+            rates: rates.into_iter().map(|(symbol, rate)| (symbol.to_uppercase(), rate)).collect(),
+        }
+    }
+
+    /// Merges `rates_file` and the inline `rates`, inline entries win.
+    pub fn from_config(config: &PriceFeedConfig) -> Result<Self> {
+        let mut rate_configs: HashMap<String, StaticRateConfig> = match &config.rates_file {
+            Some(rates_file) => Config::builder()
+                .add_source(ConfigFile::with_name(rates_file))
+                .build()
+                .and_then(|file| file.try_deserialize())
+                .map_err(|e| Error::InvalidConfig { message: format!("{rates_file}: {e}") })?,
+            None => HashMap::new(),
+        };
+        rate_configs.extend(config.rates.clone());
+
+        let mut rates = HashMap::new();
+        for (symbol, rate) in rate_configs {
+            let usd = parse_rate(&rate.usd)?;
+            let krw = parse_rate(&rate.krw)?;
+            rates.insert(symbol, (usd, krw));
+        }
+        Ok(Self::new(rates))
+    }
+}
+
+#[async_trait]
+impl PriceFeed for StaticPriceFeed {
+    fn name(&self) -> &str {
+        STATIC_FEED_NAME
+    }
+
+    async fn get_rates(&self, symbol: &str) -> Result<FiatRates> {
+        let (usd, krw) = self.rates
+            .get(&symbol.to_uppercase())
+            .ok_or_else(|| Error::SymbolNotSupported { symbol: symbol.to_string() })?;
+        Ok(FiatRates { usd: usd.clone(), krw: krw.clone(), source: STATIC_FEED_NAME.to_string() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use crate::config::price_feed::PriceFeedSource;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_static_feed_from_config() -> Result<()> {
+        let rates_file = std::env::temp_dir().join(format!("price_rates_{}.toml", uuid::Uuid::new_v4()));
+        std::fs::write(&rates_file, "[NEAR]\nusd = \"4.0\"\nkrw = \"5000\"\n\n[usdt]\nusd = \"1.0\"\nkrw = \"1350.123456789012345678\"\n").unwrap();
+
+        let config = PriceFeedConfig {
+            source: PriceFeedSource::Static,
+            rates: HashMap::from([("NEAR".to_string(), StaticRateConfig { usd: "5.2".to_string(), krw: "7000".to_string() })]),
+            rates_file: Some(rates_file.to_string_lossy().to_string()),
+            http: None,
+        };
+        let feed = StaticPriceFeed::from_config(&config)?;
+        std::fs::remove_file(&rates_file).ok();
+
+        let near = feed.get_rates("near").await?;
+        assert_eq!(near.usd, BigDecimal::from_str("5.2").unwrap());
+        assert_eq!(near.krw, BigDecimal::from(7000));
+        assert_eq!(near.source, STATIC_FEED_NAME);
+
+        let usdt = feed.get_rates("USDT").await?;
+        // --- every digit is kept
+        assert_eq!(usdt.krw, BigDecimal::from_str("1350.123456789012345678").unwrap());
+
+        assert!(matches!(feed.get_rates("ETH").await, Err(Error::SymbolNotSupported { .. })));
+        Ok(())
+    }
+
+    #[test]
+    fn test_static_feed_rejects_non_positive_rate() {
+        let config = PriceFeedConfig {
+            source: PriceFeedSource::Static,
+            rates: HashMap::from([("NEAR".to_string(), StaticRateConfig { usd: "0".to_string(), krw: "7000".to_string() })]),
+            rates_file: None,
+            http: None,
+        };
+        assert!(matches!(StaticPriceFeed::from_config(&config), Err(Error::InvalidRate { .. })));
+    }
+}
@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use async_trait::async_trait;
+use crate::adapter::output::persistence::db::error::Result;
+use crate::domain::model::rate_limit::{take_token, RateLimitDecision, TokenBucketConfig};
+use crate::port::output::rate_limit_store::RateLimitStore;
+
+/// Buckets are evicted once this many keys are tracked; evicted keys start again with a full bucket.
+const MAX_TRACKED_KEYS: usize = 100_000;
+
+/// Tokens left, last update and the config of the group the bucket belongs to.
+type Bucket = (f64, Instant, TokenBucketConfig);
+
+/// Per instance buckets. With several instances each one enforces the limit separately,
+/// use the postgres backend there.
+#[derive(Default)]
+pub struct MemoryRateLimitStore {
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl MemoryRateLimitStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl RateLimitStore for MemoryRateLimitStore {
+    async fn try_acquire(&self, key: &str, bucket: &TokenBucketConfig) -> Result<RateLimitDecision> {
+        let mut buckets = self.buckets.lock().unwrap();
+        let now = Instant::now();
+
+        if buckets.len() >= MAX_TRACKED_KEYS && !buckets.contains_key(key) {
+            // --- each bucket is judged by its own group, a full payout bucket is not a full api bucket
+            buckets.retain(|_, (tokens, updated_at, config)| {
+                *tokens + now.duration_since(*updated_at).as_secs_f64() * config.refill_per_sec < config.capacity
+            });
+            if buckets.len() >= MAX_TRACKED_KEYS {
+                buckets.clear();
+            }
+        }
+
+        let (tokens, updated_at, _) = buckets.get(key).copied().unwrap_or((bucket.capacity, now, *bucket));
+        let (remaining, decision) = take_token(tokens, now.duration_since(updated_at).as_secs_f64(), bucket);
+        buckets.insert(key.to_string(), (remaining, now, *bucket));
+        Ok(decision)
+    }
+
+    async fn purge_idle(&self, idle_secs: u64) -> Result<usize> {
+        let mut buckets = self.buckets.lock().unwrap();
+        let before = buckets.len();
+        buckets.retain(|_, (_, updated_at, _)| updated_at.elapsed() < Duration::from_secs(idle_secs));
+        Ok(before - buckets.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_try_acquire() {
+        let store = MemoryRateLimitStore::new();
+        let bucket = TokenBucketConfig { capacity: 2.0, refill_per_sec: 0.5 };
+
+        assert_eq!(store.try_acquire("user:1", &bucket).await.unwrap(), RateLimitDecision::Allowed);
+        assert_eq!(store.try_acquire("user:1", &bucket).await.unwrap(), RateLimitDecision::Allowed);
+        assert_eq!(store.try_acquire("user:1", &bucket).await.unwrap(), RateLimitDecision::Limited { retry_after_secs: 2 });
+
+        // --- buckets are per key
+        assert_eq!(store.try_acquire("user:2", &bucket).await.unwrap(), RateLimitDecision::Allowed);
+
+        assert_eq!(store.purge_idle(60).await.unwrap(), 0);
+        assert_eq!(store.purge_idle(0).await.unwrap(), 2);
+        assert_eq!(store.try_acquire("user:1", &bucket).await.unwrap(), RateLimitDecision::Allowed);
+    }
+
+    #[test]
+    fn test_take_token_refill() {
+        let bucket = TokenBucketConfig { capacity: 3.0, refill_per_sec: 1.0 };
+        assert_eq!(take_token(0.0, 1.5, &bucket), (0.5, RateLimitDecision::Allowed));
+        assert_eq!(take_token(0.0, 100.0, &bucket), (2.0, RateLimitDecision::Allowed));
+        assert_eq!(take_token(0.25, 0.0, &bucket), (0.25, RateLimitDecision::Limited { retry_after_secs: 1 }));
+    }
+}
@@ -0,0 +1,382 @@
+mod output;
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::time::Instant;
+use bigdecimal::BigDecimal;
+use chrono::{Duration, Utc};
+use clap::{Parser, Subcommand};
+use serde::Serialize;
+use uuid::Uuid;
+use zeroize::Zeroizing;
+use payment::adapter::output::keystore::{self, EncryptedKeystore, KdfParams};
+use payment::config::{config, Config};
+use payment::domain::model::audit_log::{AuditActor, ACTOR_TYPE_CLI};
+use payment::domain::model::coin::{CoinResponse, NewCoinPayload};
+use payment::domain::model::coin_network::{CoinNetworkDetailsResponse, NewCoinNetworkPayload};
+use payment::domain::model::near::{AddSignerKeysPayload, RemoveSignerKeysPayload, SignerKey, SignerKeyChanges};
+use payment::domain::model::reward_claim::{RewardClaimFilter, RewardClaimResponse, RewardClaimStatus};
+use payment::port::output::rpc_client::RpcClient;
+use payment::state::AppState;
+use payment::usecase::error::Error as UsecaseError;
+use payment::Result;
+use self::output::{print_item, print_list, OutputFormat};
+
+const CLAIM_STATUSES: [&str; 4] = ["READY", "TRANSACTION_APPROVED", "TRANSACTION_FAILED", "CANCELLED"];
+const YOCTO_NEAR_SCALE: &str = "1e24";
+
+/// Operations tool for the payment server, reads the same config and env files as the server.
+#[derive(Parser)]
+#[command(name = "payment-admin", version)]
+struct Cli {
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Table, global = true)]
+    output: OutputFormat,
+    /// recorded in the audit log for retries, cancels and new coins, defaults to $USER
+    #[arg(long, global = true)]
+    actor: Option<String>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    #[command(subcommand)]
+    Claims(ClaimsCommand),
+    #[command(subcommand)]
+    Coins(CoinsCommand),
+    /// signer keys of the keys file
+    #[command(subcommand)]
+    Keys(KeysCommand),
+    #[command(subcommand)]
+    Rpc(RpcCommand),
+    /// Report claims whose status disagrees with the chain and stale READY claims. Changes nothing.
+    Reconcile {
+        /// only claims updated in the last N hours
+        #[arg(long)]
+        since_hours: Option<i64>,
+        #[arg(long, default_value_t = 500)]
+        limit: i64,
+    },
+}
+
+#[derive(Subcommand)]
+enum ClaimsCommand {
+    /// newest first
+    List {
+        #[arg(long, value_parser = CLAIM_STATUSES, ignore_case = true)]
+        status: Option<String>,
+        #[arg(long)]
+        user_id: Option<Uuid>,
+        #[arg(long, default_value_t = 50)]
+        limit: i64,
+    },
+    /// a claim with its coin network and every transfer attempt
+    Show { id: Uuid },
+    /// pay a TRANSACTION_FAILED claim again
+    Retry { id: Uuid },
+    /// close a TRANSACTION_FAILED or stale READY claim for good
+    Cancel { id: Uuid },
+}
+
+#[derive(Subcommand)]
+enum CoinsCommand {
+    /// add a coin, and its coin network when --network-id is given
+    Add {
+        #[arg(long)]
+        name: String,
+        #[arg(long)]
+        symbol: String,
+        /// NATIVE, FT or NFT
+        #[arg(long)]
+        coin_type: String,
+        #[arg(long)]
+        decimals: i32,
+        #[arg(long)]
+        network_id: Option<Uuid>,
+        /// token contract, FT only
+        #[arg(long, requires = "network_id")]
+        contract_address: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum KeysCommand {
+    /// public keys with their on-chain nonce and permission
+    List,
+    /// balance of every signer account
+    Balance,
+    /// generate keys, add them on chain in one batch and write them to the keys file
+    Add {
+        #[arg(long)]
+        count: usize,
+        /// required for payouts, function-call keys can not attach deposits
+        #[arg(long)]
+        full_access: bool,
+        /// function-call keys only, defaults to the relayer account
+        #[arg(long, conflicts_with = "full_access")]
+        receiver_id: Option<String>,
+        /// function-call keys only, repeatable, none allows every method
+        #[arg(long = "method-name", conflicts_with = "full_access")]
+        method_names: Vec<String>,
+        /// function-call keys only, gas allowance in yoctoNEAR
+        #[arg(long, conflicts_with = "full_access")]
+        allowance: Option<String>,
+    },
+    /// take keys out of the keys file, then delete them on chain in one batch
+    Remove {
+        /// repeatable
+        #[arg(long = "public-key", required_unless_present = "count", conflicts_with = "count")]
+        public_keys: Vec<String>,
+        /// the newest keys of the keys file
+        #[arg(long)]
+        count: Option<usize>,
+    },
+    /// encrypt a plaintext keys file, the passphrase is read like the server does or prompted
+    Encrypt {
+        input: PathBuf,
+        /// defaults to the input with an `.enc` extension
+        #[arg(long)]
+        output_file: Option<PathBuf>,
+        /// replace an existing output file
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum RpcCommand {
+    /// node status and round trip time
+    Ping,
+    /// probe every configured endpoint: reachability, latency and block lag
+    Endpoints,
+}
+
+#[derive(Serialize)]
+struct SignerKeyRow {
+    account_id: String,
+    public_key: String,
+    nonce: Option<u64>,
+    permission: Option<String>,
+    error: Option<String>,
+}
+
+#[derive(Serialize)]
+struct BalanceRow {
+    account_id: String,
+    amount_near: String,
+    amount: String,
+    locked: String,
+    storage_usage: u64,
+}
+
+#[derive(Serialize)]
+struct KeyChangeRow {
+    change: &'static str,
+    account_id: String,
+    public_key: String,
+}
+
+#[derive(Serialize)]
+struct PingRow {
+    chain_id: String,
+    latest_block_height: u64,
+    latest_block_time: String,
+    syncing: bool,
+    latency_ms: u128,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    // NOTE: stdout is for command output, and the cli must never recreate the local db.
+    std::env::set_var("LOG_STDERR", "true");
+    std::env::set_var("LOCAL_DB_RECREATE", "false");
+
+    let cli = Cli::parse();
+
+    // --- runs before config, which would try to load the keys file
+    if let Command::Keys(KeysCommand::Encrypt { input, output_file, force }) = &cli.command {
+        return match encrypt_keys_file(input, output_file.as_deref(), *force) {
+            Ok(output_file) => {
+                println!("encrypted keys written to {}", output_file.display());
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("error: {e}");
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    let config = config().await;
+
+    match run(cli, config).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(cli: Cli, config: &Config) -> Result<()> {
+    let app_state = AppState::new(config).await?;
+    let format = cli.output;
+    let actor = AuditActor::new(
+        cli.actor.or_else(|| std::env::var("USER").ok()).unwrap_or_else(|| "payment-admin".to_string()),
+        ACTOR_TYPE_CLI,
+        None,
+    );
+
+    match cli.command {
+        Command::Claims(ClaimsCommand::List { status, user_id, limit }) => {
+            let filter = RewardClaimFilter {
+                status: status.map(RewardClaimStatus::from),
+                user_id,
+                limit,
+                ..Default::default()
+            };
+            let claims: Vec<RewardClaimResponse> = app_state.claim_admin_usecase.list_claims(filter).await?
+                .into_iter()
+                .map(RewardClaimResponse::from)
+                .collect();
+            print_list(format, &claims, &["id", "reward_claim_status", "amount", "user_id", "user_address", "usd_value", "updated_date"]);
+        }
+        Command::Claims(ClaimsCommand::Show { id }) => {
+            print_item(format, &app_state.claim_admin_usecase.get_claim(id).await?);
+        }
+        Command::Claims(ClaimsCommand::Retry { id }) => {
+            print_item(format, &app_state.claim_admin_usecase.retry_claim(id, actor).await?);
+        }
+        Command::Claims(ClaimsCommand::Cancel { id }) => {
+            let claim = app_state.claim_admin_usecase.cancel_claim(id, actor).await?;
+            print_item(format, &RewardClaimResponse::from(claim));
+        }
+        Command::Coins(CoinsCommand::Add { name, symbol, coin_type, decimals, network_id, contract_address }) => {
+            let payload = NewCoinPayload { name, symbol, coin_type, decimals };
+            let coin = app_state.coin_admin_usecase.create_coin(payload, actor.clone()).await?;
+            match network_id {
+                Some(network_id) => {
+                    let payload = NewCoinNetworkPayload { coin_id: coin.id, network_id, contract_address };
+                    let coin_network = app_state.coin_admin_usecase.create_coin_network(payload, actor).await?;
+                    print_item(format, &CoinNetworkDetailsResponse::from(coin_network));
+                }
+                None => print_item(format, &CoinResponse::from(coin)),
+            }
+        }
+        Command::Keys(KeysCommand::List) => {
+            let mut rows = Vec::new();
+            for SignerKey { account_id, public_key } in config.signer().public_keys() {
+                let row = match app_state.near_rpc_manager.view_access_key(&account_id, &public_key).await {
+                    Ok(key) => SignerKeyRow { account_id, public_key, nonce: Some(key.nonce), permission: Some(key.permission), error: None },
+                    Err(e) => SignerKeyRow { account_id, public_key, nonce: None, permission: None, error: Some(e.to_string()) },
+                };
+                rows.push(row);
+            }
+            print_list(format, &rows, &["account_id", "public_key", "nonce", "permission", "error"]);
+        }
+        Command::Keys(KeysCommand::Balance) => {
+            let account_ids: BTreeSet<String> = config.signer().public_keys()
+                .into_iter()
+                .map(|key| key.account_id)
+                .collect();
+            let scale_factor = BigDecimal::from_str(YOCTO_NEAR_SCALE).expect("Invalid decimal format");
+            let mut rows = Vec::new();
+            for account_id in account_ids {
+                let balance = app_state.near_rpc_manager.view_account(&account_id).await.map_err(UsecaseError::from)?;
+                let amount = BigDecimal::from_str(&balance.amount).expect("Invalid amount format");
+                rows.push(BalanceRow {
+                    account_id: balance.account_id,
+                    amount_near: (amount / &scale_factor).normalized().to_string(),
+                    amount: balance.amount,
+                    locked: balance.locked,
+                    storage_usage: balance.storage_usage,
+                });
+            }
+            print_list(format, &rows, &["account_id", "amount_near", "locked", "storage_usage"]);
+        }
+        Command::Keys(KeysCommand::Add { count, full_access, receiver_id, method_names, allowance }) => {
+            let payload = AddSignerKeysPayload { count, full_access, receiver_id, method_names, allowance };
+            let changes = app_state.signer_key_usecase.add_keys(payload, actor).await?;
+            print_key_changes(format, changes);
+        }
+        Command::Keys(KeysCommand::Remove { public_keys, count }) => {
+            let payload = RemoveSignerKeysPayload { public_keys, count };
+            let changes = app_state.signer_key_usecase.remove_keys(payload, actor).await?;
+            print_key_changes(format, changes);
+        }
+        Command::Keys(KeysCommand::Encrypt { .. }) => unreachable!("handled before config"),
+        Command::Rpc(RpcCommand::Ping) => {
+            let started = Instant::now();
+            let status = app_state.near_rpc_manager.node_status().await.map_err(UsecaseError::from)?;
+            let row = PingRow {
+                chain_id: status.chain_id,
+                latest_block_height: status.latest_block_height,
+                latest_block_time: status.latest_block_time,
+                syncing: status.syncing,
+                latency_ms: started.elapsed().as_millis(),
+            };
+            print_item(format, &row);
+        }
+        Command::Rpc(RpcCommand::Endpoints) => {
+            app_state.near_rpc_manager.endpoints.probe().await;
+            let statuses = app_state.near_rpc_manager.endpoints.statuses();
+            print_list(format, &statuses, &["url", "priority", "healthy", "latency_ms", "block_height", "block_lag", "last_error"]);
+        }
+        Command::Reconcile { since_hours, limit } => {
+            let updated_after = since_hours.map(|hours| Utc::now().naive_utc() - Duration::hours(hours));
+            let entries = app_state.claim_admin_usecase.reconcile(updated_after, limit).await?;
+            print_list(format, &entries, &["reward_claim_id", "reward_claim_status", "issue", "transaction_hash", "on_chain_status", "message"]);
+        }
+    }
+    Ok(())
+}
+
+fn print_key_changes(format: OutputFormat, changes: SignerKeyChanges) {
+    let added = changes.added.into_iter().map(|key| ("added", key));
+    let removed = changes.removed.into_iter().map(|key| ("removed", key));
+    let rows: Vec<KeyChangeRow> = added
+        .chain(removed)
+        .map(|(change, SignerKey { account_id, public_key })| KeyChangeRow { change, account_id, public_key })
+        .collect();
+    print_list(format, &rows, &["change", "account_id", "public_key"]);
+}
+
+fn encrypt_keys_file(input: &Path, output_file: Option<&Path>, force: bool) -> std::result::Result<PathBuf, String> {
+    let output_file = output_file.map(Path::to_path_buf).unwrap_or_else(|| input.with_extension("enc"));
+    if output_file.exists() && !force {
+        return Err(format!("{} exists, pass --force to replace it", output_file.display()));
+    }
+
+    let plaintext = Zeroizing::new(std::fs::read(input).map_err(|e| format!("read {}: {e}", input.display()))?);
+    if EncryptedKeystore::parse(&plaintext).map_err(|e| e.to_string())?.is_some() {
+        return Err(format!("{} is already encrypted", input.display()));
+    }
+    // --- refuse files the server could not load after decrypting
+    serde_json::from_slice::<Vec<near_crypto::InMemorySigner>>(&plaintext)
+        .map_err(|e| format!("{} is not a keys file: {e}", input.display()))?;
+
+    let passphrase = match keystore::read_passphrase() {
+        Ok(passphrase) => passphrase,
+        Err(keystore::error::Error::PassphraseNotSet) => prompt_new_passphrase()?,
+        Err(e) => return Err(e.to_string()),
+    };
+    let encrypted = EncryptedKeystore::encrypt(&plaintext, &passphrase, KdfParams::new_argon2id()).map_err(|e| e.to_string())?;
+    let contents = serde_json::to_vec_pretty(&encrypted).map_err(|e| e.to_string())?;
+
+    keystore::write_private_file(&output_file, &contents).map_err(|e| e.to_string())?;
+    Ok(output_file)
+}
+
+fn prompt_new_passphrase() -> std::result::Result<Zeroizing<String>, String> {
+    let passphrase = Zeroizing::new(rpassword::prompt_password("keystore passphrase: ").map_err(|e| e.to_string())?);
+    let confirmation = Zeroizing::new(rpassword::prompt_password("repeat passphrase: ").map_err(|e| e.to_string())?);
+    if passphrase.is_empty() {
+        return Err("passphrase must not be empty".to_string());
+    }
+    if passphrase != confirmation {
+        return Err("passphrases do not match".to_string());
+    }
+    Ok(passphrase)
+}
@@ -0,0 +1,102 @@
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::Value;
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum OutputFormat {
+    Table,
+    Json,
+}
+
+/// Prints rows as a table of `columns`, or every field as json.
+pub fn print_list<T: Serialize>(format: OutputFormat, items: &[T], columns: &[&str]) {
+    match format {
+        OutputFormat::Json => print_json(&items),
+        OutputFormat::Table => {
+            let rows: Vec<Value> = items.iter().map(to_value).collect();
+            print!("{}", render_table(&rows, columns));
+        }
+    }
+}
+
+/// Prints one record as `field  value` lines, nested values as compact json.
+pub fn print_item<T: Serialize>(format: OutputFormat, item: &T) {
+    match format {
+        OutputFormat::Json => print_json(item),
+        OutputFormat::Table => {
+            let Value::Object(fields) = to_value(item) else {
+                return print_json(item);
+            };
+            let width = fields.keys().map(String::len).max().unwrap_or(0);
+            for (key, value) in fields.iter() {
+                println!("{:width$}  {}", key, cell(value));
+            }
+        }
+    }
+}
+
+fn print_json<T: Serialize + ?Sized>(value: &T) {
+    println!("{}", serde_json::to_string_pretty(value).expect("output is serializable"));
+}
+
+fn to_value<T: Serialize>(item: &T) -> Value {
+    serde_json::to_value(item).expect("output is serializable")
+}
+
+fn cell(value: &Value) -> String {
+    match value {
+        Value::Null => "-".to_string(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn render_table(rows: &[Value], columns: &[&str]) -> String {
+    let cells: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| columns.iter().map(|column| cell(row.get(*column).unwrap_or(&Value::Null))).collect())
+        .collect();
+    let widths: Vec<usize> = columns
+        .iter()
+        .enumerate()
+        .map(|(index, column)| cells.iter().map(|row| row[index].len()).chain([column.len()]).max().unwrap_or(0))
+        .collect();
+
+    let line = |values: Vec<String>| {
+        values
+            .iter()
+            .zip(widths.iter())
+            .map(|(value, width)| format!("{:width$}", value, width = *width))
+            .collect::<Vec<String>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut table = line(columns.iter().map(|column| column.to_uppercase()).collect());
+    table.push('\n');
+    for row in cells {
+        table.push_str(&line(row));
+        table.push('\n');
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use super::render_table;
+
+    #[test]
+    fn test_render_table() {
+        let rows = vec![
+            json!({"id": "a1", "status": "READY", "amount": 10}),
+            json!({"id": "b22", "status": null, "amount": 1.5}),
+        ];
+
+        assert_eq!(
+            render_table(&rows, &["id", "status", "amount"]),
+            "ID   STATUS  AMOUNT\na1   READY   10\nb22  -       1.5\n",
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use serde::Deserialize;
+use ::config::{Config, ConfigError, File as ConfigFile};
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuthMode {
+    /// trusts the client supplied `x-user-right` header. local only.
+    Header,
+    /// verifies the `access_token` cookie as a signed JWT
+    Jwt,
+    /// asks the session introspection endpoint about the `access_token` cookie
+    Introspection,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub enum JwtAlgorithm {
+    HS256,
+    RS256,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct JwtConfig {
+    pub algorithm: JwtAlgorithm,
+    /// HS256: env variable holding the shared secret
+    #[serde(default)]
+    pub secret_env: Option<String>,
+    /// RS256: PEM encoded public key
+    #[serde(default)]
+    pub public_key_file: Option<String>,
+    /// RS256: JWKS document, the key is picked by the token `kid`. takes precedence over `public_key_file`
+    #[serde(default)]
+    pub jwks_file: Option<String>,
+    #[serde(default)]
+    pub issuer: Option<String>,
+    #[serde(default)]
+    pub audience: Option<String>,
+    #[serde(default = "default_leeway_secs")]
+    pub leeway_secs: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct IntrospectionConfig {
+    pub url: String,
+    /// env variable holding a bearer token sent along with the introspection request
+    #[serde(default)]
+    pub client_secret_env: Option<String>,
+    #[serde(default = "default_introspection_timeout_ms")]
+    pub timeout_ms: u64,
+    /// active sessions are cached for this long. 0 disables the cache
+    #[serde(default = "default_introspection_cache_secs")]
+    pub cache_secs: u64,
+    /// sessions kept at most, the oldest is dropped first
+    #[serde(default = "default_introspection_cache_max_entries")]
+    pub cache_max_entries: usize,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AuthConfig {
+    /// unset: jwt or introspection, whichever table is present, header in local only.
+    /// resolved by `init`, read it through `mode()`
+    #[serde(default)]
+    mode: Option<AuthMode>,
+    #[serde(default)]
+    pub jwt: Option<JwtConfig>,
+    #[serde(default)]
+    pub introspection: Option<IntrospectionConfig>,
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            mode: None,
+            jwt: None,
+            introspection: None,
+        }
+    }
+}
+
+impl AuthConfig {
+    /// Reads the `[auth]` table of `config.toml` and refuses to start without a usable mode.
+    /// The header mode lets any client pick its own identity, so it is refused outside of local.
+    pub fn init(run_mode: &str) -> Self {
+        let result = Config::builder()
+            .add_source(ConfigFile::with_name("config.toml"))
+            .build()
+            .unwrap()
+            .get::<AuthConfig>("auth");
+
+        let auth_config = match result {
+            Ok(auth_config) => auth_config,
+            Err(ConfigError::NotFound(_)) => AuthConfig::default(),
+            Err(e) => panic!("Failed to parse [auth] config: {e}"),
+        };
+
+        let mode = auth_config.resolve_mode(run_mode).unwrap_or_else(|e| panic!("{e}"));
+        AuthConfig { mode: Some(mode), ..auth_config }
+    }
+
+    pub fn mode(&self) -> AuthMode {
+        self.mode.expect("AuthConfig::init resolves the mode")
+    }
+
+    /// Fails closed: outside of local a missing mode is an error naming the tables to add, never the header mode.
+    fn resolve_mode(&self, run_mode: &str) -> Result<AuthMode, String> {
+        let is_local = run_mode == "local";
+        let mode = match self.mode {
+            Some(mode) => mode,
+            None if self.jwt.is_some() => AuthMode::Jwt,
+            None if self.introspection.is_some() => AuthMode::Introspection,
+            None if is_local => AuthMode::Header,
+            None => {
+                return Err(format!(
+                    "[auth] is not configured for run mode {run_mode}, add [auth.jwt] or [auth.introspection] to config.toml"
+                ))
+            }
+        };
+
+        match mode {
+            AuthMode::Header if !is_local => Err(format!(
+                "[auth] mode = \"header\" trusts x-user-right and is only allowed in local, not in {run_mode}. configure [auth.jwt] or [auth.introspection]"
+            )),
+            AuthMode::Jwt if self.jwt.is_none() => Err("[auth] mode = \"jwt\" requires [auth.jwt]".to_string()),
+            AuthMode::Introspection if self.introspection.is_none() => {
+                Err("[auth] mode = \"introspection\" requires [auth.introspection]".to_string())
+            }
+            _ => Ok(mode),
+        }
+    }
+}
+
+fn default_leeway_secs() -> u64 { 30 }
+fn default_introspection_timeout_ms() -> u64 { 3_000 }
+fn default_introspection_cache_secs() -> u64 { 30 }
+fn default_introspection_cache_max_entries() -> usize { 10_000 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn introspection() -> IntrospectionConfig {
+        IntrospectionConfig {
+            url: "http://localhost:8080/internal/sessions/introspect".to_string(),
+            client_secret_env: None,
+            timeout_ms: default_introspection_timeout_ms(),
+            cache_secs: default_introspection_cache_secs(),
+            cache_max_entries: default_introspection_cache_max_entries(),
+        }
+    }
+
+    #[test]
+    fn test_resolve_mode() {
+        let unset = AuthConfig::default();
+        assert_eq!(unset.resolve_mode("local"), Ok(AuthMode::Header));
+        assert!(unset.resolve_mode("development").unwrap_err().contains("[auth.jwt] or [auth.introspection]"));
+
+        let header = AuthConfig { mode: Some(AuthMode::Header), ..AuthConfig::default() };
+        assert!(header.resolve_mode("production").is_err());
+
+        let inferred = AuthConfig { introspection: Some(introspection()), ..AuthConfig::default() };
+        assert_eq!(inferred.resolve_mode("development"), Ok(AuthMode::Introspection));
+
+        let missing = AuthConfig { mode: Some(AuthMode::Jwt), introspection: Some(introspection()), ..AuthConfig::default() };
+        assert!(missing.resolve_mode("development").unwrap_err().contains("[auth.jwt]"));
+    }
+}
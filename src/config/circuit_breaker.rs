@@ -0,0 +1,52 @@
+use std::time::Duration;
+use serde::Deserialize;
+use ::config::{Config, ConfigError, File as ConfigFile};
+
+/// Circuit breaker around near rpc calls, the `[circuit_breaker]` table of `config.toml`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CircuitBreakerConfig {
+    /// consecutive calls failing with timeouts, transport errors, 5xx or 429 (after their retries) that open the breaker
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: u32,
+    /// how long calls fail fast before trial calls go through again
+    #[serde(default = "default_open_secs")]
+    pub open_secs: u64,
+    /// trial calls let through at a time while half-open
+    #[serde(default = "default_half_open_max_calls")]
+    pub half_open_max_calls: u32,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: default_failure_threshold(),
+            open_secs: default_open_secs(),
+            half_open_max_calls: default_half_open_max_calls(),
+        }
+    }
+}
+
+impl CircuitBreakerConfig {
+    /// Reads the `[circuit_breaker]` table of `config.toml`.
+    pub fn init() -> Self {
+        let result = Config::builder()
+            .add_source(ConfigFile::with_name("config.toml"))
+            .build()
+            .unwrap()
+            .get::<CircuitBreakerConfig>("circuit_breaker");
+
+        match result {
+            Ok(circuit_breaker) => circuit_breaker,
+            Err(ConfigError::NotFound(_)) => CircuitBreakerConfig::default(),
+            Err(e) => panic!("Failed to parse [circuit_breaker] config: {e}"),
+        }
+    }
+
+    pub fn open_duration(&self) -> Duration {
+        Duration::from_secs(self.open_secs)
+    }
+}
+
+fn default_failure_threshold() -> u32 { 5 }
+fn default_open_secs() -> u64 { 30 }
+fn default_half_open_max_calls() -> u32 { 1 }
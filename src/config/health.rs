@@ -0,0 +1,68 @@
+use std::time::Duration;
+use serde::Deserialize;
+use ::config::{Config, ConfigError, File as ConfigFile};
+
+/// Thresholds of the readiness probe, the `[health]` table of `config.toml`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct HealthConfig {
+    /// each component check is reported down after this long
+    #[serde(default = "default_check_timeout_ms")]
+    pub check_timeout_ms: u64,
+    /// the rpc is down when its latest block is older than this
+    #[serde(default = "default_max_block_age_secs")]
+    pub max_block_age_secs: u64,
+    /// yoctoNEAR the relayer account must hold, "0" skips the check
+    #[serde(default = "default_min_treasury_balance")]
+    pub min_treasury_balance: String,
+    /// probes within this long of the last checks get the same results, the endpoint takes no auth
+    #[serde(default = "default_cache_ttl_ms")]
+    pub cache_ttl_ms: u64,
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            check_timeout_ms: default_check_timeout_ms(),
+            max_block_age_secs: default_max_block_age_secs(),
+            min_treasury_balance: default_min_treasury_balance(),
+            cache_ttl_ms: default_cache_ttl_ms(),
+        }
+    }
+}
+
+impl HealthConfig {
+    /// Reads the `[health]` table of `config.toml`.
+    pub fn init() -> Self {
+        let result = Config::builder()
+            .add_source(ConfigFile::with_name("config.toml"))
+            .build()
+            .unwrap()
+            .get::<HealthConfig>("health");
+
+        let health_config = match result {
+            Ok(health_config) => health_config,
+            Err(ConfigError::NotFound(_)) => HealthConfig::default(),
+            Err(e) => panic!("Failed to parse [health] config: {e}"),
+        };
+        health_config.min_treasury_balance.parse::<u128>()
+            .unwrap_or_else(|_| panic!("[health] min_treasury_balance must be yoctoNEAR: {}", health_config.min_treasury_balance));
+        health_config
+    }
+
+    pub fn check_timeout(&self) -> Duration {
+        Duration::from_millis(self.check_timeout_ms)
+    }
+
+    pub fn cache_ttl(&self) -> Duration {
+        Duration::from_millis(self.cache_ttl_ms)
+    }
+
+    pub fn min_treasury_balance(&self) -> u128 {
+        self.min_treasury_balance.parse().unwrap_or_default()
+    }
+}
+
+fn default_check_timeout_ms() -> u64 { 3_000 }
+fn default_max_block_age_secs() -> u64 { 120 }
+fn default_min_treasury_balance() -> String { "0".to_string() }
+fn default_cache_ttl_ms() -> u64 { 5_000 }
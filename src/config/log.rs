@@ -2,26 +2,182 @@ use tower_http::classify::{ServerErrorsAsFailures, SharedClassifier};
 use tower_http::trace::TraceLayer;
 use tracing::Level;
 
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 use axum::body::Body;
 use axum::extract::Request;
 use axum::response::Response;
 
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
 use tracing_subscriber::filter;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, Layer, Registry};
 
-use axum::http::{Method, Uri};
-use serde::Serialize;
+use axum::http::{HeaderMap, HeaderName, HeaderValue, Method, StatusCode, Uri};
+use opentelemetry::global;
+use opentelemetry::propagation::{Extractor, Injector};
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::{self as sdktrace, Sampler};
+use opentelemetry_sdk::Resource;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use ::config::{Config, ConfigError, File as ConfigFile};
 use serde_with::skip_serializing_none;
 use std::time::{SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 
-use crate::adapter::input::ctx::Ctx;
+use crate::adapter::input::ctx::{Ctx, RequestId};
 use crate::adapter::input::error::{Result, Error};
+use super::redact::{RedactingMakeWriter, Redactor};
+use super::telemetry::TelemetryConfig;
 
-pub fn init_tracing(run_mode: &str) {
-    let tracing_layer = tracing_subscriber::fmt::layer();
+// region:    --- Log Config
+
+/// Log output, the `[log]` table of `config.toml`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct LogConfig {
+    #[serde(default)]
+    pub format: LogFormat,
+    /// which response bodies `log_request` writes
+    #[serde(default)]
+    pub body: BodyLogging,
+    /// share of successful response bodies written when `body = "sampled"`
+    #[serde(default = "default_body_sample_ratio")]
+    pub body_sample_ratio: f64,
+    /// values of these fields are masked in every log line, see `Redactor`
+    #[serde(default = "default_redact_fields")]
+    pub redact_fields: Vec<String>,
+    /// also write to rolling files
+    pub file: Option<LogFileConfig>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// human readable lines
+    #[default]
+    Pretty,
+    /// one JSON object per line, with the fields of the event and of its spans
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BodyLogging {
+    Off,
+    /// bodies of 4xx and 5xx responses
+    #[default]
+    Errors,
+    /// error bodies and `body_sample_ratio` of the others
+    Sampled,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LogFileConfig {
+    pub directory: PathBuf,
+    #[serde(default = "default_file_prefix")]
+    pub prefix: String,
+    #[serde(default)]
+    pub rotation: LogRotation,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogRotation {
+    Hourly,
+    #[default]
+    Daily,
+    Never,
+}
+
+impl From<LogRotation> for Rotation {
+    fn from(rotation: LogRotation) -> Self {
+        match rotation {
+            LogRotation::Hourly => Rotation::HOURLY,
+            LogRotation::Daily => Rotation::DAILY,
+            LogRotation::Never => Rotation::NEVER,
+        }
+    }
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            format: LogFormat::default(),
+            body: BodyLogging::default(),
+            body_sample_ratio: default_body_sample_ratio(),
+            redact_fields: default_redact_fields(),
+            file: None,
+        }
+    }
+}
+
+impl LogConfig {
+    /// Reads the `[log]` table of `config.toml`.
+    pub fn init() -> Self {
+        let result = Config::builder()
+            .add_source(ConfigFile::with_name("config.toml"))
+            .build()
+            .unwrap()
+            .get::<LogConfig>("log");
+
+        match result {
+            Ok(log_config) => log_config,
+            Err(ConfigError::NotFound(_)) => LogConfig::default(),
+            Err(e) => panic!("Failed to parse [log] config: {e}"),
+        }
+    }
+
+    fn should_log_body(&self, status_code: StatusCode) -> bool {
+        let failed = status_code.is_client_error() || status_code.is_server_error();
+        match self.body {
+            BodyLogging::Off => false,
+            BodyLogging::Errors => failed,
+            BodyLogging::Sampled => failed || rand::random::<f64>() < self.body_sample_ratio,
+        }
+    }
+}
+
+fn default_body_sample_ratio() -> f64 { 0.01 }
+fn default_file_prefix() -> String { "payment.log".to_string() }
+fn default_redact_fields() -> Vec<String> {
+    [
+        "user_address", "receiver_id", "phn_nmb", "ggl_id",
+        "token", "authorization", "cookie", "api_key", "password", "secret",
+        "private_key", "secret_key", "seed_phrase",
+    ].map(String::from).to_vec()
+}
+
+/// Set by `init_tracing`, read by `log_request` which has no access to the config.
+static LOG_CONFIG: OnceLock<LogConfig> = OnceLock::new();
+
+fn log_config() -> &'static LogConfig {
+    LOG_CONFIG.get_or_init(LogConfig::default)
+}
+
+// endregion: --- Log Config
+
+pub fn init_tracing(run_mode: &str, log: &LogConfig, telemetry: &TelemetryConfig) {
+    let _ = LOG_CONFIG.set(log.clone());
+    let redactor = Arc::new(Redactor::new(&log.redact_fields));
+
+    // NOTE: cli tools keep stdout for their own output
+    let writer = if std::env::var("LOG_STDERR").unwrap_or_else(|_| "false".to_string()) == "true" {
+        BoxMakeWriter::new(std::io::stderr)
+    } else {
+        BoxMakeWriter::new(std::io::stdout)
+    };
+    let mut fmt_layers = vec![fmt_layer(log.format, RedactingMakeWriter::new(writer, Arc::clone(&redactor)), true)];
+    if let Some(file) = &log.file {
+        let appender = RollingFileAppender::new(file.rotation.into(), &file.directory, &file.prefix);
+        fmt_layers.push(fmt_layer(log.format, RedactingMakeWriter::new(appender, Arc::clone(&redactor)), false));
+    }
 
     let log_level = match run_mode {
         "local" => Level::DEBUG,
@@ -38,16 +194,105 @@ pub fn init_tracing(run_mode: &str) {
         .with_target("near_jsonrpc_client", Level::INFO)
         .with_default(log_level);
 
+    // --- spans get a trace context even without export, so a caller's traceparent is continued
+    // into the response and outgoing calls, it is only sent to a collector when enabled
+    global::set_text_map_propagator(TraceContextPropagator::new());
+    let tracer = if telemetry.enabled { otlp_tracer(telemetry) } else { local_tracer() };
+    let otel_layer = tracing_opentelemetry::layer().with_tracer(tracer);
+
     tracing_subscriber::registry()
-        .with(tracing_layer)
+        .with(fmt_layers)
+        .with(otel_layer)
         .with(filter)
         .init();
+
+    if let Some(file) = &log.file {
+        tracing::info!("logs also written to {} ({:?})", file.directory.join(&file.prefix).display(), file.rotation);
+    }
+    if telemetry.enabled {
+        tracing::info!("traces exported to {} as {}", telemetry.otlp_endpoint, telemetry.service_name);
+    }
+}
+
+/// Exports the spans still buffered and flushes stdout, last thing before the process exits.
+/// File logs are written as they come and need no flush.
+pub async fn shutdown_tracing() {
+    // NOTE: the batch exporter's shutdown blocks on the runtime, keep it off the async workers
+    let _ = tokio::task::spawn_blocking(global::shutdown_tracer_provider).await;
+    let _ = std::io::Write::flush(&mut std::io::stdout());
+    let _ = std::io::Write::flush(&mut std::io::stderr());
+}
+
+fn fmt_layer<W>(format: LogFormat, writer: W, ansi: bool) -> Box<dyn Layer<Registry> + Send + Sync>
+where
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let layer = tracing_subscriber::fmt::layer().with_writer(writer).with_ansi(ansi);
+    match format {
+        LogFormat::Pretty => layer.boxed(),
+        LogFormat::Json => layer.json().with_current_span(true).with_span_list(true).boxed(),
+    }
+}
+
+fn otlp_tracer(telemetry: &TelemetryConfig) -> sdktrace::Tracer {
+    opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(opentelemetry_otlp::new_exporter().tonic().with_endpoint(telemetry.otlp_endpoint.clone()))
+        .with_trace_config(sdktrace::config()
+            .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(telemetry.sample_ratio))))
+            .with_resource(Resource::new(vec![KeyValue::new("service.name", telemetry.service_name.clone())])))
+        .install_batch(opentelemetry_sdk::runtime::Tokio)
+        .unwrap_or_else(|e| panic!("Failed to install the OTLP trace pipeline: {e}"))
+}
+
+/// Spans with no exporter: the caller's sampling decision is kept, new traces are not sampled.
+fn local_tracer() -> sdktrace::Tracer {
+    let provider = sdktrace::TracerProvider::builder()
+        .with_config(sdktrace::config().with_sampler(Sampler::ParentBased(Box::new(Sampler::AlwaysOff))))
+        .build();
+    let tracer = provider.tracer("payment");
+    global::set_tracer_provider(provider);
+    tracer
+}
+
+/// Writes the trace context of `span` into `headers`.
+pub fn inject_trace_context(span: &tracing::Span, headers: &mut HeaderMap) {
+    global::get_text_map_propagator(|propagator| propagator.inject_context(&span.context(), &mut HeaderInjector(headers)));
+}
+
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(HeaderName::as_str).collect()
+    }
+}
+
+struct HeaderInjector<'a>(&'a mut HeaderMap);
+
+impl Injector for HeaderInjector<'_> {
+    fn set(&mut self, key: &str, value: String) {
+        if let (Ok(name), Ok(value)) = (HeaderName::from_bytes(key.as_bytes()), HeaderValue::from_str(&value)) {
+            self.0.insert(name, value);
+        }
+    }
 }
 
 pub fn request_logging_middleware() -> TraceLayer<SharedClassifier<ServerErrorsAsFailures>, impl Fn(&Request<Body>) -> tracing::Span + Clone, impl Fn(&Request<Body>, &tracing::Span) + Clone, impl Fn(&Response, Duration, &tracing::Span) + Clone> {
     TraceLayer::new_for_http()
         .make_span_with(|request: &Request<Body>| {
-            tracing::info_span!("request", method = %request.method(), uri = %request.uri())
+            let request_id = request.extensions()
+                .get::<RequestId>()
+                .map(|RequestId(id)| id.to_string())
+                .unwrap_or_default();
+            let span = tracing::info_span!("request", method = %request.method(), uri = %request.uri(), request_id = %request_id);
+            // --- a child of the caller's trace when it sent a traceparent
+            span.set_parent(global::get_text_map_propagator(|propagator| propagator.extract(&HeaderExtractor(request.headers()))));
+            span
         })
         .on_request(|request: &Request<Body>, _: &tracing::Span| {
             tracing::info!("Starting request to [{}] {}", request.method(), request.uri());
@@ -65,10 +310,12 @@ struct RequestLogLine {
 
 	// -- User and context attributes.
 	user_id: Option<String>,
+	api_key_id: Option<String>,
 
 	// -- http request attributes.
 	req_path: String,
 	req_method: String,
+	status_code: u16,
 
 	// -- Errors attributes.
 	client_error_type: Option<String>,
@@ -80,6 +327,7 @@ pub async fn log_request(
 	uuid: Uuid,
 	req_method: Method,
 	uri: Uri,
+	status_code: StatusCode,
 	ctx: Option<Ctx>,
 	service_error: Option<&Error>,
 	client_error_message: Option<&String>,
@@ -101,8 +349,10 @@ pub async fn log_request(
 
 		req_path: uri.to_string(),
 		req_method: req_method.to_string(),
+		status_code: status_code.as_u16(),
 
-		user_id: ctx.map(|c| c.user_info().user_id().to_string()),
+		user_id: ctx.as_ref().map(|c| c.user_info().user_id().to_string()),
+		api_key_id: ctx.as_ref().and_then(|c| c.api_key_id()).map(|id| id.to_string()),
 		client_error_type: client_error_message.map(|s| s.to_string()),
 
 		error_type,
@@ -111,11 +361,12 @@ pub async fn log_request(
 
 	tracing::info!("{}", json!(log_line));
 
-	if let Some(body) = &res_body_str {
-		if body.len() > 0 {
+	// --- bodies carry addresses and user data, `RedactingMakeWriter` masks the known fields
+	if let Some(body) = res_body_str.filter(|body| !body.is_empty()) {
+		if log_config().should_log_body(status_code) {
 			tracing::info!("Response Body: {}", body);
 		}
-    }
+	}
 
 	Ok(())
 }
@@ -0,0 +1,173 @@
+use std::sync::OnceLock;
+use std::time::Duration;
+use prometheus::{Encoder, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Opts, Registry, TextEncoder};
+use serde::Deserialize;
+use ::config::{Config, ConfigError, File as ConfigFile};
+
+/// Prometheus `/metrics` listener, the `[metrics]` table of `config.toml`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MetricsConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// kept off the api listener, bind it to an address only the scraper reaches
+    #[serde(default = "default_host")]
+    pub host: String,
+    #[serde(default = "default_port")]
+    pub port: u16,
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: default_host(),
+            port: default_port(),
+        }
+    }
+}
+
+impl MetricsConfig {
+    /// Reads the `[metrics]` table of `config.toml`.
+    pub fn init() -> Self {
+        let result = Config::builder()
+            .add_source(ConfigFile::with_name("config.toml"))
+            .build()
+            .unwrap()
+            .get::<MetricsConfig>("metrics");
+
+        match result {
+            Ok(metrics_config) => metrics_config,
+            Err(ConfigError::NotFound(_)) => MetricsConfig::default(),
+            Err(e) => panic!("Failed to parse [metrics] config: {e}"),
+        }
+    }
+}
+
+fn default_host() -> String { "127.0.0.1".to_string() }
+fn default_port() -> u16 { 9090 }
+
+/// Every metric the service exports. Events are counted where they happen,
+/// state kept elsewhere (retry stats, signer keys, rpc endpoints, db pool) is copied in on each scrape.
+pub struct Metrics {
+    registry: Registry,
+    pub http_requests: IntCounterVec,
+    pub http_request_duration: HistogramVec,
+    pub claims_created: IntCounterVec,
+    pub claims_finalized: IntCounterVec,
+    pub rpc_call_duration: HistogramVec,
+    pub rpc_call_errors: IntCounterVec,
+    pub rpc_retries: IntCounterVec,
+    pub rpc_retries_exhausted: IntCounterVec,
+    pub rpc_endpoint_healthy: IntGaugeVec,
+    pub rpc_endpoint_block_lag: IntGaugeVec,
+    pub rpc_circuit_breaker_state: IntGaugeVec,
+    pub rpc_circuit_breaker_opened: IntCounter,
+    pub rpc_circuit_breaker_rejected: IntCounter,
+    pub signer_key_transactions: IntCounterVec,
+    pub signer_key_in_flight: IntGaugeVec,
+    pub db_pool_max_size: IntGauge,
+    pub db_pool_size: IntGauge,
+    pub db_pool_available: IntGauge,
+    pub db_pool_waiting: IntGauge,
+}
+
+static METRICS: OnceLock<Metrics> = OnceLock::new();
+
+pub fn metrics() -> &'static Metrics {
+    METRICS.get_or_init(Metrics::new)
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new();
+        let counter_vec = |name: &str, help: &str, labels: &[&str]| {
+            let counter = IntCounterVec::new(Opts::new(name, help), labels).expect("valid metric");
+            registry.register(Box::new(counter.clone())).expect("metric registered once");
+            counter
+        };
+        let gauge_vec = |name: &str, help: &str, labels: &[&str]| {
+            let gauge = IntGaugeVec::new(Opts::new(name, help), labels).expect("valid metric");
+            registry.register(Box::new(gauge.clone())).expect("metric registered once");
+            gauge
+        };
+        let histogram_vec = |name: &str, help: &str, labels: &[&str]| {
+            let histogram = HistogramVec::new(HistogramOpts::new(name, help), labels).expect("valid metric");
+            registry.register(Box::new(histogram.clone())).expect("metric registered once");
+            histogram
+        };
+        let counter = |name: &str, help: &str| {
+            let counter = IntCounter::new(name, help).expect("valid metric");
+            registry.register(Box::new(counter.clone())).expect("metric registered once");
+            counter
+        };
+        let gauge = |name: &str, help: &str| {
+            let gauge = IntGauge::new(name, help).expect("valid metric");
+            registry.register(Box::new(gauge.clone())).expect("metric registered once");
+            gauge
+        };
+
+        Self {
+            http_requests: counter_vec("http_requests_total", "HTTP requests by route and status", &["method", "route", "status"]),
+            http_request_duration: histogram_vec("http_request_duration_seconds", "HTTP request latency by route and status", &["method", "route", "status"]),
+            claims_created: counter_vec("reward_claims_created_total", "Reward claims created, by coin network", &["coin_network"]),
+            claims_finalized: counter_vec("reward_claims_finalized_total", "Reward claims approved or failed, by coin network", &["coin_network", "status"]),
+            rpc_call_duration: histogram_vec("near_rpc_call_duration_seconds", "Near rpc call latency including retries and failover", &["method"]),
+            rpc_call_errors: counter_vec("near_rpc_call_errors_total", "Near rpc calls that failed after their retries, by error class", &["method", "class"]),
+            rpc_retries: counter_vec("near_rpc_retries_total", "Near rpc retries started, by error class", &["class"]),
+            rpc_retries_exhausted: counter_vec("near_rpc_retries_exhausted_total", "Near rpc calls out of attempts or time, by error class", &["class"]),
+            rpc_endpoint_healthy: gauge_vec("near_rpc_endpoint_healthy", "1 when the rpc endpoint is reachable and not lagging", &["url"]),
+            rpc_endpoint_block_lag: gauge_vec("near_rpc_endpoint_block_lag", "Blocks behind the highest rpc endpoint at the last probe", &["url"]),
+            rpc_circuit_breaker_state: gauge_vec("near_rpc_circuit_breaker_state", "1 for the current state of the rpc circuit breaker", &["state"]),
+            rpc_circuit_breaker_opened: counter("near_rpc_circuit_breaker_opened_total", "Times the rpc circuit breaker opened"),
+            rpc_circuit_breaker_rejected: counter("near_rpc_circuit_breaker_rejected_total", "Rpc calls failed fast by the circuit breaker"),
+            signer_key_transactions: counter_vec("near_signer_key_transactions_total", "Transactions signed per relayer access key", &["public_key"]),
+            signer_key_in_flight: gauge_vec("near_signer_key_in_flight", "Transactions in flight per relayer access key", &["public_key"]),
+            db_pool_max_size: gauge("db_pool_max_size", "Postgres pool max size"),
+            db_pool_size: gauge("db_pool_size", "Postgres connections open"),
+            db_pool_available: gauge("db_pool_available", "Postgres connections idle in the pool"),
+            db_pool_waiting: gauge("db_pool_waiting", "Tasks waiting for a postgres connection"),
+            registry,
+        }
+    }
+
+    pub fn observe_http(&self, method: &str, route: &str, status: u16, latency: Duration) {
+        let status = status.to_string();
+        let labels = [method, route, status.as_str()];
+        self.http_requests.with_label_values(&labels).inc();
+        self.http_request_duration.with_label_values(&labels).observe(latency.as_secs_f64());
+    }
+
+    /// The prometheus text format of every metric.
+    pub fn encode(&self) -> String {
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .expect("text encoding into a vec");
+        String::from_utf8(buffer).expect("text format is utf-8")
+    }
+}
+
+/// Moves a counter up to a total counted elsewhere.
+pub fn sync_counter(counter: &IntCounter, total: u64) {
+    let current = counter.get();
+    if total > current {
+        counter.inc_by(total - current);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        let metrics = Metrics::new();
+        metrics.observe_http("GET", "/api/coins", 200, Duration::from_millis(12));
+        sync_counter(&metrics.rpc_retries.with_label_values(&["timeout"]), 3);
+        sync_counter(&metrics.rpc_retries.with_label_values(&["timeout"]), 2);
+
+        let text = metrics.encode();
+        assert!(text.contains(r#"http_requests_total{method="GET",route="/api/coins",status="200"} 1"#));
+        assert!(text.contains(r#"near_rpc_retries_total{class="timeout"} 3"#));
+    }
+}
@@ -1,12 +1,34 @@
 pub mod log; 
 pub mod near;
 pub mod swagger;
+pub mod outbox;
+pub mod auth;
+pub mod rate_limit;
+pub mod price_feed;
+pub mod retry;
+pub mod circuit_breaker;
+pub mod metrics;
+pub mod health;
+pub mod telemetry;
+pub mod redact;
+pub mod shutdown;
 
 use std::env;
 use dotenvy::dotenv;
 use tokio::sync::OnceCell;
 use crate::adapter::output::persistence::db::_dev_utils;
 use self::near::{KeyRotatingSignerWrapper, NearNetworkConfig};
+use self::outbox::OutboxConfig;
+use self::auth::AuthConfig;
+use self::rate_limit::RateLimitConfig;
+use self::price_feed::PriceFeedConfig;
+use self::retry::RetryPolicy;
+use self::circuit_breaker::CircuitBreakerConfig;
+use self::metrics::MetricsConfig;
+use self::health::HealthConfig;
+use self::telemetry::TelemetryConfig;
+use self::log::LogConfig;
+use self::shutdown::ShutdownConfig;
 
 #[derive(Debug, Clone)]
 struct ServerConfig {
@@ -19,6 +41,7 @@ struct ServerConfig {
 struct DatabaseConfig {
     url: String,
     connection_size: usize,
+    migrate_on_start: bool,
 }
 
 
@@ -29,6 +52,17 @@ pub struct Config {
     db: DatabaseConfig,
     signer: KeyRotatingSignerWrapper,
     near_network_config: NearNetworkConfig,
+    outbox: OutboxConfig,
+    auth: AuthConfig,
+    rate_limit: RateLimitConfig,
+    price_feed: PriceFeedConfig,
+    retry: RetryPolicy,
+    circuit_breaker: CircuitBreakerConfig,
+    metrics: MetricsConfig,
+    health: HealthConfig,
+    telemetry: TelemetryConfig,
+    log: LogConfig,
+    shutdown: ShutdownConfig,
 }
 
 
@@ -41,6 +75,11 @@ impl Config {
         self.db.connection_size
     }
 
+    /// Run pending migrations before serving. Local runs always migrate the recreated db.
+    pub fn db_migrate_on_start(&self) -> bool {
+        self.db.migrate_on_start
+    }
+
     pub fn server_host(&self) -> &str {
         &self.server.host
     }
@@ -61,6 +100,50 @@ impl Config {
         self.near_network_config.clone()
     }
 
+    pub fn outbox(&self) -> &OutboxConfig {
+        &self.outbox
+    }
+
+    pub fn auth(&self) -> &AuthConfig {
+        &self.auth
+    }
+
+    pub fn rate_limit(&self) -> &RateLimitConfig {
+        &self.rate_limit
+    }
+
+    pub fn price_feed(&self) -> &PriceFeedConfig {
+        &self.price_feed
+    }
+
+    pub fn retry(&self) -> &RetryPolicy {
+        &self.retry
+    }
+
+    pub fn circuit_breaker(&self) -> &CircuitBreakerConfig {
+        &self.circuit_breaker
+    }
+
+    pub fn metrics(&self) -> &MetricsConfig {
+        &self.metrics
+    }
+
+    pub fn health(&self) -> &HealthConfig {
+        &self.health
+    }
+
+    pub fn telemetry(&self) -> &TelemetryConfig {
+        &self.telemetry
+    }
+
+    pub fn log(&self) -> &LogConfig {
+        &self.log
+    }
+
+    pub fn shutdown(&self) -> &ShutdownConfig {
+        &self.shutdown
+    }
+
     pub fn is_local(&self) -> bool {
         self.run_mode == "local"
     }
@@ -79,7 +162,12 @@ async fn init_config() -> Config {
     let env_file = format!(".env.{}", run_mode);
     dotenvy::from_filename(&env_file).ok();
 
-    log::init_tracing(&run_mode);
+    // --- first, a passphrase descriptor must be read before log files take free descriptor numbers
+    let near_network_config = NearNetworkConfig::init();
+
+    let log_config = LogConfig::init();
+    let telemetry = TelemetryConfig::init();
+    log::init_tracing(&run_mode, &log_config, &telemetry);
     
 	tracing::info!("RUN MODE: {}", run_mode);
 
@@ -106,12 +194,22 @@ async fn init_config() -> Config {
     let database_config = DatabaseConfig {
         url: format!("{}/{}", db_url, databse_name),
         connection_size: pg_connection_size,
+        migrate_on_start: env::var("MIGRATE_ON_START").unwrap_or_else(|_| "false".to_string()) == "true",
     };
 
-    let near_network_config = NearNetworkConfig::init();
     let signer = near_network_config.init_rotating_signer();
-
-    if run_mode == "local" {
+    let outbox = OutboxConfig::init();
+    let auth = AuthConfig::init(&run_mode);
+    let rate_limit = RateLimitConfig::init();
+    let price_feed = PriceFeedConfig::init();
+    let retry = RetryPolicy::init();
+    let circuit_breaker = CircuitBreakerConfig::init();
+    let metrics = MetricsConfig::init();
+    let health = HealthConfig::init();
+    let shutdown = ShutdownConfig::init();
+
+    let local_db_recreate = env::var("LOCAL_DB_RECREATE").unwrap_or_else(|_| "true".to_string()) == "true";
+    if run_mode == "local" && local_db_recreate {
         // NOTE: Hardcode to prevent deployed system db update.
         let pg_host = env::var("POSTGRES_HOST").unwrap_or_else(|_| "localhost".to_string());
         let pg_port = env::var("POSTGRES_PORT").unwrap_or_else(|_| "5432".to_string());
@@ -125,6 +223,17 @@ async fn init_config() -> Config {
         db: database_config,
         signer,
         near_network_config,
+        outbox,
+        auth,
+        rate_limit,
+        price_feed,
+        retry,
+        circuit_breaker,
+        metrics,
+        health,
+        telemetry,
+        log: log_config,
+        shutdown,
     }
 }
 
@@ -1,7 +1,11 @@
 use near_fetch::signer::KeyRotatingSigner;
 use near_crypto::InMemorySigner;
+use zeroize::Zeroizing;
+use crate::adapter::output::keystore::{self, EncryptedKeystore, KdfParams, KeystorePassphrase};
+use crate::adapter::output::near::endpoint::{RpcEndpoint, RpcEndpointPool};
+use crate::domain::model::near::{SignerKey, SignerKeyChanges};
 use serde::Deserialize;
-use std::{fmt::Debug, path::PathBuf, sync::Arc};
+use std::{fmt::Debug, path::{Path, PathBuf}, sync::{Arc, RwLock}, time::Duration};
 use ::config::{Config, File as ConfigFile};
 
 // region: --- ApiKey
@@ -50,55 +54,207 @@ impl<'de> serde::de::Deserialize<'de> for ApiKey {
 
 // endregion: --- ApiKey
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct RpcEndpointConfig {
+    pub url: url::Url,
+    #[serde(default)]
+    pub api_key: Option<ApiKey>,
+    /// name of the env variable holding the api key, keeps it out of `config.toml`
+    #[serde(default)]
+    pub api_key_env: Option<String>,
+    /// lower is tried first
+    #[serde(default)]
+    pub priority: u32,
+}
+
+impl RpcEndpointConfig {
+    fn api_key(&self) -> Option<ApiKey> {
+        self.api_key_env
+            .as_ref()
+            .and_then(|name| std::env::var(name).ok())
+            .filter(|api_key| !api_key.is_empty())
+            .map(|api_key| api_key.parse().unwrap_or_else(|e| panic!("Invalid rpc api key in {:?}: {e}", self.api_key_env)))
+            .or_else(|| self.api_key.clone())
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct NearNetworkConfig {
-    rpc_url: url::Url,
+    /// the only endpoint when `rpc_endpoints` is empty
+    #[serde(default)]
+    rpc_url: Option<url::Url>,
+    #[serde(default)]
     rpc_api_key: Option<ApiKey>,
+    #[serde(default)]
+    rpc_endpoints: Vec<RpcEndpointConfig>,
+    /// status probe of every endpoint, every N seconds. 0 disables the probes, failed calls still move an endpoint back
+    #[serde(default = "default_rpc_probe_interval_secs")]
+    pub rpc_probe_interval_secs: u64,
+    /// endpoints further behind the highest one are skipped while another is healthy
+    #[serde(default = "default_rpc_max_block_lag")]
+    pub rpc_max_block_lag: u64,
+    /// per request, a hung endpoint fails over after this long
+    #[serde(default = "default_rpc_timeout_ms")]
+    pub rpc_timeout_ms: u64,
     pub whitelisted_contracts: Vec<String>,
     pub whitelisted_senders: Vec<String>,
     keys_filename: PathBuf,
+    /// reload the keys file when it changes, checked every N seconds. 0 disables the watcher
+    #[serde(default)]
+    pub keys_reload_poll_secs: u64,
+    /// removing keys waits this long for the transactions still signed with them before the DeleteKey
+    #[serde(default = "default_keys_drain_timeout_secs")]
+    pub keys_drain_timeout_secs: u64,
+    /// shared by the clones, so a reload finds the passphrase read at startup
+    #[serde(skip)]
+    passphrase: Arc<KeystorePassphrase>,
 }
 
 impl NearNetworkConfig {
+    /// A passphrase descriptor is read here, before anything else in the process opens a file under its number.
     pub fn init() -> Self {
-        Config::builder()
+        let near_network_config = Config::builder()
             .add_source(ConfigFile::with_name("config.toml"))
             .build()
             .unwrap()
             .try_deserialize::<NearNetworkConfig>()
-            .unwrap()
+            .unwrap();
+
+        if std::env::var(keystore::PASSPHRASE_FD_ENV).is_ok() {
+            near_network_config.passphrase.get()
+                .unwrap_or_else(|e| panic!("Failed to read the keystore passphrase: {e}"));
+        }
+        near_network_config
+    }
+
+    /// `rpc_endpoints`, or `rpc_url` with `rpc_api_key` when there are none.
+    pub fn rpc_endpoints(&self) -> Vec<RpcEndpointConfig> {
+        if !self.rpc_endpoints.is_empty() {
+            return self.rpc_endpoints.clone();
+        }
+        let url = self.rpc_url.clone().expect("rpc_url or rpc_endpoints must be set");
+        vec![RpcEndpointConfig { url, api_key: self.rpc_api_key.clone(), api_key_env: None, priority: 0 }]
+    }
+
+    pub fn rpc_endpoint_pool(&self) -> RpcEndpointPool {
+        let http_client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(self.rpc_timeout_ms))
+            .build()
+            .expect("Failed to build rpc http client");
+        let endpoints = self.rpc_endpoints()
+            .into_iter()
+            .map(|endpoint| {
+                let mut json_rpc_client = near_jsonrpc_client::JsonRpcClient::with(http_client.clone()).connect(endpoint.url.as_str());
+                if let Some(rpc_api_key) = endpoint.api_key() {
+                    json_rpc_client = json_rpc_client.header(rpc_api_key.0);
+                };
+                RpcEndpoint::new(endpoint.url.to_string(), endpoint.priority, json_rpc_client)
+            })
+            .collect();
+        RpcEndpointPool::new(endpoints, self.rpc_max_block_lag)
     }
 
-    pub fn rpc_client(&self) -> near_fetch::Client {
-        near_fetch::Client::from_client(self.raw_rpc_client())
+    pub fn keys_filename(&self) -> &Path {
+        &self.keys_filename
     }
 
-    // TODO: more specific setting ref. near_core github
-    pub fn raw_rpc_client(&self) -> near_jsonrpc_client::JsonRpcClient {
-        let mut json_rpc_client =
-            near_jsonrpc_client::JsonRpcClient::connect(self.rpc_url.as_ref());
-        if let Some(rpc_api_key) = &self.rpc_api_key {
-            json_rpc_client = json_rpc_client.header(rpc_api_key.0.clone());
+    /// Encrypted keys files are unlocked with `KEYSTORE_PASSPHRASE` or `KEYSTORE_PASSPHRASE_FD`.
+    /// The file contents are zeroized once parsed. The parsed `SecretKey`s are not: near_crypto does not
+    /// zeroize them and the signer keeps them for the life of the process.
+    pub fn try_load_signers(&self) -> keystore::error::Result<Vec<InMemorySigner>> {
+        let contents = Zeroizing::new(std::fs::read(&self.keys_filename)
+            .map_err(|e| keystore::error::Error::ReadFailed { message: format!("{}: {e}", self.keys_filename.display()) })?);
+        let parse_signers = |plaintext: &[u8]| {
+            serde_json::from_slice::<Vec<InMemorySigner>>(plaintext)
+                .map_err(|e| keystore::error::Error::InvalidFormat { message: e.to_string() })
         };
-        json_rpc_client
+
+        match EncryptedKeystore::parse(&contents)? {
+            Some(keystore) => parse_signers(&keystore.decrypt(self.passphrase.get()?)?),
+            None => {
+                tracing::warn!("keys file {} is not encrypted, run `payment-admin keys encrypt`", self.keys_filename.display());
+                parse_signers(&contents)
+            }
+        }
     }
 
-    pub fn init_rotating_signer(&self) -> KeyRotatingSignerWrapper {
-        let keys_file = std::fs::File::open(&self.keys_filename)
-            .expect("Failed to open keys file");
-        let signers: Vec<InMemorySigner> = serde_json::from_reader(keys_file)
-            .expect("Failed to parse keys file");
+    /// Writes `signers` next to the keys file, encrypted with a fresh salt when the keys file is encrypted.
+    /// `commit_staged_signers` moves it over the keys file.
+    pub fn stage_signers(&self, signers: &[InMemorySigner]) -> keystore::error::Result<()> {
+        let current = Zeroizing::new(std::fs::read(&self.keys_filename)
+            .map_err(|e| keystore::error::Error::ReadFailed { message: format!("{}: {e}", self.keys_filename.display()) })?);
+        let plaintext = Zeroizing::new(serde_json::to_vec_pretty(signers)
+            .map_err(|e| keystore::error::Error::InvalidFormat { message: e.to_string() })?);
+
+        let contents = match EncryptedKeystore::parse(&current)? {
+            Some(current) => {
+                let kdf = KdfParams::with_costs(current.kdf.m_cost, current.kdf.t_cost, current.kdf.p_cost);
+                let encrypted = EncryptedKeystore::encrypt(&plaintext, self.passphrase.get()?, kdf)?;
+                Zeroizing::new(serde_json::to_vec_pretty(&encrypted)
+                    .map_err(|e| keystore::error::Error::InvalidFormat { message: e.to_string() })?)
+            }
+            None => plaintext,
+        };
+        keystore::write_private_file(&self.staged_keys_filename(), &contents)
+    }
+
+    pub fn commit_staged_signers(&self) -> keystore::error::Result<()> {
+        std::fs::rename(self.staged_keys_filename(), &self.keys_filename)
+            .map_err(|e| keystore::error::Error::WriteFailed { message: format!("{}: {e}", self.keys_filename.display()) })
+    }
+
+    pub fn discard_staged_signers(&self) {
+        let _ = std::fs::remove_file(self.staged_keys_filename());
+    }
 
-        KeyRotatingSignerWrapper::from_signers(signers)
+    fn staged_keys_filename(&self) -> PathBuf {
+        self.keys_filename.with_extension("staged")
+    }
+
+    pub fn load_signers(&self) -> Vec<InMemorySigner> {
+        self.try_load_signers()
+            .unwrap_or_else(|e| panic!("Failed to load keys file: {e}"))
+    }
+
+    pub fn init_rotating_signer(&self) -> KeyRotatingSignerWrapper {
+        KeyRotatingSignerWrapper::from_signers(self.load_signers())
     }
 }
 
 
+fn default_rpc_probe_interval_secs() -> u64 { 15 }
+fn default_rpc_max_block_lag() -> u64 { 30 }
+fn default_rpc_timeout_ms() -> u64 { 30_000 }
+fn default_keys_drain_timeout_secs() -> u64 { 60 }
+
 // region: --- KeyRotatingSignerWrapper
 
+struct SignerKeySet {
+    signer: Arc<KeyRotatingSigner>,
+    /// the same keys one by one, for callers that pick the key themselves
+    signers: Vec<Arc<InMemorySigner>>,
+    /// kept apart so keys can be listed without touching the secrets
+    keys: Vec<SignerKey>,
+}
+
+impl SignerKeySet {
+    fn new(signers: Vec<InMemorySigner>) -> Self {
+        let keys = signers
+            .iter()
+            .map(|signer| SignerKey { account_id: signer.account_id.to_string(), public_key: signer.public_key.to_string() })
+            .collect();
+        Self {
+            signers: signers.iter().cloned().map(Arc::new).collect(),
+            signer: Arc::new(KeyRotatingSigner::from_signers(signers)),
+            keys,
+        }
+    }
+}
+
+/// Clones share the key set, `replace` swaps it for every holder at once.
 #[derive(Clone)]
 pub struct KeyRotatingSignerWrapper{
-    signer: Arc<KeyRotatingSigner>,
+    current: Arc<RwLock<SignerKeySet>>,
 }
 
 impl Debug for KeyRotatingSignerWrapper {
@@ -110,12 +266,59 @@ impl Debug for KeyRotatingSignerWrapper {
 impl KeyRotatingSignerWrapper {
     pub fn from_signers(signers: Vec<InMemorySigner>) -> Self {
         KeyRotatingSignerWrapper {
-            signer: Arc::new(KeyRotatingSigner::from_signers(signers)),
+            current: Arc::new(RwLock::new(SignerKeySet::new(signers))),
         }
     }
 
+    /// Snapshot of the current key set. A transaction holding it keeps signing with it after a `replace`.
     pub fn inner(&self) -> Arc<KeyRotatingSigner> {
-        Arc::clone(&self.signer)
+        Arc::clone(&self.current.read().expect("signer lock poisoned").signer)
+    }
+
+    pub fn signers(&self) -> Vec<Arc<InMemorySigner>> {
+        self.current.read().expect("signer lock poisoned").signers.clone()
+    }
+
+    pub fn public_keys(&self) -> Vec<SignerKey> {
+        self.current.read().expect("signer lock poisoned").keys.clone()
+    }
+
+    /// Swaps in `signers` when the key set changed.
+    pub fn replace(&self, signers: Vec<InMemorySigner>) -> SignerKeyChanges {
+        let next = SignerKeySet::new(signers);
+        let mut current = self.current.write().expect("signer lock poisoned");
+        let changes = SignerKeyChanges::between(&current.keys, &next.keys);
+        if !changes.is_empty() {
+            *current = next;
+        }
+        changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use near_crypto::KeyType;
+    use super::*;
+
+    fn signer(seed: &str) -> InMemorySigner {
+        InMemorySigner::from_seed("payout.testnet".parse().unwrap(), KeyType::ED25519, seed)
+    }
+
+    #[test]
+    fn test_replace_signer_keys() {
+        let wrapper = KeyRotatingSignerWrapper::from_signers(vec![signer("a"), signer("b")]);
+        let in_flight = wrapper.inner();
+
+        let changes = wrapper.replace(vec![signer("b"), signer("c")]);
+
+        assert_eq!(changes.added, vec![SignerKey { account_id: "payout.testnet".to_string(), public_key: signer("c").public_key.to_string() }]);
+        assert_eq!(changes.removed, vec![SignerKey { account_id: "payout.testnet".to_string(), public_key: signer("a").public_key.to_string() }]);
+        assert!(!Arc::ptr_eq(&in_flight, &wrapper.inner()));
+        assert_eq!(wrapper.public_keys().len(), 2);
+
+        let current = wrapper.inner();
+        assert!(wrapper.replace(vec![signer("c"), signer("b")]).is_empty());
+        assert!(Arc::ptr_eq(&current, &wrapper.inner()));
     }
 }
 // endregion: --- KeyRotatingSignerWrapper
\ No newline at end of file
@@ -0,0 +1,89 @@
+use serde::Deserialize;
+use ::config::{Config, ConfigError, File as ConfigFile};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OutboxConsumerConfig {
+    pub name: String,
+    pub url: String,
+    /// HMAC-SHA256 signing secret. Prefer `secret_env` so the secret stays out of `config.toml`.
+    #[serde(default)]
+    pub secret: Option<String>,
+    /// name of the env variable holding the signing secret
+    #[serde(default)]
+    pub secret_env: Option<String>,
+    /// if empty, every event type is delivered
+    #[serde(default)]
+    pub event_types: Vec<String>,
+    #[serde(default = "default_consumer_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+impl OutboxConsumerConfig {
+    pub fn secret(&self) -> Option<String> {
+        self.secret_env
+            .as_ref()
+            .and_then(|name| std::env::var(name).ok())
+            .or_else(|| self.secret.clone())
+            .filter(|secret| !secret.is_empty())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OutboxConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+    #[serde(default = "default_batch_size")]
+    pub batch_size: i64,
+    #[serde(default = "default_lease_secs")]
+    pub lease_secs: i64,
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: i32,
+    #[serde(default = "default_initial_backoff_ms")]
+    pub initial_backoff_ms: u64,
+    #[serde(default = "default_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+    #[serde(default)]
+    pub consumers: Vec<OutboxConsumerConfig>,
+}
+
+impl Default for OutboxConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            poll_interval_ms: default_poll_interval_ms(),
+            batch_size: default_batch_size(),
+            lease_secs: default_lease_secs(),
+            max_attempts: default_max_attempts(),
+            initial_backoff_ms: default_initial_backoff_ms(),
+            max_backoff_ms: default_max_backoff_ms(),
+            consumers: vec![],
+        }
+    }
+}
+
+impl OutboxConfig {
+    /// Reads the `[outbox]` table of `config.toml`. A missing table disables the dispatcher.
+    pub fn init() -> Self {
+        let result = Config::builder()
+            .add_source(ConfigFile::with_name("config.toml"))
+            .build()
+            .unwrap()
+            .get::<OutboxConfig>("outbox");
+
+        match result {
+            Ok(outbox_config) => outbox_config,
+            Err(ConfigError::NotFound(_)) => OutboxConfig::default(),
+            Err(e) => panic!("Failed to parse [outbox] config: {e}"),
+        }
+    }
+}
+
+fn default_consumer_timeout_ms() -> u64 { 5_000 }
+fn default_poll_interval_ms() -> u64 { 1_000 }
+fn default_batch_size() -> i64 { 50 }
+fn default_lease_secs() -> i64 { 60 }
+fn default_max_attempts() -> i32 { 10 }
+fn default_initial_backoff_ms() -> u64 { 1_000 }
+fn default_max_backoff_ms() -> u64 { 10 * 60 * 1_000 }
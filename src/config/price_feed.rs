@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+use serde::Deserialize;
+use ::config::{Config, ConfigError, File as ConfigFile};
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PriceFeedSource {
+    /// claims are paid without a fiat snapshot
+    None,
+    /// fixed rates from `rates` and `rates_file`, for tests and local runs
+    Static,
+    /// rates fetched from a JSON http endpoint
+    Http,
+}
+
+/// Price of one whole token, as decimal strings (`usd = "5.2"`) so no rate goes through a float.
+#[derive(Debug, Clone, Deserialize)]
+pub struct StaticRateConfig {
+    pub usd: String,
+    pub krw: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct HttpPriceFeedConfig {
+    /// stored as `price_source` on every snapshot
+    #[serde(default = "default_http_name")]
+    pub name: String,
+    /// `{symbol}` is replaced by the provider id of the coin
+    pub url: String,
+    /// JSON pointers (RFC 6901) to the rates in the response body, `{symbol}` is replaced as in `url`
+    pub usd_pointer: String,
+    pub krw_pointer: String,
+    /// coin symbol -> provider id, e.g. NEAR = "near". unmapped symbols are sent lowercased
+    #[serde(default)]
+    pub symbol_ids: HashMap<String, String>,
+    #[serde(default)]
+    pub api_key_env: Option<String>,
+    #[serde(default = "default_api_key_header")]
+    pub api_key_header: String,
+    #[serde(default = "default_http_timeout_ms")]
+    pub timeout_ms: u64,
+    #[serde(default = "default_http_cache_secs")]
+    pub cache_secs: u64,
+}
+
+impl HttpPriceFeedConfig {
+    pub fn api_key(&self) -> Option<String> {
+        self.api_key_env.as_ref().and_then(|name| std::env::var(name).ok())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PriceFeedConfig {
+    #[serde(default = "default_source")]
+    pub source: PriceFeedSource,
+    /// coin symbol -> rates, used by the static source
+    #[serde(default)]
+    pub rates: HashMap<String, StaticRateConfig>,
+    /// toml or json file with the same layout as `rates`, entries in `rates` win
+    #[serde(default)]
+    pub rates_file: Option<String>,
+    #[serde(default)]
+    pub http: Option<HttpPriceFeedConfig>,
+}
+
+impl Default for PriceFeedConfig {
+    fn default() -> Self {
+        Self {
+            source: default_source(),
+            rates: HashMap::new(),
+            rates_file: None,
+            http: None,
+        }
+    }
+}
+
+impl PriceFeedConfig {
+    /// Reads the `[price_feed]` table of `config.toml`.
+    pub fn init() -> Self {
+        let result = Config::builder()
+            .add_source(ConfigFile::with_name("config.toml"))
+            .build()
+            .unwrap()
+            .get::<PriceFeedConfig>("price_feed");
+
+        match result {
+            Ok(price_feed_config) => price_feed_config,
+            Err(ConfigError::NotFound(_)) => PriceFeedConfig::default(),
+            Err(e) => panic!("Failed to parse [price_feed] config: {e}"),
+        }
+    }
+}
+
+fn default_source() -> PriceFeedSource { PriceFeedSource::None }
+fn default_http_name() -> String { "http".to_string() }
+fn default_api_key_header() -> String { "x-api-key".to_string() }
+fn default_http_timeout_ms() -> u64 { 3_000 }
+fn default_http_cache_secs() -> u64 { 60 }
@@ -0,0 +1,68 @@
+use serde::Deserialize;
+use ::config::{Config, ConfigError, File as ConfigFile};
+use crate::domain::model::rate_limit::TokenBucketConfig;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RateLimitBackend {
+    /// buckets live in the process, every instance limits on its own
+    Memory,
+    /// buckets live in the `rate_limit_bucket` table and are shared by all instances
+    Postgres,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RateLimitConfig {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_backend")]
+    pub backend: RateLimitBackend,
+    /// use the first `x-forwarded-for` address as client ip. only enable behind a trusted proxy
+    #[serde(default)]
+    pub trust_forwarded_for: bool,
+    /// how often buckets idle long enough to be full again are dropped
+    #[serde(default = "default_cleanup_interval_secs")]
+    pub cleanup_interval_secs: u64,
+    /// payout endpoints (`POST /api/reward-claims`)
+    #[serde(default = "default_payout_bucket")]
+    pub payout: TokenBucketConfig,
+    /// every other `/api` endpoint
+    #[serde(default = "default_api_bucket")]
+    pub api: TokenBucketConfig,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            backend: default_backend(),
+            trust_forwarded_for: false,
+            cleanup_interval_secs: default_cleanup_interval_secs(),
+            payout: default_payout_bucket(),
+            api: default_api_bucket(),
+        }
+    }
+}
+
+impl RateLimitConfig {
+    /// Reads the `[rate_limit]` table of `config.toml`.
+    pub fn init() -> Self {
+        let result = Config::builder()
+            .add_source(ConfigFile::with_name("config.toml"))
+            .build()
+            .unwrap()
+            .get::<RateLimitConfig>("rate_limit");
+
+        match result {
+            Ok(rate_limit_config) => rate_limit_config,
+            Err(ConfigError::NotFound(_)) => RateLimitConfig::default(),
+            Err(e) => panic!("Failed to parse [rate_limit] config: {e}"),
+        }
+    }
+}
+
+fn default_enabled() -> bool { true }
+fn default_backend() -> RateLimitBackend { RateLimitBackend::Memory }
+fn default_cleanup_interval_secs() -> u64 { 300 }
+fn default_payout_bucket() -> TokenBucketConfig { TokenBucketConfig { capacity: 5.0, refill_per_sec: 0.1 } }
+fn default_api_bucket() -> TokenBucketConfig { TokenBucketConfig { capacity: 120.0, refill_per_sec: 2.0 } }
@@ -0,0 +1,161 @@
+use std::borrow::Cow;
+use std::io;
+use std::sync::Arc;
+use tracing_subscriber::fmt::MakeWriter;
+
+const REDACTED: &str = "[REDACTED]";
+
+/// Masks the values of sensitive fields in formatted log lines, whatever wrote them:
+/// `key=value` fields, `key: value` debug output and `"key":"value"` JSON, escaped or not.
+/// A field is sensitive when its name, or its last `_`/`-` separated part, is in the list,
+/// so `token` also covers `access_token` and `user_address` covers `sended_user_address`.
+#[derive(Debug, Clone)]
+pub struct Redactor {
+    fields: Vec<String>,
+}
+
+impl Redactor {
+    pub fn new(fields: &[String]) -> Self {
+        Self { fields: fields.iter().map(|field| field.to_ascii_lowercase()).collect() }
+    }
+
+    fn is_sensitive(&self, key: &str) -> bool {
+        let key = key.to_ascii_lowercase();
+        self.fields.iter().any(|field| {
+            key == *field || key.strip_suffix(field.as_str()).is_some_and(|prefix| prefix.ends_with(['_', '-']))
+        })
+    }
+
+    pub fn redact<'a>(&self, line: &'a str) -> Cow<'a, str> {
+        let bytes = line.as_bytes();
+        let mut redacted = String::new();
+        let mut copied = 0;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            if !matches!(bytes[i], b'=' | b':') || !self.is_sensitive(key_before(line, i)) {
+                i += 1;
+                continue;
+            }
+            let Some((start, end)) = value_after(bytes, i + 1) else {
+                i += 1;
+                continue;
+            };
+            redacted.push_str(&line[copied..start]);
+            redacted.push_str(REDACTED);
+            copied = end;
+            i = end;
+        }
+
+        if copied == 0 {
+            return Cow::Borrowed(line);
+        }
+        redacted.push_str(&line[copied..]);
+        Cow::Owned(redacted)
+    }
+}
+
+/// The field name ending right before the separator at `separator`, past a closing `"` or `\"`.
+fn key_before(line: &str, separator: usize) -> &str {
+    let head = line[..separator].trim_end_matches('"').trim_end_matches('\\');
+    let start = head
+        .rfind(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '-'))
+        .map_or(0, |position| position + 1);
+    &head[start..]
+}
+
+/// Byte range of the value starting at `from`, inside its quotes when quoted.
+/// `None` for objects and lists, and for a separator with nothing after it.
+fn value_after(bytes: &[u8], from: usize) -> Option<(usize, usize)> {
+    let mut start = from;
+    while start < bytes.len() && bytes[start] == b' ' {
+        start += 1;
+    }
+
+    match bytes.get(start)? {
+        b'{' | b'[' => None,
+        // --- JSON inside a JSON string: \"value\"
+        b'\\' if bytes.get(start + 1) == Some(&b'"') => {
+            let start = start + 2;
+            let end = (start..bytes.len()).find(|&i| bytes[i] == b'\\' && bytes.get(i + 1) == Some(&b'"'))?;
+            Some((start, end))
+        }
+        b'"' => {
+            let start = start + 1;
+            let end = (start..bytes.len()).find(|&i| bytes[i] == b'"' && bytes[i - 1] != b'\\')?;
+            Some((start, end))
+        }
+        _ => {
+            let end = (start..bytes.len())
+                .find(|&i| matches!(bytes[i], b' ' | b',' | b'}' | b']' | b')' | b';' | b'&' | b'\\' | b'\n'))
+                .unwrap_or(bytes.len());
+            (end > start).then_some((start, end))
+        }
+    }
+}
+
+/// Redacts every formatted event on its way to `inner`.
+#[derive(Clone)]
+pub struct RedactingMakeWriter<M> {
+    inner: M,
+    redactor: Arc<Redactor>,
+}
+
+impl<M> RedactingMakeWriter<M> {
+    pub fn new(inner: M, redactor: Arc<Redactor>) -> Self {
+        Self { inner, redactor }
+    }
+}
+
+impl<'a, M: MakeWriter<'a>> MakeWriter<'a> for RedactingMakeWriter<M> {
+    type Writer = RedactingWriter<M::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RedactingWriter { inner: self.inner.make_writer(), redactor: Arc::clone(&self.redactor) }
+    }
+}
+
+pub struct RedactingWriter<W> {
+    inner: W,
+    redactor: Arc<Redactor>,
+}
+
+impl<W: io::Write> io::Write for RedactingWriter<W> {
+    // NOTE: the fmt layer writes each event whole, so a field is never split between two writes
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let line = String::from_utf8_lossy(buf);
+        self.inner.write_all(self.redactor.redact(&line).as_bytes())?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn redactor() -> Redactor {
+        Redactor::new(&["user_address".to_string(), "phn_nmb".to_string(), "token".to_string()])
+    }
+
+    #[test]
+    fn test_redact() {
+        let redactor = redactor();
+
+        assert_eq!(redactor.redact("user_address=alice.near amount=1"), "user_address=[REDACTED] amount=1");
+        assert_eq!(redactor.redact(r#"{"user_address":"alice.near","amount":"1"}"#), r#"{"user_address":"[REDACTED]","amount":"1"}"#);
+        assert_eq!(redactor.redact(r#"{"phn_nmb": 01012345678, "id": 1}"#), r#"{"phn_nmb": [REDACTED], "id": 1}"#);
+        assert_eq!(
+            redactor.redact(r#"{"message":"{\"sended_user_address\":\"bob.near\",\"id\":1}"}"#),
+            r#"{"message":"{\"sended_user_address\":\"[REDACTED]\",\"id\":1}"}"#,
+        );
+        assert_eq!(redactor.redact(r#"Ctx { access_token: "abc", ggl_id: "1" }"#), r#"Ctx { access_token: "[REDACTED]", ggl_id: "1" }"#);
+        // --- names only match whole parts
+        assert_eq!(redactor.redact("tokens=3 untoken=4"), "tokens=3 untoken=4");
+        assert_eq!(redactor.redact("http://localhost:8090 12:30:01"), "http://localhost:8090 12:30:01");
+        assert_eq!(redactor.redact(r#"{"user_address":{"id":1}}"#), r#"{"user_address":{"id":1}}"#);
+    }
+}
@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+use std::time::Duration;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use ::config::{Config, ConfigError, File as ConfigFile};
+
+/// What went wrong with an rpc call, decides whether it is worth another attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, strum_macros::Display)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ErrorClass {
+    /// the node or a proxy timed out, the request may still go through
+    Timeout,
+    /// connection refused or reset, bad response body
+    Transport,
+    /// 5xx and node internal errors
+    ServerError,
+    /// 429
+    RateLimited,
+    /// the access key nonce moved, signing again with a resynced nonce
+    InvalidNonce,
+    InvalidSignature,
+    /// anything the node will answer the same way again: bad requests, unknown accounts, invalid transactions
+    Rejected,
+}
+
+impl ErrorClass {
+    pub const ALL: [ErrorClass; 7] = [
+        ErrorClass::Timeout,
+        ErrorClass::Transport,
+        ErrorClass::ServerError,
+        ErrorClass::RateLimited,
+        ErrorClass::InvalidNonce,
+        ErrorClass::InvalidSignature,
+        ErrorClass::Rejected,
+    ];
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RetryDecision {
+    Retry,
+    Fail,
+}
+
+/// Retries of near rpc calls, the `[retry]` table of `config.toml`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RetryPolicy {
+    /// attempts including the first one
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+    #[serde(default = "default_initial_backoff_ms")]
+    pub initial_backoff_ms: u64,
+    #[serde(default = "default_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+    /// no retry starts once this much time has passed since the first attempt
+    #[serde(default = "default_max_elapsed_ms")]
+    pub max_elapsed_ms: u64,
+    /// each delay is moved by up to this fraction, up or down
+    #[serde(default = "default_jitter")]
+    pub jitter: f64,
+    /// overrides of `default_decision`
+    #[serde(default)]
+    pub decisions: HashMap<ErrorClass, RetryDecision>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_max_attempts(),
+            initial_backoff_ms: default_initial_backoff_ms(),
+            max_backoff_ms: default_max_backoff_ms(),
+            max_elapsed_ms: default_max_elapsed_ms(),
+            jitter: default_jitter(),
+            decisions: HashMap::new(),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Reads the `[retry]` table of `config.toml`.
+    pub fn init() -> Self {
+        let result = Config::builder()
+            .add_source(ConfigFile::with_name("config.toml"))
+            .build()
+            .unwrap()
+            .get::<RetryPolicy>("retry");
+
+        match result {
+            Ok(retry_policy) => retry_policy,
+            Err(ConfigError::NotFound(_)) => RetryPolicy::default(),
+            Err(e) => panic!("Failed to parse [retry] config: {e}"),
+        }
+    }
+
+    pub fn decision(&self, class: ErrorClass) -> RetryDecision {
+        self.decisions.get(&class).copied().unwrap_or_else(|| default_decision(class))
+    }
+
+    pub fn max_elapsed(&self) -> Duration {
+        Duration::from_millis(self.max_elapsed_ms)
+    }
+
+    /// Delay before the retry following `attempt` (1-based), with jitter.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let delay_ms = self.backoff_ms(attempt) as f64;
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        let factor = if jitter > 0.0 { rand::thread_rng().gen_range(1.0 - jitter..=1.0 + jitter) } else { 1.0 };
+        Duration::from_millis((delay_ms * factor) as u64)
+    }
+
+    /// Exponential backoff: `initial * 2^(attempt - 1)`, capped at `max_backoff_ms`.
+    fn backoff_ms(&self, attempt: u32) -> u64 {
+        let exponent = attempt.saturating_sub(1).min(20);
+        self.initial_backoff_ms.saturating_mul(1u64 << exponent).min(self.max_backoff_ms)
+    }
+}
+
+pub fn default_decision(class: ErrorClass) -> RetryDecision {
+    match class {
+        ErrorClass::Timeout
+        | ErrorClass::Transport
+        | ErrorClass::ServerError
+        | ErrorClass::RateLimited
+        | ErrorClass::InvalidNonce => RetryDecision::Retry,
+        ErrorClass::InvalidSignature | ErrorClass::Rejected => RetryDecision::Fail,
+    }
+}
+
+fn default_max_attempts() -> u32 { 5 }
+fn default_initial_backoff_ms() -> u64 { 250 }
+fn default_max_backoff_ms() -> u64 { 5_000 }
+fn default_max_elapsed_ms() -> u64 { 30_000 }
+fn default_jitter() -> f64 { 0.2 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff() {
+        let policy = RetryPolicy { jitter: 0.0, ..Default::default() };
+
+        assert_eq!(policy.backoff(1), Duration::from_millis(250));
+        assert_eq!(policy.backoff(3), Duration::from_millis(1_000));
+        assert_eq!(policy.backoff(10), Duration::from_millis(5_000));
+
+        let policy = RetryPolicy { jitter: 0.2, ..Default::default() };
+        let delay = policy.backoff(2);
+        assert!(delay >= Duration::from_millis(399) && delay <= Duration::from_millis(600));
+    }
+
+    #[test]
+    fn test_decision_overrides() {
+        let policy = RetryPolicy {
+            decisions: HashMap::from([(ErrorClass::Timeout, RetryDecision::Fail), (ErrorClass::Rejected, RetryDecision::Retry)]),
+            ..Default::default()
+        };
+
+        assert_eq!(policy.decision(ErrorClass::Timeout), RetryDecision::Fail);
+        assert_eq!(policy.decision(ErrorClass::Rejected), RetryDecision::Retry);
+        assert_eq!(policy.decision(ErrorClass::ServerError), RetryDecision::Retry);
+        assert_eq!(policy.decision(ErrorClass::InvalidSignature), RetryDecision::Fail);
+    }
+}
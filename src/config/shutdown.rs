@@ -0,0 +1,42 @@
+use std::time::Duration;
+use serde::Deserialize;
+use ::config::{Config, ConfigError, File as ConfigFile};
+
+/// Graceful shutdown on SIGTERM or ctrl-c, the `[shutdown]` table of `config.toml`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ShutdownConfig {
+    /// from the signal, how long open requests and then the payouts still in flight get to finish
+    #[serde(default = "default_drain_timeout_secs")]
+    pub drain_timeout_secs: u64,
+}
+
+impl Default for ShutdownConfig {
+    fn default() -> Self {
+        Self {
+            drain_timeout_secs: default_drain_timeout_secs(),
+        }
+    }
+}
+
+impl ShutdownConfig {
+    /// Reads the `[shutdown]` table of `config.toml`.
+    pub fn init() -> Self {
+        let result = Config::builder()
+            .add_source(ConfigFile::with_name("config.toml"))
+            .build()
+            .unwrap()
+            .get::<ShutdownConfig>("shutdown");
+
+        match result {
+            Ok(shutdown_config) => shutdown_config,
+            Err(ConfigError::NotFound(_)) => ShutdownConfig::default(),
+            Err(e) => panic!("Failed to parse [shutdown] config: {e}"),
+        }
+    }
+
+    pub fn drain_timeout(&self) -> Duration {
+        Duration::from_secs(self.drain_timeout_secs)
+    }
+}
+
+fn default_drain_timeout_secs() -> u64 { 30 }
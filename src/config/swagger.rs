@@ -3,14 +3,33 @@ use utoipa::{Modify, OpenApi};
 
 use crate::adapter::input::web::_dev_routes_login::LoginPayload;
 use crate::adapter::input::web::middleware::response::ErrorResponse;
-use crate::domain::model::coin_network::CoinNetworkDetailsResponse;
-use crate::domain::model::network::NetworkResponse;
-use crate::domain::model::coin::CoinResponse;
+use crate::domain::model::coin_network::{CoinNetworkDetailsResponse, FtMetadataCheckResponse, NewCoinNetworkPayload, UpdateCoinNetworkPayload};
+use crate::domain::model::near::{AddSignerKeysPayload, CircuitBreakerStatus, CircuitState, FtMetadata, RemoveSignerKeysPayload, RpcEndpointStatus, SignerKey, SignerKeyChanges};
+use crate::domain::model::network::{NetworkResponse, NewNetworkPayload, UpdateNetworkPayload};
+use crate::domain::model::coin::{CoinResponse, NewCoinPayload, UpdateCoinPayload};
 use crate::domain::model::reward_claim::{CombinedRewardClaimResponse, NewRewardClaimPayload};
 use crate::adapter::input::web::routes_hello::__path_hello;
+use crate::adapter::input::web::routes_health::{__path_live, __path_ready};
+use crate::domain::model::health::{ComponentHealth, HealthStatus, LivenessReport, ReadinessReport};
 use crate::adapter::input::web::routes_coin_network::__path_list_coin_networks;
 use crate::adapter::input::web::routes_reward_claim::{__path_create_reward_claim, __path_list_me_reward_claim};
 use crate::domain::model::reward_claim_detail::RewardClaimDetailResponse;
+use crate::domain::model::webhook_delivery::WebhookDeliveryResponse;
+use crate::domain::model::audit_log::AuditLogResponse;
+use crate::adapter::input::web::routes_admin_webhook::{__path_list_webhook_deliveries, __path_redeliver_webhook, __path_requeue_outbox_event};
+use crate::adapter::input::web::routes_admin_audit_log::__path_list_audit_logs;
+use crate::adapter::input::web::routes_admin_api_key::{__path_create_api_key, __path_list_api_keys, __path_rotate_api_key, __path_revoke_api_key};
+use crate::adapter::input::web::routes_admin_coin::{
+    SetActivePayload,
+    __path_create_coin, __path_update_coin, __path_set_coin_active,
+    __path_create_network, __path_update_network, __path_set_network_active,
+    __path_create_coin_network, __path_update_coin_network, __path_set_coin_network_active, __path_deprecate_coin_network,
+    __path_check_ft_metadata, __path_sync_ft_metadata,
+};
+use crate::adapter::input::web::routes_admin_signer_key::{
+    __path_add_signer_keys, __path_list_signer_keys, __path_reload_signer_keys, __path_remove_signer_keys,
+};
+use crate::domain::model::api_key::{ApiKeyResponse, ApiKeyScope, IssuedApiKeyResponse, NewApiKeyPayload, RotateApiKeyPayload};
 
 struct SecurityAddon;
 
@@ -25,6 +44,10 @@ impl Modify for SecurityAddon {
                 "ggl_id",
                 SecurityScheme::ApiKey(ApiKey::Cookie(ApiKeyValue::new("ggl_id"))),
             );
+            components.add_security_scheme(
+                "x-api-key",
+                SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("x-api-key"))),
+            );
             components.add_security_scheme(
                 "x-user-right",
                 SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("x-user-right"))),
@@ -43,10 +66,35 @@ impl Modify for SecurityAddon {
     ),
     paths(
         hello,
+        live,
+        ready,
         list_coin_networks,
         create_reward_claim,
-        list_me_reward_claim
-        
+        list_me_reward_claim,
+        list_webhook_deliveries,
+        redeliver_webhook,
+        requeue_outbox_event,
+        list_audit_logs,
+        create_api_key,
+        list_api_keys,
+        rotate_api_key,
+        revoke_api_key,
+        create_coin,
+        update_coin,
+        set_coin_active,
+        create_network,
+        update_network,
+        set_network_active,
+        create_coin_network,
+        update_coin_network,
+        set_coin_network_active,
+        deprecate_coin_network,
+        check_ft_metadata,
+        sync_ft_metadata,
+        list_signer_keys,
+        reload_signer_keys,
+        add_signer_keys,
+        remove_signer_keys
     ),
     components(
         schemas(
@@ -54,12 +102,20 @@ impl Modify for SecurityAddon {
             NewRewardClaimPayload,
             CoinNetworkDetailsResponse, CoinResponse, NetworkResponse,
             CombinedRewardClaimResponse, RewardClaimDetailResponse,
+            WebhookDeliveryResponse, AuditLogResponse,
+            NewApiKeyPayload, RotateApiKeyPayload, ApiKeyScope, ApiKeyResponse, IssuedApiKeyResponse,
+            NewCoinPayload, UpdateCoinPayload, NewNetworkPayload, UpdateNetworkPayload,
+            NewCoinNetworkPayload, UpdateCoinNetworkPayload, SetActivePayload,
+            FtMetadata, FtMetadataCheckResponse,
+            SignerKey, SignerKeyChanges, AddSignerKeysPayload, RemoveSignerKeysPayload,
+            HealthStatus, ComponentHealth, LivenessReport, ReadinessReport, RpcEndpointStatus, CircuitBreakerStatus, CircuitState,
             ErrorResponse
         )
     ),
     security(
         ("access_token" = []),
         ("ggl_id" = []),
+        ("x-api-key" = []),
         ("x-user-right" = [])
     )
 )]
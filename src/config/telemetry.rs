@@ -0,0 +1,49 @@
+use serde::Deserialize;
+use ::config::{Config, ConfigError, File as ConfigFile};
+
+/// OTLP trace export, the `[telemetry]` table of `config.toml`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TelemetryConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// OTLP/gRPC collector
+    #[serde(default = "default_otlp_endpoint")]
+    pub otlp_endpoint: String,
+    #[serde(default = "default_service_name")]
+    pub service_name: String,
+    /// share of new traces exported, a caller's `traceparent` keeps its own sampling decision
+    #[serde(default = "default_sample_ratio")]
+    pub sample_ratio: f64,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            otlp_endpoint: default_otlp_endpoint(),
+            service_name: default_service_name(),
+            sample_ratio: default_sample_ratio(),
+        }
+    }
+}
+
+impl TelemetryConfig {
+    /// Reads the `[telemetry]` table of `config.toml`.
+    pub fn init() -> Self {
+        let result = Config::builder()
+            .add_source(ConfigFile::with_name("config.toml"))
+            .build()
+            .unwrap()
+            .get::<TelemetryConfig>("telemetry");
+
+        match result {
+            Ok(telemetry_config) => telemetry_config,
+            Err(ConfigError::NotFound(_)) => TelemetryConfig::default(),
+            Err(e) => panic!("Failed to parse [telemetry] config: {e}"),
+        }
+    }
+}
+
+fn default_otlp_endpoint() -> String { "http://localhost:4317".to_string() }
+fn default_service_name() -> String { "payment".to_string() }
+fn default_sample_ratio() -> f64 { 1.0 }
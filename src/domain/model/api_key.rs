@@ -0,0 +1,223 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use rand::{distributions::Alphanumeric, Rng};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use crate::adapter::output::persistence::db::schema::api_key;
+
+/// Keys look like `lpk_<prefix>_<secret>`. Only the prefix and the sha256 of the whole key are stored.
+pub const API_KEY_PREFIX: &str = "lpk";
+const KEY_PREFIX_LEN: usize = 12;
+const KEY_SECRET_LEN: usize = 40;
+/// `last_used_date` is only written again once it is this old, not on every request.
+const LAST_USED_TOUCH_INTERVAL_MINS: i64 = 5;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum ApiKeyScope {
+    #[serde(rename = "claims:write")]
+    ClaimsWrite,
+    #[serde(rename = "reports:read")]
+    ReportsRead,
+    #[serde(rename = "admin")]
+    Admin,
+    /// may act for a user with `x-on-behalf-of`
+    #[serde(rename = "users:act_as")]
+    ActAsUser,
+}
+
+impl ApiKeyScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiKeyScope::ClaimsWrite => "claims:write",
+            ApiKeyScope::ReportsRead => "reports:read",
+            ApiKeyScope::Admin => "admin",
+            ApiKeyScope::ActAsUser => "users:act_as",
+        }
+    }
+
+    pub fn parse(scope: &str) -> Option<Self> {
+        match scope {
+            "claims:write" => Some(ApiKeyScope::ClaimsWrite),
+            "reports:read" => Some(ApiKeyScope::ReportsRead),
+            "admin" => Some(ApiKeyScope::Admin),
+            "users:act_as" => Some(ApiKeyScope::ActAsUser),
+            _ => None,
+        }
+    }
+}
+
+impl core::fmt::Display for ApiKeyScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Returns `(plaintext key, key prefix)`. The plaintext is shown to the caller once and never stored.
+pub fn generate_api_key() -> (String, String) {
+    let key_prefix = random_alphanumeric(KEY_PREFIX_LEN);
+    let secret = random_alphanumeric(KEY_SECRET_LEN);
+    (format!("{API_KEY_PREFIX}_{key_prefix}_{secret}"), key_prefix)
+}
+
+fn random_alphanumeric(len: usize) -> String {
+    rand::thread_rng().sample_iter(&Alphanumeric).take(len).map(char::from).collect()
+}
+
+pub fn hash_api_key(raw_key: &str) -> String {
+    hex::encode(Sha256::digest(raw_key.as_bytes()))
+}
+
+pub fn parse_key_prefix(raw_key: &str) -> Option<&str> {
+    let mut parts = raw_key.splitn(3, '_');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(API_KEY_PREFIX), Some(key_prefix), Some(secret)) if !key_prefix.is_empty() && !secret.is_empty() => Some(key_prefix),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Identifiable, Selectable)]
+#[diesel(table_name = api_key)]
+pub struct ApiKey {
+    pub id: Uuid,
+    pub name: String,
+    pub key_prefix: String,
+    pub key_hash: String,
+    pub scopes: Vec<String>,
+    pub expires_date: Option<NaiveDateTime>,
+    pub revoked_date: Option<NaiveDateTime>,
+    pub last_used_date: Option<NaiveDateTime>,
+    pub rotated_from: Option<Uuid>,
+    pub created_by: String,
+    pub created_date: NaiveDateTime,
+    pub updated_date: NaiveDateTime,
+}
+
+impl ApiKey {
+    pub fn scopes(&self) -> Vec<ApiKeyScope> {
+        self.scopes.iter().filter_map(|scope| ApiKeyScope::parse(scope)).collect()
+    }
+
+    pub fn is_active(&self, now: NaiveDateTime) -> bool {
+        self.revoked_date.is_none() && self.expires_date.map_or(true, |expires_date| expires_date > now)
+    }
+
+    /// Compares the hashes in constant time, so the response time tells nothing about how much of it matched.
+    pub fn matches(&self, raw_key: &str) -> bool {
+        self.key_hash.as_bytes().ct_eq(hash_api_key(raw_key).as_bytes()).into()
+    }
+
+    pub fn needs_last_used_touch(&self, now: NaiveDateTime) -> bool {
+        self.last_used_date.map_or(true, |last_used_date| {
+            now - last_used_date >= chrono::Duration::minutes(LAST_USED_TOUCH_INTERVAL_MINS)
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Insertable)]
+#[diesel(table_name = api_key)]
+pub struct NewApiKey {
+    pub id: Uuid,
+    pub name: String,
+    pub key_prefix: String,
+    pub key_hash: String,
+    pub scopes: Vec<String>,
+    pub expires_date: Option<NaiveDateTime>,
+    pub rotated_from: Option<Uuid>,
+    pub created_by: String,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct NewApiKeyPayload {
+    pub name: String,
+    /// claims:write, reports:read, admin, users:act_as
+    pub scopes: Vec<ApiKeyScope>,
+    /// never expires if omitted
+    pub expires_in_days: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, ToSchema)]
+pub struct RotateApiKeyPayload {
+    /// how long the old key keeps working (default 24h)
+    pub grace_period_secs: Option<i64>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ApiKeyResponse {
+    pub id: String,
+    pub name: String,
+    pub key_prefix: String,
+    pub scopes: Vec<String>,
+    pub expires_date: Option<i64>,
+    pub revoked_date: Option<i64>,
+    pub last_used_date: Option<i64>,
+    pub rotated_from: Option<String>,
+    pub created_by: String,
+    pub created_date: i64,
+}
+
+impl From<ApiKey> for ApiKeyResponse {
+    fn from(api_key: ApiKey) -> Self {
+        ApiKeyResponse {
+            id: api_key.id.to_string(),
+            name: api_key.name,
+            key_prefix: api_key.key_prefix,
+            scopes: api_key.scopes,
+            expires_date: api_key.expires_date.map(|date| date.and_utc().timestamp()),
+            revoked_date: api_key.revoked_date.map(|date| date.and_utc().timestamp()),
+            last_used_date: api_key.last_used_date.map(|date| date.and_utc().timestamp()),
+            rotated_from: api_key.rotated_from.map(|id| id.to_string()),
+            created_by: api_key.created_by,
+            created_date: api_key.created_date.and_utc().timestamp(),
+        }
+    }
+}
+
+/// Returned once on create and rotate, `api_key` can not be read again.
+#[derive(Serialize, ToSchema)]
+pub struct IssuedApiKeyResponse {
+    pub api_key: String,
+    pub key: ApiKeyResponse,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn api_key(raw_key: &str, last_used_date: Option<NaiveDateTime>) -> ApiKey {
+        let now = chrono::Utc::now().naive_utc();
+        ApiKey {
+            id: Uuid::new_v4(),
+            name: "ludium-backend".to_string(),
+            key_prefix: parse_key_prefix(raw_key).unwrap().to_string(),
+            key_hash: hash_api_key(raw_key),
+            scopes: vec![ApiKeyScope::ClaimsWrite.to_string()],
+            expires_date: None,
+            revoked_date: None,
+            last_used_date,
+            rotated_from: None,
+            created_by: "admin".to_string(),
+            created_date: now,
+            updated_date: now,
+        }
+    }
+
+    #[test]
+    fn test_matches() {
+        let (raw_key, _) = generate_api_key();
+        let api_key = api_key(&raw_key, None);
+        assert!(api_key.matches(&raw_key));
+        assert!(!api_key.matches(&format!("{raw_key}x")));
+    }
+
+    #[test]
+    fn test_needs_last_used_touch() {
+        let (raw_key, _) = generate_api_key();
+        let now = chrono::Utc::now().naive_utc();
+        assert!(api_key(&raw_key, None).needs_last_used_touch(now));
+        assert!(!api_key(&raw_key, Some(now - chrono::Duration::minutes(1))).needs_last_used_touch(now));
+        assert!(api_key(&raw_key, Some(now - chrono::Duration::minutes(LAST_USED_TOUCH_INTERVAL_MINS))).needs_last_used_touch(now));
+    }
+}
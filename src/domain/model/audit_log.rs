@@ -0,0 +1,165 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use crate::adapter::output::persistence::db::schema::audit_log;
+
+pub const ACTOR_TYPE_USER: &str = "USER";
+pub const ACTOR_TYPE_ADMIN: &str = "ADMIN";
+pub const ACTOR_TYPE_SYSTEM: &str = "SYSTEM";
+pub const ACTOR_TYPE_API_KEY: &str = "API_KEY";
+pub const ACTOR_TYPE_CLI: &str = "CLI";
+
+pub const ENTITY_REWARD_CLAIM: &str = "REWARD_CLAIM";
+pub const ENTITY_OUTBOX_EVENT: &str = "OUTBOX_EVENT";
+pub const ENTITY_WEBHOOK_DELIVERY: &str = "WEBHOOK_DELIVERY";
+pub const ENTITY_API_KEY: &str = "API_KEY";
+pub const ENTITY_COIN: &str = "COIN";
+pub const ENTITY_NETWORK: &str = "NETWORK";
+pub const ENTITY_COIN_NETWORK: &str = "COIN_NETWORK";
+pub const ENTITY_SIGNER_KEY: &str = "SIGNER_KEY";
+
+pub const ACTION_REWARD_CLAIM_CREATE: &str = "reward_claim.create";
+pub const ACTION_REWARD_CLAIM_UPDATE_STATUS: &str = "reward_claim.update_status";
+pub const ACTION_REWARD_CLAIM_PRICE_SNAPSHOT: &str = "reward_claim.price_snapshot";
+pub const ACTION_OUTBOX_EVENT_REQUEUE: &str = "outbox_event.requeue";
+pub const ACTION_WEBHOOK_REDELIVER: &str = "webhook_delivery.redeliver";
+pub const ACTION_API_KEY_CREATE: &str = "api_key.create";
+pub const ACTION_API_KEY_ROTATE: &str = "api_key.rotate";
+pub const ACTION_API_KEY_REVOKE: &str = "api_key.revoke";
+pub const ACTION_COIN_CREATE: &str = "coin.create";
+pub const ACTION_COIN_UPDATE: &str = "coin.update";
+pub const ACTION_COIN_SET_ACTIVE: &str = "coin.set_active";
+pub const ACTION_NETWORK_CREATE: &str = "network.create";
+pub const ACTION_NETWORK_UPDATE: &str = "network.update";
+pub const ACTION_NETWORK_SET_ACTIVE: &str = "network.set_active";
+pub const ACTION_COIN_NETWORK_CREATE: &str = "coin_network.create";
+pub const ACTION_COIN_NETWORK_UPDATE: &str = "coin_network.update";
+pub const ACTION_COIN_NETWORK_SET_ACTIVE: &str = "coin_network.set_active";
+pub const ACTION_COIN_NETWORK_DEPRECATE: &str = "coin_network.deprecate";
+pub const ACTION_COIN_NETWORK_METADATA_CHECK: &str = "coin_network.metadata_check";
+pub const ACTION_SIGNER_KEY_ADD: &str = "signer_key.add";
+pub const ACTION_SIGNER_KEY_REMOVE: &str = "signer_key.remove";
+
+/// Who triggered a change. Built from `Ctx` for api requests, `AuditActor::system()` for background jobs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditActor {
+    pub actor_id: String,
+    pub actor_type: String,
+    pub request_id: Option<Uuid>,
+    /// user an api key acted for with `x-on-behalf-of`
+    #[serde(default)]
+    pub on_behalf_of: Option<String>,
+}
+
+impl AuditActor {
+    pub fn new(actor_id: impl Into<String>, actor_type: &str, request_id: Option<Uuid>) -> Self {
+        Self {
+            actor_id: actor_id.into(),
+            actor_type: actor_type.to_string(),
+            request_id,
+            on_behalf_of: None,
+        }
+    }
+
+    pub fn on_behalf_of(self, user_id: Option<String>) -> Self {
+        Self { on_behalf_of: user_id, ..self }
+    }
+
+    pub fn system() -> Self {
+        Self::new(ACTOR_TYPE_SYSTEM, ACTOR_TYPE_SYSTEM, None)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Identifiable, Selectable)]
+#[diesel(table_name = audit_log)]
+pub struct AuditLog {
+    pub id: Uuid,
+    pub request_id: Option<Uuid>,
+    pub actor_id: String,
+    pub actor_type: String,
+    pub action: String,
+    pub entity_type: String,
+    pub entity_id: String,
+    pub before_value: Option<Value>,
+    pub after_value: Option<Value>,
+    pub created_date: NaiveDateTime,
+    pub on_behalf_of: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Insertable)]
+#[diesel(table_name = audit_log)]
+pub struct NewAuditLog {
+    pub id: Uuid,
+    pub request_id: Option<Uuid>,
+    pub actor_id: String,
+    pub actor_type: String,
+    pub action: String,
+    pub entity_type: String,
+    pub entity_id: String,
+    pub before_value: Option<Value>,
+    pub after_value: Option<Value>,
+    pub on_behalf_of: Option<String>,
+}
+
+impl NewAuditLog {
+    pub fn new<T: Serialize>(actor: &AuditActor, action: &str, entity_type: &str, entity_id: impl ToString, before: Option<&T>, after: Option<&T>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            request_id: actor.request_id,
+            actor_id: actor.actor_id.clone(),
+            actor_type: actor.actor_type.clone(),
+            action: action.to_string(),
+            entity_type: entity_type.to_string(),
+            entity_id: entity_id.to_string(),
+            before_value: before.and_then(|v| serde_json::to_value(v).ok()),
+            after_value: after.and_then(|v| serde_json::to_value(v).ok()),
+            on_behalf_of: actor.on_behalf_of.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AuditLogFilter {
+    pub entity_type: Option<String>,
+    pub entity_id: Option<String>,
+    pub actor_id: Option<String>,
+    pub limit: i64,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct AuditLogResponse {
+    pub id: String,
+    pub request_id: Option<String>,
+    pub actor_id: String,
+    pub actor_type: String,
+    pub on_behalf_of: Option<String>,
+    pub action: String,
+    pub entity_type: String,
+    pub entity_id: String,
+    #[schema(value_type = Option<Object>)]
+    pub before_value: Option<Value>,
+    #[schema(value_type = Option<Object>)]
+    pub after_value: Option<Value>,
+    pub created_date: i64,
+}
+
+impl From<AuditLog> for AuditLogResponse {
+    fn from(audit_log: AuditLog) -> Self {
+        AuditLogResponse {
+            id: audit_log.id.to_string(),
+            request_id: audit_log.request_id.map(|id| id.to_string()),
+            actor_id: audit_log.actor_id,
+            actor_type: audit_log.actor_type,
+            on_behalf_of: audit_log.on_behalf_of,
+            action: audit_log.action,
+            entity_type: audit_log.entity_type,
+            entity_id: audit_log.entity_id,
+            before_value: audit_log.before_value,
+            after_value: audit_log.after_value,
+            created_date: audit_log.created_date.and_utc().timestamp(),
+        }
+    }
+}
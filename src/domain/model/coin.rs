@@ -6,6 +6,11 @@ use utoipa::ToSchema;
 use uuid::Uuid;
 use crate::adapter::output::persistence::db::schema::coin;
 
+pub const MAX_SYMBOL_LEN: usize = 10;
+pub const MAX_NAME_LEN: usize = 50;
+/// NEAR itself uses 24, the largest in use
+pub const MAX_DECIMALS: i32 = 24;
+
 #[derive(Clone, Debug, Serialize, Deserialize, DbEnum)]
 #[ExistingTypePath = "crate::adapter::output::persistence::db::schema::sql_types::CoinType"]
 pub enum CoinType {
@@ -28,6 +33,18 @@ impl From<String> for CoinType {
     }
 }
 
+impl CoinType {
+    /// Strict version of `From<String>`, unknown types are `None` instead of `Native`.
+    pub fn parse(coin_type: &str) -> Option<Self> {
+        match coin_type.to_uppercase().as_str() {
+            "NATIVE" => Some(CoinType::Native),
+            "FT" => Some(CoinType::FT),
+            "NFT" => Some(CoinType::NFT),
+            _ => None,
+        }
+    }
+}
+
 impl PartialEq for CoinType {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
@@ -48,7 +65,7 @@ impl core::fmt::Display for CoinType {
     }
 }
 
-#[derive(Clone, Debug, Queryable, Identifiable)]
+#[derive(Clone, Debug, Serialize, Queryable, Identifiable)]
 #[diesel(table_name = coin)]
 pub struct Coin {
     pub id: Uuid,
@@ -58,6 +75,7 @@ pub struct Coin {
     pub decimals: i32,
     pub created_date: NaiveDateTime,
     pub updated_date: NaiveDateTime,
+    pub is_active: bool,
 }
 
 #[derive(Insertable, Debug, Clone)]
@@ -70,14 +88,85 @@ pub struct NewCoin {
     pub coin_type: CoinType,
 }
 
-#[derive(Deserialize, Clone)]
+#[derive(Deserialize, Clone, ToSchema)]
 pub struct NewCoinPayload {
     pub name: String,
     pub symbol: String,
+    /// NATIVE, FT, NFT
     pub coin_type: String,
     pub decimals: i32,
 }
 
+impl NewCoinPayload {
+    pub fn validate(&self) -> core::result::Result<(), String> {
+        validate_name(&self.name)?;
+        validate_symbol(&self.symbol)?;
+        validate_decimals(self.decimals)?;
+        CoinType::parse(&self.coin_type)
+            .map(|_| ())
+            .ok_or_else(|| format!("coin_type must be one of NATIVE, FT, NFT: {}", self.coin_type))
+    }
+}
+
+#[derive(Deserialize, Clone, Default, ToSchema)]
+pub struct UpdateCoinPayload {
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub decimals: Option<i32>,
+}
+
+impl UpdateCoinPayload {
+    pub fn validate(&self) -> core::result::Result<(), String> {
+        if self.name.is_none() && self.symbol.is_none() && self.decimals.is_none() {
+            return Err("nothing to update".to_string());
+        }
+        self.name.as_deref().map_or(Ok(()), validate_name)?;
+        self.symbol.as_deref().map_or(Ok(()), validate_symbol)?;
+        self.decimals.map_or(Ok(()), validate_decimals)
+    }
+}
+
+#[derive(AsChangeset, Debug, Clone)]
+#[diesel(table_name = coin)]
+pub struct CoinChangeset {
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub decimals: Option<i32>,
+    pub updated_date: NaiveDateTime,
+}
+
+impl From<UpdateCoinPayload> for CoinChangeset {
+    fn from(payload: UpdateCoinPayload) -> Self {
+        CoinChangeset {
+            name: payload.name,
+            symbol: payload.symbol,
+            decimals: payload.decimals,
+            updated_date: chrono::Utc::now().naive_utc(),
+        }
+    }
+}
+
+fn validate_name(name: &str) -> core::result::Result<(), String> {
+    if name.trim().is_empty() || name.chars().count() > MAX_NAME_LEN {
+        return Err(format!("name must be 1 to {} characters", MAX_NAME_LEN));
+    }
+    Ok(())
+}
+
+fn validate_symbol(symbol: &str) -> core::result::Result<(), String> {
+    if symbol.is_empty() || symbol.len() > MAX_SYMBOL_LEN || !symbol.chars().all(|c| c.is_ascii_alphanumeric() || c == '.') {
+        return Err(format!("symbol must be 1 to {} ascii letters, digits or dots: {}", MAX_SYMBOL_LEN, symbol));
+    }
+    Ok(())
+}
+
+fn validate_decimals(decimals: i32) -> core::result::Result<(), String> {
+    if !(0..=MAX_DECIMALS).contains(&decimals) {
+        return Err(format!("decimals must be between 0 and {}: {}", MAX_DECIMALS, decimals));
+    }
+    Ok(())
+}
+
 #[derive(Serialize, ToSchema)]
 pub struct CoinResponse {
     pub id: String,
@@ -85,6 +174,7 @@ pub struct CoinResponse {
     pub symbol: String,
     pub decimals: i32,
     pub coin_type: String,
+    pub is_active: bool,
     pub created_date: i64, 
     pub updated_date: i64, 
 }
@@ -99,6 +189,7 @@ impl From<Coin> for CoinResponse {
             symbol: coin.symbol,
             decimals: coin.decimals,
             coin_type: coin.coin_type.to_string(),
+            is_active: coin.is_active,
             created_date: coin.created_date.and_utc().timestamp(),
             updated_date: coin.updated_date.and_utc().timestamp(),
         }
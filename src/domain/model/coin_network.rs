@@ -1,10 +1,13 @@
+use std::str::FromStr;
 use diesel::prelude::*;
+use near_primitives::types::AccountId;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use uuid::Uuid;
 use crate::adapter::output::persistence::db::schema::coin_network;
-use crate::domain::model::coin::Coin;
-use crate::domain::model::network::Network;
+use crate::domain::model::coin::{Coin, CoinType};
+use crate::domain::model::near::FtMetadata;
+use crate::domain::model::network::{Network, NEAR_NETWORK_CODE};
 
 use super::coin::CoinResponse;
 use super::network::NetworkResponse;
@@ -21,6 +24,21 @@ pub struct CoinNetwork {
     pub contract_address: Option<String>,
     pub created_date: NaiveDateTime,
     pub updated_date: NaiveDateTime,
+    pub is_active: bool,
+    pub deprecated_date: Option<NaiveDateTime>,
+    pub metadata_mismatch: Option<String>,
+    pub metadata_checked_date: Option<NaiveDateTime>,
+}
+
+impl CoinNetwork {
+    pub fn is_deprecated(&self) -> bool {
+        self.deprecated_date.is_some()
+    }
+
+    /// New claims are only accepted when the coin network, its coin and its network are all enabled.
+    pub fn is_available(&self, coin: &Coin, network: &Network) -> bool {
+        self.is_active && !self.is_deprecated() && coin.is_active && network.is_active
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Insertable)]
@@ -32,13 +50,50 @@ pub struct NewCoinNetwork {
     pub contract_address: Option<String>,
 }
 
-#[derive(Deserialize, Clone)]
+#[derive(Deserialize, Clone, ToSchema)]
 pub struct NewCoinNetworkPayload {
+    #[schema(value_type = String)]
     pub coin_id: Uuid,
+    #[schema(value_type = String)]
     pub network_id: Uuid,
+    /// token contract, must be empty for NATIVE coins
     pub contract_address: Option<String>,
 }
 
+#[derive(Deserialize, Clone, ToSchema)]
+pub struct UpdateCoinNetworkPayload {
+    pub contract_address: Option<String>,
+}
+
+/// Differences between the coin row and the on-chain `ft_metadata`, `None` when they agree.
+/// Symbols are compared case insensitively, contracts like `usdt.tether-token.near` report `USDt`.
+pub fn metadata_mismatch(coin: &Coin, metadata: &FtMetadata) -> Option<String> {
+    let mut mismatches = vec![];
+    if coin.decimals != i32::from(metadata.decimals) {
+        mismatches.push(format!("decimals: db {}, chain {}", coin.decimals, metadata.decimals));
+    }
+    if !coin.symbol.eq_ignore_ascii_case(&metadata.symbol) {
+        mismatches.push(format!("symbol: db {}, chain {}", coin.symbol, metadata.symbol));
+    }
+    (!mismatches.is_empty()).then(|| mismatches.join("; "))
+}
+
+/// NATIVE coins have no contract, FT and NFT need one in the address format of the network.
+pub fn validate_contract_address(network_code: &str, coin_type: &CoinType, contract_address: Option<&str>) -> core::result::Result<(), String> {
+    match (coin_type, contract_address) {
+        (CoinType::Native, None) => Ok(()),
+        (CoinType::Native, Some(_)) => Err("NATIVE coins must not have a contract_address".to_string()),
+        (_, None) => Err(format!("{} coins require a contract_address", coin_type)),
+        (_, Some(address)) if network_code.eq_ignore_ascii_case(NEAR_NETWORK_CODE) => AccountId::from_str(address)
+            .map(|_| ())
+            .map_err(|_| format!("contract_address is not a valid NEAR account id: {}", address)),
+        (_, Some(address)) if address.is_empty() || address.len() > 100 || address.chars().any(char::is_whitespace) => {
+            Err(format!("invalid contract_address: {}", address))
+        }
+        _ => Ok(()),
+    }
+}
+
 // region: --- Response 
 
 #[derive(Serialize)]
@@ -47,6 +102,11 @@ pub struct CoinNetworkResponse {
     pub coin_id: String,
     pub network_id: String,
     pub contract_address: Option<String>,
+    pub is_active: bool,
+    pub deprecated_date: Option<i64>,
+    /// payouts are refused while set
+    pub metadata_mismatch: Option<String>,
+    pub metadata_checked_date: Option<i64>,
     pub created_date: i64, 
     pub updated_date: i64, 
 }
@@ -58,6 +118,10 @@ impl From<CoinNetwork> for CoinNetworkResponse {
             coin_id: coin_network.coin_id.to_string(),
             network_id: coin_network.network_id.to_string(),
             contract_address: coin_network.contract_address,
+            is_active: coin_network.is_active,
+            deprecated_date: coin_network.deprecated_date.map(|date| date.and_utc().timestamp()),
+            metadata_mismatch: coin_network.metadata_mismatch,
+            metadata_checked_date: coin_network.metadata_checked_date.map(|date| date.and_utc().timestamp()),
             created_date: coin_network.created_date.and_utc().timestamp(),
             updated_date: coin_network.updated_date.and_utc().timestamp(),
         }
@@ -70,6 +134,11 @@ pub struct CoinNetworkDetailsResponse {
     pub coin: CoinResponse,
     pub network: NetworkResponse,
     pub contract_address: Option<String>,
+    pub is_active: bool,
+    pub deprecated_date: Option<i64>,
+    /// payouts are refused while set
+    pub metadata_mismatch: Option<String>,
+    pub metadata_checked_date: Option<i64>,
     pub created_date: i64, 
     pub updated_date: i64, 
 }
@@ -81,10 +150,25 @@ impl From<(CoinNetwork, Coin, Network)> for CoinNetworkDetailsResponse {
             network: NetworkResponse::from(network),
             id: coin_network.id.to_string(),
             contract_address: coin_network.contract_address,
+            is_active: coin_network.is_active,
+            deprecated_date: coin_network.deprecated_date.map(|date| date.and_utc().timestamp()),
+            metadata_mismatch: coin_network.metadata_mismatch,
+            metadata_checked_date: coin_network.metadata_checked_date.map(|date| date.and_utc().timestamp()),
             created_date: coin_network.created_date.and_utc().timestamp(),
             updated_date: coin_network.updated_date.and_utc().timestamp(),
         }
     }
 }
 
+/// Result of comparing one FT coin network with its contract.
+#[derive(Serialize, ToSchema)]
+pub struct FtMetadataCheckResponse {
+    pub coin_network_id: String,
+    pub contract_address: Option<String>,
+    pub metadata: Option<FtMetadata>,
+    pub metadata_mismatch: Option<String>,
+    /// set when `ft_metadata` could not be read, the previous check result is kept
+    pub error: Option<String>,
+}
+
 // endregion: --- Response 
\ No newline at end of file
@@ -0,0 +1,50 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+use super::near::{CircuitBreakerStatus, RpcEndpointStatus};
+
+pub const COMPONENT_DB: &str = "db";
+pub const COMPONENT_RPC: &str = "rpc";
+pub const COMPONENT_SIGNER_KEYS: &str = "signer_keys";
+pub const COMPONENT_TREASURY: &str = "treasury";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum HealthStatus {
+    Up,
+    Down,
+}
+
+/// One dependency check of the readiness probe.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ComponentHealth {
+    pub name: String,
+    pub status: HealthStatus,
+    pub latency_ms: u64,
+    /// what was seen when up, why it is down otherwise
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct LivenessReport {
+    pub status: HealthStatus,
+}
+
+/// `UP` only when every component is up.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ReadinessReport {
+    pub status: HealthStatus,
+    pub components: Vec<ComponentHealth>,
+    pub rpc_endpoints: Vec<RpcEndpointStatus>,
+    pub circuit_breaker: CircuitBreakerStatus,
+}
+
+impl ReadinessReport {
+    pub fn new(components: Vec<ComponentHealth>, rpc_endpoints: Vec<RpcEndpointStatus>, circuit_breaker: CircuitBreakerStatus) -> Self {
+        let status = if components.iter().all(|component| component.status == HealthStatus::Up) {
+            HealthStatus::Up
+        } else {
+            HealthStatus::Down
+        };
+        Self { status, components, rpc_endpoints, circuit_breaker }
+    }
+}
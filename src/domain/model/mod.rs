@@ -7,4 +7,11 @@ pub mod reward_claim;
 pub mod reward_claim_detail;
 pub mod mission;
 pub mod mission_submit;
-pub mod detailed_posting;
\ No newline at end of file
+pub mod detailed_posting;
+pub mod outbox_event;
+pub mod webhook_delivery;
+pub mod audit_log;
+pub mod api_key;
+pub mod rate_limit;
+pub mod price;
+pub mod health;
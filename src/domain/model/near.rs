@@ -24,4 +24,188 @@ pub struct TransactionResultResponse {
     pub transaction_hash: CryptoHash,
     pub has_errors: bool,
     pub error_details: Vec<String>,
-}
\ No newline at end of file
+}
+/// NEP-148 `ft_metadata` view result
+#[derive(Clone, Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct FtMetadata {
+    pub spec: String,
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+/// `status` of the rpc node
+#[derive(Clone, Debug, Serialize)]
+pub struct RpcNodeStatus {
+    pub chain_id: String,
+    pub latest_block_height: u64,
+    pub latest_block_time: String,
+    pub syncing: bool,
+}
+
+/// Health of one rpc endpoint, from the last probe and the calls since.
+#[derive(Clone, Debug, Serialize, utoipa::ToSchema)]
+pub struct RpcEndpointStatus {
+    pub url: String,
+    pub priority: u32,
+    pub healthy: bool,
+    /// the last call or probe got an answer
+    pub reachable: bool,
+    /// behind the highest endpoint by more than `rpc_max_block_lag` blocks, or syncing
+    pub lagging: bool,
+    pub latency_ms: Option<u64>,
+    pub block_height: Option<u64>,
+    pub block_lag: Option<u64>,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, strum_macros::Display, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum CircuitState {
+    Closed,
+    /// rpc calls fail fast with `RpcUnavailable`
+    Open,
+    /// trial calls decide between closing and opening again
+    HalfOpen,
+}
+
+/// Circuit breaker around the rpc calls, counters since start.
+#[derive(Clone, Debug, Serialize, utoipa::ToSchema)]
+pub struct CircuitBreakerStatus {
+    pub state: CircuitState,
+    pub consecutive_failures: u32,
+    /// seconds until trial calls are let through, while open
+    pub retry_in_secs: Option<u64>,
+    pub opened_count: u64,
+    /// calls failed fast while open or half-open
+    pub rejected_count: u64,
+}
+
+/// Use of one relayer access key since start.
+#[derive(Clone, Debug, Serialize)]
+pub struct SignerKeyUsage {
+    pub public_key: String,
+    pub in_flight: u64,
+    pub transactions: u64,
+}
+
+/// `view_account` result, amounts in yoctoNEAR
+#[derive(Clone, Debug, Serialize)]
+pub struct AccountBalance {
+    pub account_id: String,
+    pub amount: String,
+    pub locked: String,
+    pub storage_usage: u64,
+}
+
+/// `view_access_key` result of one signer key
+#[derive(Clone, Debug, Serialize)]
+pub struct AccessKeyInfo {
+    pub account_id: String,
+    pub public_key: String,
+    pub nonce: u64,
+    /// FULL_ACCESS or FUNCTION_CALL(<receiver_id>)
+    pub permission: String,
+}
+
+/// Outcome of a sent transaction as the chain sees it.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+#[serde(tag = "status", content = "message", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OnChainTransactionStatus {
+    Succeeded,
+    Failed(String),
+    Pending,
+    NotFound,
+}
+
+pub const ACCESS_KEY_FULL_ACCESS: &str = "FULL_ACCESS";
+/// upper bound of keys added or deleted in one batch transaction
+pub const MAX_SIGNER_KEYS_PER_BATCH: usize = 50;
+
+/// Public half of a signer access key, the secret never leaves the keys file and the signer.
+#[derive(Clone, Debug, Serialize, PartialEq, Eq, utoipa::ToSchema)]
+pub struct SignerKey {
+    pub account_id: String,
+    pub public_key: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize, utoipa::ToSchema)]
+pub struct SignerKeyChanges {
+    pub added: Vec<SignerKey>,
+    pub removed: Vec<SignerKey>,
+}
+
+impl SignerKeyChanges {
+    pub fn between(current: &[SignerKey], next: &[SignerKey]) -> Self {
+        Self {
+            added: next.iter().filter(|key| !current.contains(key)).cloned().collect(),
+            removed: current.iter().filter(|key| !next.contains(key)).cloned().collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Generates `count` ed25519 keys and adds them to the relayer account in one `AddKey` batch.
+/// Function-call keys (the default) can not attach deposits, so they only suit calls without one.
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct AddSignerKeysPayload {
+    pub count: usize,
+    #[serde(default)]
+    pub full_access: bool,
+    /// function-call keys only, defaults to the relayer account
+    #[serde(default)]
+    pub receiver_id: Option<String>,
+    /// function-call keys only, empty allows every method
+    #[serde(default)]
+    pub method_names: Vec<String>,
+    /// function-call keys only, gas allowance in yoctoNEAR, empty is unlimited
+    #[serde(default)]
+    pub allowance: Option<String>,
+}
+
+impl AddSignerKeysPayload {
+    pub fn validate(&self) -> core::result::Result<(), String> {
+        validate_batch_size(self.count)?;
+        if self.full_access && (self.receiver_id.is_some() || !self.method_names.is_empty() || self.allowance.is_some()) {
+            return Err("receiver_id, method_names and allowance only apply to function-call keys".to_string());
+        }
+        if let Some(allowance) = &self.allowance {
+            allowance.parse::<u128>().map_err(|_| format!("allowance must be yoctoNEAR: {}", allowance))?;
+        }
+        if let Some(receiver_id) = &self.receiver_id {
+            receiver_id.parse::<AccountId>().map_err(|e| format!("invalid receiver_id {}: {}", receiver_id, e))?;
+        }
+        Ok(())
+    }
+}
+
+/// Deletes keys with one `DeleteKey` batch, either the listed `public_keys` or the newest `count` keys of the keys file.
+/// Keys leave the signer before the transaction is sent, so nothing new is signed with them.
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct RemoveSignerKeysPayload {
+    #[serde(default)]
+    pub public_keys: Vec<String>,
+    #[serde(default)]
+    pub count: Option<usize>,
+}
+
+impl RemoveSignerKeysPayload {
+    pub fn validate(&self) -> core::result::Result<(), String> {
+        match (self.public_keys.is_empty(), self.count) {
+            (false, None) => validate_batch_size(self.public_keys.len()),
+            (true, Some(count)) => validate_batch_size(count),
+            _ => Err("set either public_keys or count".to_string()),
+        }
+    }
+}
+
+fn validate_batch_size(count: usize) -> core::result::Result<(), String> {
+    if count == 0 || count > MAX_SIGNER_KEYS_PER_BATCH {
+        return Err(format!("count must be between 1 and {}: {}", MAX_SIGNER_KEYS_PER_BATCH, count));
+    }
+    Ok(())
+}
@@ -4,7 +4,12 @@ use utoipa::ToSchema;
 use uuid::Uuid;
 use crate::adapter::output::persistence::db::schema::network;
 use chrono::NaiveDateTime;
-#[derive(Debug, Clone, Queryable, Identifiable)]
+
+pub const NEAR_NETWORK_CODE: &str = "NEAR";
+pub const MAX_NETWORK_CODE_LEN: usize = 20;
+pub const MAX_NETWORK_NAME_LEN: usize = 50;
+
+#[derive(Debug, Clone, Serialize, Queryable, Identifiable)]
 #[diesel(table_name = network)]
 pub struct Network {
     pub id: Uuid,
@@ -12,6 +17,7 @@ pub struct Network {
     pub code: String,
     pub created_date: NaiveDateTime,
     pub updated_date: NaiveDateTime,
+    pub is_active: bool,
 }
 
 #[derive(Debug, Clone, Insertable)]
@@ -22,17 +28,55 @@ pub struct NewNetwork {
     pub code: String,
 }
 
-#[derive(Deserialize, Clone)]
+#[derive(Deserialize, Clone, ToSchema)]
 pub struct NewNetworkPayload {
     pub name: String,
+    /// upper case, ex) NEAR
     pub code: String,
 }
 
+impl NewNetworkPayload {
+    pub fn validate(&self) -> core::result::Result<(), String> {
+        validate_network_name(&self.name)?;
+        validate_network_code(&self.code)
+    }
+}
+
+/// `code` is not updatable, contract addresses are validated by it.
+#[derive(Deserialize, Clone, ToSchema)]
+pub struct UpdateNetworkPayload {
+    pub name: String,
+}
+
+impl UpdateNetworkPayload {
+    pub fn validate(&self) -> core::result::Result<(), String> {
+        validate_network_name(&self.name)
+    }
+}
+
+fn validate_network_name(name: &str) -> core::result::Result<(), String> {
+    if name.trim().is_empty() || name.chars().count() > MAX_NETWORK_NAME_LEN {
+        return Err(format!("name must be 1 to {} characters", MAX_NETWORK_NAME_LEN));
+    }
+    Ok(())
+}
+
+fn validate_network_code(code: &str) -> core::result::Result<(), String> {
+    let is_valid = !code.is_empty()
+        && code.len() <= MAX_NETWORK_CODE_LEN
+        && code.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_' || c == '-');
+    if !is_valid {
+        return Err(format!("code must be 1 to {} upper case letters, digits, '_' or '-': {}", MAX_NETWORK_CODE_LEN, code));
+    }
+    Ok(())
+}
+
 #[derive(Serialize, ToSchema)]
 pub struct NetworkResponse {
     pub id: String,
     pub name: String,
     pub symbol: String,
+    pub is_active: bool,
     pub created_date: i64, 
     pub updated_date: i64, 
 }
@@ -43,6 +87,7 @@ impl From<Network> for NetworkResponse {
             id: network.id.to_string(),
             name: network.name,
             symbol: network.code,
+            is_active: network.is_active,
             created_date: network.created_date.and_utc().timestamp(),
             updated_date: network.updated_date.and_utc().timestamp(),
         }
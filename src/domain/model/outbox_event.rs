@@ -0,0 +1,138 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel_derive_enum::DbEnum;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+use crate::adapter::output::persistence::db::schema::outbox_event;
+use super::reward_claim::{RewardClaim, RewardClaimResponse, RewardClaimStatus};
+
+pub const AGGREGATE_REWARD_CLAIM: &str = "REWARD_CLAIM";
+
+#[derive(Clone, Debug, Serialize, Deserialize, DbEnum, PartialEq)]
+#[ExistingTypePath = "crate::adapter::output::persistence::db::schema::sql_types::OutboxEventStatus"]
+pub enum OutboxEventStatus {
+    #[db_rename = "PENDING"]
+    Pending,
+    #[db_rename = "DELIVERED"]
+    Delivered,
+    #[db_rename = "DEAD_LETTER"]
+    DeadLetter,
+}
+
+impl core::fmt::Display for OutboxEventStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutboxEventStatus::Pending => write!(f, "PENDING"),
+            OutboxEventStatus::Delivered => write!(f, "DELIVERED"),
+            OutboxEventStatus::DeadLetter => write!(f, "DEAD_LETTER"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum OutboxEventType {
+    ClaimCreated,
+    ClaimApproved,
+    ClaimFailed,
+    ClaimRetried,
+    ClaimCancelled,
+}
+
+impl OutboxEventType {
+    /// Event emitted when a reward claim moves from `prev` to `next`.
+    /// `prev` is `None` when the claim has just been inserted.
+    pub fn from_claim_transition(prev: Option<&RewardClaimStatus>, next: &RewardClaimStatus) -> Self {
+        match (prev, next) {
+            (None, _) => OutboxEventType::ClaimCreated,
+            (Some(RewardClaimStatus::TransactionFailed), RewardClaimStatus::Ready) => OutboxEventType::ClaimRetried,
+            (_, RewardClaimStatus::Ready) => OutboxEventType::ClaimCreated,
+            (_, RewardClaimStatus::TransactionApproved) => OutboxEventType::ClaimApproved,
+            (_, RewardClaimStatus::TransactionFailed) => OutboxEventType::ClaimFailed,
+            (_, RewardClaimStatus::Cancelled) => OutboxEventType::ClaimCancelled,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutboxEventType::ClaimCreated => "claim.created",
+            OutboxEventType::ClaimApproved => "claim.approved",
+            OutboxEventType::ClaimFailed => "claim.failed",
+            OutboxEventType::ClaimRetried => "claim.retried",
+            OutboxEventType::ClaimCancelled => "claim.cancelled",
+        }
+    }
+}
+
+impl core::fmt::Display for OutboxEventType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Identifiable, Selectable)]
+#[diesel(table_name = outbox_event)]
+pub struct OutboxEvent {
+    pub id: Uuid,
+    pub aggregate_type: String,
+    pub aggregate_id: Uuid,
+    pub event_type: String,
+    pub payload: Value,
+    pub status: OutboxEventStatus,
+    pub attempts: i32,
+    pub next_attempt_date: NaiveDateTime,
+    pub last_error: Option<String>,
+    pub delivered_date: Option<NaiveDateTime>,
+    pub created_date: NaiveDateTime,
+    pub updated_date: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Insertable)]
+#[diesel(table_name = outbox_event)]
+pub struct NewOutboxEvent {
+    pub id: Uuid,
+    pub aggregate_type: String,
+    pub aggregate_id: Uuid,
+    pub event_type: String,
+    pub payload: Value,
+}
+
+impl NewOutboxEvent {
+    pub fn from_reward_claim(event_type: OutboxEventType, claim: &RewardClaim) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            aggregate_type: AGGREGATE_REWARD_CLAIM.to_string(),
+            aggregate_id: claim.id,
+            event_type: event_type.to_string(),
+            payload: serde_json::to_value(RewardClaimResponse::from(claim.clone())).unwrap_or(Value::Null),
+        }
+    }
+}
+
+// region: --- Message
+
+/// What consumers receive. `id` is stable across redeliveries so consumers can deduplicate.
+#[derive(Debug, Clone, Serialize)]
+pub struct OutboxEventMessage {
+    pub id: String,
+    pub event_type: String,
+    pub aggregate_type: String,
+    pub aggregate_id: String,
+    pub occurred_at: i64,
+    pub data: Value,
+}
+
+impl From<OutboxEvent> for OutboxEventMessage {
+    fn from(event: OutboxEvent) -> Self {
+        Self {
+            id: event.id.to_string(),
+            event_type: event.event_type,
+            aggregate_type: event.aggregate_type,
+            aggregate_id: event.aggregate_id.to_string(),
+            occurred_at: event.created_date.and_utc().timestamp(),
+            data: event.payload,
+        }
+    }
+}
+
+// endregion: --- Message
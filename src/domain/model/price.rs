@@ -0,0 +1,68 @@
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use crate::adapter::output::persistence::db::schema::reward_claim;
+
+pub const FIAT_USD: &str = "usd";
+pub const FIAT_KRW: &str = "krw";
+
+/// Price of one whole token (not the smallest unit) in fiat.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FiatRates {
+    pub usd: BigDecimal,
+    pub krw: BigDecimal,
+    /// name of the feed that produced the rates, stored with the snapshot
+    pub source: String,
+}
+
+/// Fiat value of a claim, snapshotted when the payout is approved.
+#[derive(Debug, Clone, AsChangeset)]
+#[diesel(table_name = reward_claim)]
+pub struct RewardClaimValuation {
+    pub usd_rate: BigDecimal,
+    pub krw_rate: BigDecimal,
+    pub usd_value: BigDecimal,
+    pub krw_value: BigDecimal,
+    pub price_source: String,
+    pub priced_date: NaiveDateTime,
+}
+
+impl RewardClaimValuation {
+    /// `amount_in_smallest_unit` is scaled down by `decimals` before it is priced.
+    pub fn new(amount_in_smallest_unit: &BigDecimal, decimals: i32, rates: FiatRates) -> Self {
+        let (digits, scale) = amount_in_smallest_unit.as_bigint_and_exponent();
+        let amount = BigDecimal::new(digits, scale + i64::from(decimals));
+        Self {
+            usd_value: (&amount * &rates.usd).normalized(),
+            krw_value: (&amount * &rates.krw).normalized(),
+            usd_rate: rates.usd,
+            krw_rate: rates.krw,
+            price_source: rates.source,
+            priced_date: chrono::Utc::now().naive_utc(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use super::*;
+
+    #[test]
+    fn test_valuation_scales_by_decimals() {
+        let rates = FiatRates {
+            usd: BigDecimal::from_str("5.2").unwrap(),
+            krw: BigDecimal::from(7000),
+            source: "static".to_string(),
+        };
+        let valuation = RewardClaimValuation::new(&BigDecimal::from(1_500_000), 6, rates);
+        assert_eq!(valuation.usd_value, BigDecimal::from_str("7.8").unwrap());
+        assert_eq!(valuation.krw_value, BigDecimal::from(10500));
+        assert_eq!(valuation.price_source, "static");
+
+        let rates = FiatRates { usd: BigDecimal::from(1), krw: BigDecimal::from(1300), source: "static".to_string() };
+        let valuation = RewardClaimValuation::new(&BigDecimal::from_str("2.5e24").unwrap(), 24, rates);
+        assert_eq!(valuation.usd_value, BigDecimal::from_str("2.5").unwrap());
+        assert_eq!(valuation.krw_value, BigDecimal::from(3250));
+    }
+}
@@ -0,0 +1,49 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::adapter::output::persistence::db::schema::rate_limit_bucket;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RateLimitDecision {
+    Allowed,
+    Limited { retry_after_secs: u64 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct TokenBucketConfig {
+    /// burst size
+    pub capacity: f64,
+    /// tokens added back per second
+    pub refill_per_sec: f64,
+}
+
+impl TokenBucketConfig {
+    /// Seconds an empty bucket takes to fill up again, `None` when it never refills.
+    pub fn refill_secs(&self) -> Option<f64> {
+        (self.refill_per_sec > 0.0).then(|| self.capacity / self.refill_per_sec)
+    }
+}
+
+/// Refills `tokens` for `elapsed_secs` and takes one token if there is one.
+/// Returns the remaining tokens and whether the request may pass.
+pub fn take_token(tokens: f64, elapsed_secs: f64, bucket: &TokenBucketConfig) -> (f64, RateLimitDecision) {
+    let refilled = (tokens + elapsed_secs.max(0.0) * bucket.refill_per_sec).min(bucket.capacity);
+    if refilled >= 1.0 {
+        return (refilled - 1.0, RateLimitDecision::Allowed);
+    }
+
+    let retry_after_secs = if bucket.refill_per_sec > 0.0 {
+        ((1.0 - refilled) / bucket.refill_per_sec).ceil().max(1.0) as u64
+    } else {
+        u64::MAX
+    };
+    (refilled, RateLimitDecision::Limited { retry_after_secs })
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Selectable, Insertable)]
+#[diesel(table_name = rate_limit_bucket)]
+pub struct RateLimitBucket {
+    pub bucket_key: String,
+    pub tokens: f64,
+    pub updated_date: NaiveDateTime,
+}
@@ -10,8 +10,12 @@ use super::coin_network::CoinNetworkDetailsResponse;
 use super::network::Network;
 use super::reward_claim_detail::{RewardClaimDetail, RewardClaimDetailResponse};
 use crate::domain::model::coin_network::CoinNetwork;
+use crate::domain::model::near::OnChainTransactionStatus;
 use crate::adapter::output::persistence::db::schema::reward_claim;
 
+/// A READY claim untouched for this long lost its transfer, e.g. the server stopped mid payout.
+pub const STALE_READY_CLAIM_MINUTES: i64 = 10;
+
 #[derive(Clone, Debug, Serialize, Deserialize, DbEnum)]
 #[ExistingTypePath = "crate::adapter::output::persistence::db::schema::sql_types::RewardClaimStatus"]
 pub enum RewardClaimStatus {
@@ -21,6 +25,9 @@ pub enum RewardClaimStatus {
     TransactionApproved,
     #[db_rename = "TRANSACTION_FAILED"]
     TransactionFailed,
+    /// closed by an operator, the resource can not be claimed again
+    #[db_rename = "CANCELLED"]
+    Cancelled,
 }
 
 impl From<String> for RewardClaimStatus {
@@ -29,6 +36,7 @@ impl From<String> for RewardClaimStatus {
             "READY" => RewardClaimStatus::Ready,
             "TRANSACTION_APPROVED" => RewardClaimStatus::TransactionApproved,
             "TRANSACTION_FAILED" => RewardClaimStatus::TransactionFailed,
+            "CANCELLED" => RewardClaimStatus::Cancelled,
             _ => RewardClaimStatus::Ready,
         }
     }
@@ -40,6 +48,7 @@ impl PartialEq for RewardClaimStatus {
             (RewardClaimStatus::Ready, RewardClaimStatus::Ready) => true,
             (RewardClaimStatus::TransactionApproved, RewardClaimStatus::TransactionApproved) => true,
             (RewardClaimStatus::TransactionFailed, RewardClaimStatus::TransactionFailed) => true,
+            (RewardClaimStatus::Cancelled, RewardClaimStatus::Cancelled) => true,
             _ => false,
         }
     }
@@ -51,6 +60,7 @@ impl core::fmt::Display for RewardClaimStatus {
             RewardClaimStatus::Ready => write!(f, "READY"),
             RewardClaimStatus::TransactionApproved => write!(f, "TRANSACTION_APPROVED"),
             RewardClaimStatus::TransactionFailed => write!(f, "TRANSACTION_FAILED"),
+            RewardClaimStatus::Cancelled => write!(f, "CANCELLED"),
         }
     }
 }
@@ -109,6 +119,12 @@ pub struct RewardClaim {
     pub user_address: String,
     pub created_date: NaiveDateTime,
     pub updated_date: NaiveDateTime,
+    pub usd_rate: Option<BigDecimal>,
+    pub krw_rate: Option<BigDecimal>,
+    pub usd_value: Option<BigDecimal>,
+    pub krw_value: Option<BigDecimal>,
+    pub price_source: Option<String>,
+    pub priced_date: Option<NaiveDateTime>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Insertable)]
@@ -131,6 +147,14 @@ pub struct UpdateRewardClaimStatus {
     pub updated_date: NaiveDateTime,
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct RewardClaimFilter {
+    pub status: Option<RewardClaimStatus>,
+    pub user_id: Option<Uuid>,
+    pub updated_after: Option<NaiveDateTime>,
+    pub limit: i64,
+}
+
 #[derive(Deserialize, Clone, ToSchema)]
 pub struct NewRewardClaimPayload {
     #[schema(value_type = String)]
@@ -154,6 +178,13 @@ pub struct CombinedRewardClaimResponse {
     user_address: String,
     reward_claim_status: String,
     detail: RewardClaimDetailResponse,
+    /// fiat price of one token and value of the reward at payout time, empty when no price was available
+    usd_rate: Option<String>,
+    krw_rate: Option<String>,
+    usd_value: Option<String>,
+    krw_value: Option<String>,
+    price_source: Option<String>,
+    priced_date: Option<i64>,
     created_date: i64,
     updated_date: i64,
 }
@@ -170,6 +201,12 @@ impl From<(RewardClaim, RewardClaimDetail, CoinNetwork, Coin, Network)> for Comb
             user_address: claim.user_address,
             reward_claim_status: claim.reward_claim_status.to_string(),
             detail: RewardClaimDetailResponse::from(detail),
+            usd_rate: claim.usd_rate.map(|rate| rate.to_string()),
+            krw_rate: claim.krw_rate.map(|rate| rate.to_string()),
+            usd_value: claim.usd_value.map(|value| value.to_string()),
+            krw_value: claim.krw_value.map(|value| value.to_string()),
+            price_source: claim.price_source,
+            priced_date: claim.priced_date.map(|date| date.and_utc().timestamp()),
             created_date: claim.created_date.and_utc().timestamp(),
             updated_date: claim.updated_date.and_utc().timestamp(),
         }
@@ -186,6 +223,9 @@ pub struct RewardClaimResponse {
     user_id: String,
     user_address: String,
     reward_claim_status: String,
+    usd_value: Option<String>,
+    krw_value: Option<String>,
+    price_source: Option<String>,
     created_date: i64,
     updated_date: i64,
 }
@@ -201,9 +241,74 @@ impl From<RewardClaim> for RewardClaimResponse {
             user_id: claim.user_id.to_string(),
             user_address: claim.user_address,
             reward_claim_status: claim.reward_claim_status.to_string(),
+            usd_value: claim.usd_value.map(|value| value.to_string()),
+            krw_value: claim.krw_value.map(|value| value.to_string()),
+            price_source: claim.price_source,
             created_date: claim.created_date.and_utc().timestamp(),
             updated_date: claim.updated_date.and_utc().timestamp(),
         }
     }
 }
 
+/// A claim with every transfer attempt, newest first. For operators.
+#[derive(Serialize)]
+pub struct RewardClaimAdminResponse {
+    #[serde(flatten)]
+    claim: RewardClaimResponse,
+    coin_network: Option<CoinNetworkDetailsResponse>,
+    details: Vec<RewardClaimDetailResponse>,
+}
+
+impl From<(RewardClaim, Vec<RewardClaimDetail>, Option<(CoinNetwork, Coin, Network)>)> for RewardClaimAdminResponse {
+    fn from((claim, details, coin_network): (RewardClaim, Vec<RewardClaimDetail>, Option<(CoinNetwork, Coin, Network)>)) -> Self {
+        Self {
+            claim: RewardClaimResponse::from(claim),
+            coin_network: coin_network.map(CoinNetworkDetailsResponse::from),
+            details: details.into_iter().map(RewardClaimDetailResponse::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ReconcileIssue {
+    /// approved in the db, but the chain reports the transaction failed or unknown
+    ApprovedNotOnChain,
+    /// approved in the db without any transaction hash
+    ApprovedWithoutTransaction,
+    /// failed in the db, but the transaction succeeded on chain, retrying would pay twice
+    FailedButSucceeded,
+    StaleReady,
+    /// the rpc node could not answer, check again later
+    Unchecked,
+}
+
+/// A claim whose stored status disagrees with the chain. Reported only, nothing is changed.
+#[derive(Debug, Serialize)]
+pub struct ReconcileEntry {
+    pub reward_claim_id: String,
+    pub reward_claim_status: String,
+    pub user_id: String,
+    pub amount: String,
+    pub transaction_hash: Option<String>,
+    pub on_chain_status: Option<OnChainTransactionStatus>,
+    pub issue: ReconcileIssue,
+    pub message: Option<String>,
+    pub updated_date: i64,
+}
+
+impl ReconcileEntry {
+    pub fn new(claim: &RewardClaim, transaction_hash: Option<String>, on_chain_status: Option<OnChainTransactionStatus>, issue: ReconcileIssue, message: Option<String>) -> Self {
+        Self {
+            reward_claim_id: claim.id.to_string(),
+            reward_claim_status: claim.reward_claim_status.to_string(),
+            user_id: claim.user_id.to_string(),
+            amount: claim.amount.to_string(),
+            transaction_hash,
+            on_chain_status,
+            issue,
+            message,
+            updated_date: claim.updated_date.and_utc().timestamp(),
+        }
+    }
+}
@@ -45,4 +45,17 @@ pub struct NewUserPayload {
     pub nick: String,
     pub self_intro: String,
     pub phn_nmb: String,
-}
\ No newline at end of file
+}
+/// Identity proven by a `TokenVerifier`. Field names follow the `x-user-right` header
+/// so the same flags can be issued as JWT claims or returned by session introspection.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AuthClaims {
+    #[serde(alias = "id")]
+    pub sub: String,
+    #[serde(default)]
+    pub adm: bool,
+    #[serde(default)]
+    pub prv: bool,
+    #[serde(default)]
+    pub crt: bool,
+}
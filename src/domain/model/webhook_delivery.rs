@@ -0,0 +1,66 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use crate::adapter::output::persistence::db::schema::webhook_delivery;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Identifiable, Selectable)]
+#[diesel(table_name = webhook_delivery)]
+pub struct WebhookDelivery {
+    pub id: Uuid,
+    pub event_id: Uuid,
+    pub endpoint_name: String,
+    pub endpoint_url: String,
+    pub attempt: i32,
+    pub success: bool,
+    pub status_code: Option<i32>,
+    pub error: Option<String>,
+    pub duration_ms: i32,
+    pub created_date: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Insertable)]
+#[diesel(table_name = webhook_delivery)]
+pub struct NewWebhookDelivery {
+    pub id: Uuid,
+    pub event_id: Uuid,
+    pub endpoint_name: String,
+    pub endpoint_url: String,
+    pub attempt: i32,
+    pub success: bool,
+    pub status_code: Option<i32>,
+    pub error: Option<String>,
+    pub duration_ms: i32,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct WebhookDeliveryResponse {
+    pub id: String,
+    pub event_id: String,
+    pub endpoint_name: String,
+    pub endpoint_url: String,
+    pub attempt: i32,
+    pub success: bool,
+    pub status_code: Option<i32>,
+    pub error: Option<String>,
+    pub duration_ms: i32,
+    pub created_date: i64,
+}
+
+impl From<WebhookDelivery> for WebhookDeliveryResponse {
+    fn from(delivery: WebhookDelivery) -> Self {
+        WebhookDeliveryResponse {
+            id: delivery.id.to_string(),
+            event_id: delivery.event_id.to_string(),
+            endpoint_name: delivery.endpoint_name,
+            endpoint_url: delivery.endpoint_url,
+            attempt: delivery.attempt,
+            success: delivery.success,
+            status_code: delivery.status_code,
+            error: delivery.error,
+            duration_ms: delivery.duration_ms,
+            created_date: delivery.created_date.and_utc().timestamp(),
+        }
+    }
+}
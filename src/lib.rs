@@ -0,0 +1,8 @@
+pub mod adapter;
+pub mod port;
+pub mod domain;
+pub mod config;
+pub mod usecase;
+pub mod state;
+
+pub use self::adapter::input::error::Result;
@@ -1,46 +1,114 @@
-// #![allow(unused)] // For beginning only.
-
-mod adapter;
-mod port;
-mod domain;
-mod config;
-mod usecase;
-mod state; 
-
 use std::net::IpAddr;
 use std::str::FromStr;
 use std::{net::SocketAddr, path::PathBuf};
 use std::sync::Arc;
-use adapter::input::web::middleware::permission;
+use std::time::Instant;
+use payment::adapter::input::web::middleware::permission;
+use payment::adapter::input::web::middleware::rate_limit::{rate_limit, RateLimitGroup};
 use axum::{middleware, Router};
 use axum_server::tls_rustls::RustlsConfig;
-use config::log::request_logging_middleware;
-use config::swagger::ApiDoc;
-use state::AppState;
+use axum_server::Handle;
+use payment::config::log::{request_logging_middleware, shutdown_tracing};
+use payment::config::swagger::ApiDoc;
+use payment::state::AppState;
+use payment::domain::model::audit_log::AuditActor;
+use payment::adapter::output::persistence::db::migration;
 use tower_cookies::CookieManagerLayer;
 use utoipa::OpenApi;
+use payment::usecase::outbox_dispatcher::OutboxDispatcher;
+use payment::usecase::signer_key_watcher::SignerKeyWatcher;
 use utoipa_swagger_ui::SwaggerUi;
-use crate::{
+use payment::{
     adapter::input::{
+        command,
         routes_static, 
-        web::{self, middleware::{auth, response}, routes_hello}
+        web::{self, middleware::{auth, metrics, request_id, response}, routes_hello}
     }, 
     config::config
 };
-pub use self::adapter::input::error::Result;
+use payment::Result;
 
 #[tokio::main]
 async fn main() -> Result<()>{    
     let config = config().await;
+
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(migrate_args) = command::migrate_args(&args) {
+        return command::run_migrate_command(config, migrate_args).await;
+    }
+
     let app_state = Arc::new(AppState::new(&config).await?);
+
+    if config.db_migrate_on_start() {
+        let versions = migration::run_pending_migrations(app_state.db_manager.as_ref()).await?;
+        tracing::info!("migrations applied on start: {:?}", versions);
+    }
+
+    {
+        // --- flags coins whose decimals or symbol disagree with their FT contract, payouts in them are refused
+        let app_state = Arc::clone(&app_state);
+        tokio::spawn(async move {
+            match app_state.coin_admin_usecase.check_ft_metadata(AuditActor::system()).await {
+                Ok(checks) => tracing::info!(
+                    "ft_metadata check done: {} checked, {} mismatched, {} failed",
+                    checks.len(),
+                    checks.iter().filter(|check| check.metadata_mismatch.is_some()).count(),
+                    checks.iter().filter(|check| check.error.is_some()).count(),
+                ),
+                Err(e) => tracing::error!("ft_metadata check failed: {:?}", e),
+            }
+        });
+    }
+
+    if config.outbox().enabled {
+        let outbox_dispatcher = Arc::new(OutboxDispatcher::new(
+            Arc::clone(&app_state.db_manager),
+            Arc::clone(&app_state.outbox_event_repo),
+            Arc::clone(&app_state.webhook_delivery_repo),
+            app_state.event_consumers.clone(),
+            config.outbox().clone(),
+        ));
+        tokio::spawn(outbox_dispatcher.run());
+        tracing::info!("outbox dispatcher enabled ({} consumers)", config.outbox().consumers.len());
+    }
     
+    let keys_reload_poll_secs = config.near_network_config().keys_reload_poll_secs;
+    if keys_reload_poll_secs > 0 {
+        let signer_key_watcher = SignerKeyWatcher::new(
+            Arc::clone(&app_state.signer_key_usecase),
+            config.near_network_config().keys_filename().to_path_buf(),
+            std::time::Duration::from_secs(keys_reload_poll_secs),
+        );
+        tokio::spawn(signer_key_watcher.run());
+        tracing::info!("signer keys file watcher enabled (every {}s)", keys_reload_poll_secs);
+    }
+    
+    let rpc_probe_interval_secs = config.near_network_config().rpc_probe_interval_secs;
+    if rpc_probe_interval_secs > 0 {
+        let endpoints = Arc::clone(&app_state.near_rpc_manager.endpoints);
+        tokio::spawn(endpoints.run_probes(std::time::Duration::from_secs(rpc_probe_interval_secs)));
+        tracing::info!("rpc endpoint probes enabled (every {}s)", rpc_probe_interval_secs);
+    }
+
+    if config.rate_limit().enabled {
+        tokio::spawn(Arc::clone(&app_state.rate_limiter).run_cleanup());
+    }
+
     let mut routes_all = Router::new()
-        .merge(routes_hello::routes());
+        .merge(routes_hello::routes())
+        .merge(web::routes_health::routes(Arc::clone(&app_state)));
     let routes_auth_apis = web::routes_network::routes(Arc::clone(&app_state))
         .merge(web::routes_reward_claim::routes(Arc::clone(&app_state)))
         .merge(web::routes_coin::routes(Arc::clone(&app_state)))
         .merge(web::routes_coin_network::routes(Arc::clone(&app_state)))
-        .route_layer(middleware::from_fn(permission::mw_require_auth));
+        .merge(web::routes_admin_webhook::routes(Arc::clone(&app_state)))
+        .merge(web::routes_admin_audit_log::routes(Arc::clone(&app_state)))
+        .merge(web::routes_admin_api_key::routes(Arc::clone(&app_state)))
+        .merge(web::routes_admin_coin::routes(Arc::clone(&app_state)))
+        .merge(web::routes_admin_signer_key::routes(Arc::clone(&app_state)))
+        .route_layer(middleware::from_fn(permission::mw_require_auth))
+        // --- outside auth, so requests guessing keys or tokens are limited per ip before their 401
+        .route_layer(rate_limit(&app_state.rate_limiter, RateLimitGroup::Api));
 
     if config.is_local() {
         tracing::info!("dev routes enabled");
@@ -54,6 +122,7 @@ async fn main() -> Result<()>{
 
     routes_all = routes_all
         .nest("/api", routes_auth_apis)
+        .route_layer(middleware::from_fn(metrics::mw_track_metrics))
         .layer(middleware::map_response(response::mapper))
         .layer(request_logging_middleware())
         .layer(middleware::from_fn_with_state(
@@ -61,16 +130,48 @@ async fn main() -> Result<()>{
             auth::mw_ctx_resolver,
         ))
         .layer(CookieManagerLayer::new())
+        .layer(middleware::from_fn(request_id::mw_request_id))
         .fallback_service(routes_static());
 
 
+    if config.metrics().enabled {
+        let metrics_ip_addr = IpAddr::from_str(&config.metrics().host).unwrap();
+        let metrics_addr = SocketAddr::from((metrics_ip_addr, config.metrics().port));
+        let routes_metrics = web::routes_metrics::routes(Arc::clone(&app_state));
+        tokio::spawn(async move {
+            axum_server::bind(metrics_addr)
+                .serve(routes_metrics.into_make_service())
+                .await
+                .unwrap();
+        });
+        tracing::info!("metrics listening on {}", metrics_addr);
+    }
+
     let ip_addr = IpAddr::from_str(config.server_host()).unwrap();
     let addr = SocketAddr::from((ip_addr, config.server_port()));
     tracing::info!("listening on {}", addr);
 
+    let drain_timeout = config.shutdown().drain_timeout();
+    let handle = Handle::new();
+    // --- one drain window from the signal, shared by the open requests and the payouts after them
+    let (deadline_tx, deadline_rx) = tokio::sync::oneshot::channel::<Instant>();
+    {
+        // --- new claims are refused first, then the listener stops and open requests get the window to finish
+        let handle = handle.clone();
+        let payout_gate = Arc::clone(&app_state.payout_gate);
+        tokio::spawn(async move {
+            shutdown_signal().await;
+            let _ = deadline_tx.send(Instant::now() + drain_timeout);
+            tracing::info!("shutdown signal received, draining for up to {:?}", drain_timeout);
+            payout_gate.close();
+            handle.graceful_shutdown(Some(drain_timeout));
+        });
+    }
+
     if config.is_local() || !config.server_use_tls() {
         axum_server::bind(addr)
-        .serve(routes_all.into_make_service())
+        .handle(handle)
+        .serve(routes_all.into_make_service_with_connect_info::<SocketAddr>())
         .await
         .unwrap();
     } else {
@@ -86,10 +187,44 @@ async fn main() -> Result<()>{
         tracing::info!("TLS file loaded");
 
         axum_server::bind_rustls(addr, tls_config)
-        .serve(routes_all.into_make_service())
+        .handle(handle)
+        .serve(routes_all.into_make_service_with_connect_info::<SocketAddr>())
         .await
         .unwrap();
     }
 
+    // --- payouts run detached from their requests, a forced close of the connections leaves them running
+    let deadline = deadline_rx.await.unwrap_or_else(|_| Instant::now());
+    if !app_state.payout_gate.drained(deadline.saturating_duration_since(Instant::now())).await {
+        tracing::error!(
+            "{} payouts still in flight at shutdown, their claims may be left READY, check them with `payment-admin reconcile`",
+            app_state.payout_gate.in_flight(),
+        );
+    }
+    tracing::info!("shutdown complete");
+    shutdown_tracing().await;
+
     Ok(())
+}
+
+/// SIGTERM, sent by docker stop and deploys, or ctrl-c.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c().await.expect("Failed to install the ctrl-c handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to install the SIGTERM handler")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
 }
\ No newline at end of file
@@ -0,0 +1,18 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use deadpool_diesel::postgres::Object;
+use uuid::Uuid;
+use crate::domain::model::{api_key::{ApiKey, NewApiKey}, audit_log::AuditActor};
+use crate::adapter::output::persistence::db::error::Result;
+
+#[async_trait]
+pub trait ApiKeyRepository {
+    async fn insert(&self, conn: Object, new_api_key: NewApiKey, actor: AuditActor) -> Result<ApiKey>;
+    async fn get(&self, conn: Object, api_key_id: Uuid) -> Result<ApiKey>;
+    async fn get_by_prefix(&self, conn: Object, key_prefix: String) -> Result<ApiKey>;
+    async fn list(&self, conn: Object) -> Result<Vec<ApiKey>>;
+    /// Inserts the replacement key and lets the old one expire at `old_key_expires_date`, in one transaction.
+    async fn rotate(&self, conn: Object, api_key_id: Uuid, new_api_key: NewApiKey, old_key_expires_date: NaiveDateTime, actor: AuditActor) -> Result<ApiKey>;
+    async fn revoke(&self, conn: Object, api_key_id: Uuid, actor: AuditActor) -> Result<ApiKey>;
+    async fn touch_last_used(&self, conn: Object, api_key_id: Uuid) -> Result<()>;
+}
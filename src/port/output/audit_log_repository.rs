@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+use deadpool_diesel::postgres::Object;
+use crate::domain::model::audit_log::{AuditLog, AuditLogFilter, NewAuditLog};
+use crate::adapter::output::persistence::db::error::Result;
+
+/// `audit_log` is append-only, there is intentionally no update or delete.
+/// Repositories that change audited entities write their rows in the same transaction
+/// (see `insert_audit_log`); `insert` is for actions that have no owning transaction.
+#[async_trait]
+pub trait AuditLogRepository {
+    async fn insert(&self, conn: Object, new_audit_log: NewAuditLog) -> Result<AuditLog>;
+    async fn list(&self, conn: Object, filter: AuditLogFilter) -> Result<Vec<AuditLog>>;
+}
@@ -1,14 +1,23 @@
 use axum::async_trait;
 use uuid::Uuid;
 use deadpool_diesel::postgres::Object;
-use crate::domain::model::{coin::Coin, coin_network::CoinNetwork, network::Network};
+use crate::domain::model::audit_log::AuditActor;
+use crate::domain::model::{coin::Coin, coin_network::{CoinNetwork, NewCoinNetwork}, network::Network};
 use crate::adapter::output::persistence::db::error::Result;
 
 #[async_trait]
 pub trait CoinNetworkRepository {
     async fn get_with_coin_and_network(&self, conn: Object, coin_network_id: Uuid) -> Result<(CoinNetwork, Coin, Network)>;
-    async fn list_all(&self, conn: Object) -> Result<Vec<(CoinNetwork, Coin, Network)>>;
-    async fn list_all_by_network_code(&self, conn: Object, network_code: String) -> Result<Vec<(CoinNetwork, Coin, Network)>>;
+    /// Disabled and deprecated coin networks, or ones whose coin or network is disabled, are left out unless `include_inactive`.
+    async fn list_all(&self, conn: Object, include_inactive: bool) -> Result<Vec<(CoinNetwork, Coin, Network)>>;
+    async fn list_all_by_network_code(&self, conn: Object, network_code: String, include_inactive: bool) -> Result<Vec<(CoinNetwork, Coin, Network)>>;
     async fn list_all_by_ids(&self, conn: Object, coin_network_ids: Vec<Uuid>) -> Result<Vec<(CoinNetwork, Coin, Network)>>;
     async fn list_by_coin_id(&self, conn: Object, coin_id: Uuid) -> Result<Vec<CoinNetwork>>;
+    async fn create(&self, conn: Object, new_coin_network: NewCoinNetwork, actor: AuditActor) -> Result<CoinNetwork>;
+    async fn update_contract_address(&self, conn: Object, coin_network_id: Uuid, contract_address: Option<String>, actor: AuditActor) -> Result<CoinNetwork>;
+    async fn set_active(&self, conn: Object, coin_network_id: Uuid, is_active: bool, actor: AuditActor) -> Result<CoinNetwork>;
+    /// Soft delete, disables the coin network for good.
+    async fn deprecate(&self, conn: Object, coin_network_id: Uuid, actor: AuditActor) -> Result<CoinNetwork>;
+    /// Stores the `ft_metadata` comparison. Only a changed `metadata_mismatch` is audited.
+    async fn record_metadata_check(&self, conn: Object, coin_network_id: Uuid, metadata_mismatch: Option<String>, actor: AuditActor) -> Result<CoinNetwork>;
 }
@@ -1,14 +1,18 @@
 use axum::async_trait;
 use deadpool_diesel::postgres::Object;
 use uuid::Uuid;
-use crate::domain::model::coin::{Coin, NewCoinPayload};
+use crate::domain::model::audit_log::AuditActor;
+use crate::domain::model::coin::{Coin, CoinChangeset, NewCoin, NewCoinPayload};
 use crate::adapter::output::persistence::db::error::Result;
 
 #[async_trait]
 pub trait CoinRepository {
     async fn get(&self, conn: Object, coin_id: Uuid) -> Result<Coin>;
     async fn list(&self, conn: Object) -> Result<Vec<Coin>>;
+    async fn create(&self, conn: Object, new_coin: NewCoin, actor: AuditActor) -> Result<Coin>;
+    async fn update(&self, conn: Object, coin_id: Uuid, changeset: CoinChangeset, actor: AuditActor) -> Result<Coin>;
+    async fn set_active(&self, conn: Object, coin_id: Uuid, is_active: bool, actor: AuditActor) -> Result<Coin>;
 
     // --- test only
     async fn insert(&self, conn: Object, new_coin_payload: NewCoinPayload) -> Result<Coin>;
-}
\ No newline at end of file
+}
@@ -0,0 +1,12 @@
+use async_trait::async_trait;
+use crate::adapter::output::event::error::Result;
+use crate::domain::model::outbox_event::OutboxEventMessage;
+
+#[async_trait]
+pub trait EventConsumer: Send + Sync {
+    fn name(&self) -> &str;
+    fn endpoint(&self) -> &str;
+    fn accepts(&self, event_type: &str) -> bool;
+    /// Returns the response status code of a successful delivery.
+    async fn deliver(&self, message: &OutboxEventMessage) -> Result<u16>;
+}
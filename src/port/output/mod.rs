@@ -7,6 +7,14 @@ pub mod reward_claim_repository;
 pub mod mission_submit_repository;
 pub mod rpc_client;
 pub mod detailed_posting_repository;
+pub mod outbox_event_repository;
+pub mod event_consumer;
+pub mod webhook_delivery_repository;
+pub mod audit_log_repository;
+pub mod token_verifier;
+pub mod api_key_repository;
+pub mod rate_limit_store;
+pub mod price_feed;
 
 pub use db_manager::DbManager;
 pub use user_repository::UserRepository;
@@ -2,7 +2,8 @@ use axum::async_trait;
 use uuid::Uuid;
 use deadpool_diesel::postgres::Object;
 use crate::adapter::output::persistence::db::error::Result;
-use crate::domain::model::network::{Network, NewNetworkPayload};
+use crate::domain::model::audit_log::AuditActor;
+use crate::domain::model::network::{Network, NewNetwork, NewNetworkPayload};
 
 #[async_trait]
 pub trait NetworkRepository {
@@ -10,4 +11,8 @@ pub trait NetworkRepository {
     async fn insert(&self, conn: Object, new_network_payload: NewNetworkPayload) -> Result<Network>;
     async fn get(&self, conn: Object, id: Uuid) -> Result<Network>;
     async fn list(&self, conn: Object) -> Result<Vec<Network>>;
+    async fn find_by_code(&self, conn: Object, code: String) -> Result<Option<Network>>;
+    async fn create(&self, conn: Object, new_network: NewNetwork, actor: AuditActor) -> Result<Network>;
+    async fn update_name(&self, conn: Object, id: Uuid, name: String, actor: AuditActor) -> Result<Network>;
+    async fn set_active(&self, conn: Object, id: Uuid, is_active: bool, actor: AuditActor) -> Result<Network>;
 }
@@ -0,0 +1,21 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use deadpool_diesel::postgres::Object;
+use uuid::Uuid;
+use crate::domain::model::{audit_log::AuditActor, outbox_event::OutboxEvent};
+use crate::adapter::output::persistence::db::error::Result;
+
+/// Events themselves are written by the owning repository inside the same transaction
+/// as the state change (see `PostgresRewardClaimRepository`), so there is no `insert` here.
+#[async_trait]
+pub trait OutboxEventRepository {
+    async fn get(&self, conn: Object, event_id: Uuid) -> Result<OutboxEvent>;
+    /// Locks up to `limit` due PENDING events and pushes their `next_attempt_date` by `lease_secs`
+    /// so that other dispatcher instances skip them while they are being delivered.
+    async fn claim_due(&self, conn: Object, limit: i64, lease_secs: i64) -> Result<Vec<OutboxEvent>>;
+    async fn mark_delivered(&self, conn: Object, event_id: Uuid) -> Result<OutboxEvent>;
+    async fn mark_retry(&self, conn: Object, event_id: Uuid, attempts: i32, next_attempt_date: NaiveDateTime, last_error: String) -> Result<OutboxEvent>;
+    async fn mark_dead_letter(&self, conn: Object, event_id: Uuid, attempts: i32, last_error: String) -> Result<OutboxEvent>;
+    /// Puts a DELIVERED or DEAD_LETTER event back to PENDING with a fresh attempt budget.
+    async fn requeue(&self, conn: Object, event_id: Uuid, actor: AuditActor) -> Result<OutboxEvent>;
+}
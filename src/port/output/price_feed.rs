@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+use crate::adapter::output::price::error::Result;
+use crate::domain::model::price::FiatRates;
+
+#[async_trait]
+pub trait PriceFeed: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// USD and KRW price of one whole `symbol` token.
+    async fn get_rates(&self, symbol: &str) -> Result<FiatRates>;
+}
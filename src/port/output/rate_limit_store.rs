@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+use crate::adapter::output::persistence::db::error::Result;
+use crate::domain::model::rate_limit::{RateLimitDecision, TokenBucketConfig};
+
+#[async_trait]
+pub trait RateLimitStore: Send + Sync {
+    /// Takes one token from the bucket stored under `key`, creating a full bucket on first use.
+    async fn try_acquire(&self, key: &str, bucket: &TokenBucketConfig) -> Result<RateLimitDecision>;
+
+    /// Drops the buckets untouched for `idle_secs`, long enough for them to be full again.
+    /// Returns how many were dropped.
+    async fn purge_idle(&self, idle_secs: u64) -> Result<usize>;
+}
@@ -1,13 +1,14 @@
 use async_trait::async_trait;
 use deadpool_diesel::postgres::Object;
 use uuid::Uuid;
-use crate::domain::model::{reward_claim::{NewRewardClaim, ResourceType, RewardClaim, RewardClaimStatus}, reward_claim_detail::{NewRewardClaimDetail, RewardClaimDetail}};
+use crate::domain::model::{audit_log::AuditActor, price::RewardClaimValuation, reward_claim::{NewRewardClaim, ResourceType, RewardClaim, RewardClaimFilter, RewardClaimStatus}, reward_claim_detail::{NewRewardClaimDetail, RewardClaimDetail}};
 use crate::adapter::output::persistence::db::error::Result;
 
 #[async_trait]
 pub trait RewardClaimRepository {
     // --- reward_claim domain
-    async fn insert(&self, conn: Object, new_reward_claim: NewRewardClaim) -> Result<RewardClaim>;
+    // insert and update_status append an `audit_log` row for `actor` in the same transaction
+    async fn insert(&self, conn: Object, new_reward_claim: NewRewardClaim, actor: AuditActor) -> Result<RewardClaim>;
     async fn get_by_resource_and_user(
         &self,
         conn: Object,
@@ -17,9 +18,18 @@ pub trait RewardClaimRepository {
     ) -> Result<RewardClaim>;
 
     async fn list_all_by_user(&self, conn: Object, user_id: Uuid) -> Result<Vec<(RewardClaim, RewardClaimDetail)>>;
+
+    async fn get(&self, conn: Object, reward_claim_id: Uuid) -> Result<RewardClaim>;
+    /// newest `updated_date` first
+    async fn list(&self, conn: Object, filter: RewardClaimFilter) -> Result<Vec<RewardClaim>>;
     
-    async fn update_status(&self, conn: Object, reward_claim_id: Uuid, status: RewardClaimStatus, retryable: bool) -> Result<RewardClaim>;
+    async fn update_status(&self, conn: Object, reward_claim_id: Uuid, status: RewardClaimStatus, retryable: bool, actor: AuditActor) -> Result<RewardClaim>;
+
+    /// Stores the fiat value of the claim at payout time, audited like update_status.
+    async fn update_valuation(&self, conn: Object, reward_claim_id: Uuid, valuation: RewardClaimValuation, actor: AuditActor) -> Result<RewardClaim>;
     
     // --- reward_claim_detail domain
     async fn insert_detail(&self, conn: Object, new_reward_claim: NewRewardClaimDetail) -> Result<RewardClaimDetail>;
+    /// every transfer attempt of a claim, newest first
+    async fn list_details(&self, conn: Object, reward_claim_id: Uuid) -> Result<Vec<RewardClaimDetail>>;
 }
@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 use near_primitives::{action::delegate::SignedDelegateAction, views::TxExecutionStatus};
-use crate::{adapter::output::near::error::Result, domain::model::near::{TransactionResultResponse, TransferActionType}};
+use crate::{adapter::output::near::error::Result, domain::model::near::{AccessKeyInfo, AccountBalance, FtMetadata, OnChainTransactionStatus, RpcNodeStatus, TransactionResultResponse, TransferActionType}};
 
 #[async_trait]
 pub trait RpcClient: Send + Sync{
@@ -12,6 +12,11 @@ pub trait RpcClient: Send + Sync{
         signed_delegate_action: &SignedDelegateAction,
         _wait_until: Option<TxExecutionStatus>,
     ) -> Result<TransactionResultResponse>;
-
-    
+    /// Calls the NEP-148 `ft_metadata` view method of `contract_id`.
+    async fn ft_metadata(&self, contract_id: &str) -> Result<FtMetadata>;
+    async fn node_status(&self) -> Result<RpcNodeStatus>;
+    async fn view_account(&self, account_id: &str) -> Result<AccountBalance>;
+    async fn view_access_key(&self, account_id: &str, public_key: &str) -> Result<AccessKeyInfo>;
+    /// Status of a transaction sent by the relayer account, `NotFound` when the rpc does not know the hash.
+    async fn transaction_status(&self, transaction_hash: &str) -> Result<OnChainTransactionStatus>;
 }
@@ -0,0 +1,9 @@
+use async_trait::async_trait;
+use crate::adapter::output::auth::error::Result;
+use crate::domain::model::user::AuthClaims;
+
+#[async_trait]
+pub trait TokenVerifier: Send + Sync {
+    /// Verifies the `access_token` cookie and returns who it belongs to.
+    async fn verify(&self, access_token: &str) -> Result<AuthClaims>;
+}
@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+use deadpool_diesel::postgres::Object;
+use uuid::Uuid;
+use crate::domain::model::webhook_delivery::{NewWebhookDelivery, WebhookDelivery};
+use crate::adapter::output::persistence::db::error::Result;
+
+#[async_trait]
+pub trait WebhookDeliveryRepository {
+    async fn insert(&self, conn: Object, new_delivery: NewWebhookDelivery) -> Result<WebhookDelivery>;
+    async fn get(&self, conn: Object, delivery_id: Uuid) -> Result<WebhookDelivery>;
+    async fn list_by_event(&self, conn: Object, event_id: Uuid) -> Result<Vec<WebhookDelivery>>;
+    async fn list_recent(&self, conn: Object, limit: i64) -> Result<Vec<WebhookDelivery>>;
+}
@@ -1,6 +1,30 @@
 use std::sync::Arc;
 use crate::adapter::output::persistence::db::postgres::detailed_posting_repository_impl::PostgresDetailedPostingRepository;
 use crate::adapter::output::persistence::db::postgres::mission_repository_impl::PostgresMissionSubmitRepository;
+use crate::adapter::output::persistence::db::postgres::outbox_event_repository_impl::PostgresOutboxEventRepository;
+use crate::adapter::output::persistence::db::postgres::webhook_delivery_repository_impl::PostgresWebhookDeliveryRepository;
+use crate::adapter::output::persistence::db::postgres::audit_log_repository_impl::PostgresAuditLogRepository;
+use crate::adapter::output::persistence::db::postgres::api_key_repository_impl::PostgresApiKeyRepository;
+use crate::usecase::{api_key_usecase_impl::ApiKeyUsecaseImpl, utrait::api_key_usecase::ApiKeyUsecase};
+use crate::usecase::{coin_admin_usecase_impl::CoinAdminUsecaseImpl, utrait::coin_admin_usecase::CoinAdminUsecase};
+use crate::usecase::{claim_admin_usecase_impl::ClaimAdminUsecaseImpl, utrait::claim_admin_usecase::ClaimAdminUsecase};
+use crate::usecase::{signer_key_usecase_impl::SignerKeyUsecaseImpl, utrait::signer_key_usecase::SignerKeyUsecase};
+use crate::usecase::{health_usecase_impl::HealthUsecaseImpl, utrait::health_usecase::HealthUsecase};
+use crate::usecase::payout_gate::PayoutGate;
+use crate::adapter::output::event::webhook_consumer::WebhookConsumer;
+use crate::port::output::event_consumer::EventConsumer;
+use crate::port::output::token_verifier::TokenVerifier;
+use crate::adapter::output::auth::{jwt_verifier::JwtTokenVerifier, introspection_verifier::IntrospectionTokenVerifier};
+use crate::config::auth::AuthMode;
+use crate::config::rate_limit::RateLimitBackend;
+use crate::adapter::input::web::middleware::rate_limit::RateLimiter;
+use crate::adapter::output::rate_limit::memory_store::MemoryRateLimitStore;
+use crate::adapter::output::persistence::db::postgres::rate_limit_store_impl::PostgresRateLimitStore;
+use crate::port::output::rate_limit_store::RateLimitStore;
+use crate::config::price_feed::PriceFeedSource;
+use crate::adapter::output::price::{static_feed::StaticPriceFeed, http_feed::HttpPriceFeed};
+use crate::port::output::price_feed::PriceFeed;
+use crate::usecase::{webhook_usecase_impl::WebhookUsecaseImpl, utrait::webhook_usecase::WebhookUsecase};
 use crate::adapter::output::persistence::db::postgres::{PostgresDbManager, user_repository_impl::PostgresUserRepository};
 use crate::config::Config;
 use crate::usecase::{reward_claim_usecase_impl::RewardClaimUsecaseImpl, utrait::reward_claim_usecase::RewardClaimUsecase};
@@ -23,8 +47,25 @@ pub struct AppState {
     pub network_repo: Arc<PostgresNetworkRepository>,
     pub coin_network_repo: Arc<PostgresCoinNetworkRepository>,
     pub reward_claim_repo: Arc<PostgresRewardClaimRepository>,
+    pub outbox_event_repo: Arc<PostgresOutboxEventRepository>,
+    pub webhook_delivery_repo: Arc<PostgresWebhookDeliveryRepository>,
+    pub audit_log_repo: Arc<PostgresAuditLogRepository>,
+    pub event_consumers: Vec<Arc<dyn EventConsumer>>,
     pub reward_claim_usecase: Arc<dyn RewardClaimUsecase + Send + Sync>,
+    pub webhook_usecase: Arc<dyn WebhookUsecase + Send + Sync>,
+    pub api_key_usecase: Arc<dyn ApiKeyUsecase + Send + Sync>,
+    pub coin_admin_usecase: Arc<dyn CoinAdminUsecase + Send + Sync>,
+    pub claim_admin_usecase: Arc<dyn ClaimAdminUsecase + Send + Sync>,
+    pub signer_key_usecase: Arc<dyn SignerKeyUsecase + Send + Sync>,
+    pub health_usecase: Arc<dyn HealthUsecase + Send + Sync>,
     pub near_rpc_manager: Arc<NearRpcManager>, 
+    /// `None` in header mode, where `x-user-right` is trusted (local only)
+    pub token_verifier: Option<Arc<dyn TokenVerifier>>,
+    pub rate_limiter: Arc<RateLimiter>,
+    /// `None` when `[price_feed] source = "none"`
+    pub price_feed: Option<Arc<dyn PriceFeed>>,
+    /// closed on shutdown, which then waits for the payouts in flight
+    pub payout_gate: Arc<PayoutGate>,
 }
 
 impl AppState {
@@ -47,12 +88,37 @@ impl AppState {
         let reward_claim_repo = Arc::new(PostgresRewardClaimRepository);
         let mission_submit_repo = Arc::new(PostgresMissionSubmitRepository);
         let detailed_posting_repo = Arc::new(PostgresDetailedPostingRepository);
+        let outbox_event_repo = Arc::new(PostgresOutboxEventRepository);
+        let webhook_delivery_repo = Arc::new(PostgresWebhookDeliveryRepository);
+        let audit_log_repo = Arc::new(PostgresAuditLogRepository);
+        let event_consumers: Vec<Arc<dyn EventConsumer>> = config.outbox().consumers
+            .iter()
+            .map(|consumer_config| Arc::new(WebhookConsumer::new(consumer_config)) as Arc<dyn EventConsumer>)
+            .collect();
         let near_rpc_manager = Arc::new(NearRpcManager::new(
-            config.near_network_config().rpc_client(),
+            Arc::new(config.near_network_config().rpc_endpoint_pool()),
             config.signer().clone(),
             config.near_network_config().whitelisted_contracts.clone(),
             config.near_network_config().whitelisted_senders.clone(),
+            config.retry().clone(),
+            config.circuit_breaker().clone(),
         ));
+        let price_feed: Option<Arc<dyn PriceFeed>> = match config.price_feed().source {
+            PriceFeedSource::None => {
+                tracing::warn!("price feed is disabled, reward claims are paid without a fiat value");
+                None
+            }
+            PriceFeedSource::Static => {
+                let feed = StaticPriceFeed::from_config(config.price_feed())
+                    .unwrap_or_else(|e| panic!("Failed to init static price feed: {e}"));
+                Some(Arc::new(feed))
+            }
+            PriceFeedSource::Http => {
+                let http_config = config.price_feed().http.as_ref().expect("[price_feed.http] must be set");
+                Some(Arc::new(HttpPriceFeed::new(http_config)))
+            }
+        };
+        let payout_gate = Arc::new(PayoutGate::default());
         let reward_claim_usecase: Arc<dyn RewardClaimUsecase + Send + Sync> = Arc::new(RewardClaimUsecaseImpl::new(
             Arc::clone(&db_manager),
             Arc::clone(&reward_claim_repo),
@@ -61,7 +127,74 @@ impl AppState {
             Arc::clone(&user_repo),
             Arc::clone(&mission_submit_repo),
             Arc::clone(&detailed_posting_repo),
+            price_feed.clone(),
+            Arc::clone(&payout_gate),
         ));
+        let webhook_usecase: Arc<dyn WebhookUsecase + Send + Sync> = Arc::new(WebhookUsecaseImpl::new(
+            Arc::clone(&db_manager),
+            Arc::clone(&outbox_event_repo),
+            Arc::clone(&webhook_delivery_repo),
+            Arc::clone(&audit_log_repo),
+            event_consumers.clone(),
+        ));
+
+        let api_key_usecase: Arc<dyn ApiKeyUsecase + Send + Sync> = Arc::new(ApiKeyUsecaseImpl::new(
+            Arc::clone(&db_manager),
+            Arc::new(PostgresApiKeyRepository),
+        ));
+
+        let coin_admin_usecase: Arc<dyn CoinAdminUsecase + Send + Sync> = Arc::new(CoinAdminUsecaseImpl::new(
+            Arc::clone(&db_manager),
+            Arc::clone(&coin_repo),
+            Arc::clone(&network_repo),
+            Arc::clone(&coin_network_repo),
+            Arc::clone(&near_rpc_manager),
+        ));
+
+        let claim_admin_usecase: Arc<dyn ClaimAdminUsecase + Send + Sync> = Arc::new(ClaimAdminUsecaseImpl::new(
+            Arc::clone(&db_manager),
+            Arc::clone(&reward_claim_repo),
+            Arc::clone(&coin_network_repo),
+            Arc::clone(&near_rpc_manager),
+            Arc::clone(&reward_claim_usecase),
+        ));
+
+        let signer_key_usecase: Arc<dyn SignerKeyUsecase + Send + Sync> = Arc::new(SignerKeyUsecaseImpl::new(
+            Arc::clone(&db_manager),
+            Arc::clone(&audit_log_repo),
+            Arc::clone(&near_rpc_manager),
+            config.near_network_config(),
+            config.signer(),
+        ));
+
+        let health_usecase: Arc<dyn HealthUsecase + Send + Sync> = Arc::new(HealthUsecaseImpl::new(
+            Arc::clone(&db_manager),
+            Arc::clone(&near_rpc_manager),
+            config.health().clone(),
+        ));
+
+        let token_verifier: Option<Arc<dyn TokenVerifier>> = match config.auth().mode() {
+            AuthMode::Header => {
+                tracing::warn!("auth mode is header, x-user-right is trusted without verification");
+                None
+            }
+            AuthMode::Jwt => {
+                let jwt_config = config.auth().jwt.as_ref().expect("[auth.jwt] must be set");
+                let verifier = JwtTokenVerifier::new(jwt_config)
+                    .unwrap_or_else(|e| panic!("Failed to init jwt verifier: {e}"));
+                Some(Arc::new(verifier))
+            }
+            AuthMode::Introspection => {
+                let introspection_config = config.auth().introspection.as_ref().expect("[auth.introspection] must be set");
+                Some(Arc::new(IntrospectionTokenVerifier::new(introspection_config)))
+            }
+        };
+
+        let rate_limit_store: Arc<dyn RateLimitStore> = match config.rate_limit().backend {
+            RateLimitBackend::Memory => Arc::new(MemoryRateLimitStore::new()),
+            RateLimitBackend::Postgres => Arc::new(PostgresRateLimitStore::new(Arc::clone(&db_manager))),
+        };
+        let rate_limiter = Arc::new(RateLimiter::new(rate_limit_store, config.rate_limit().clone()));
 
         Ok(Self {
             config: config.clone(),
@@ -71,8 +204,22 @@ impl AppState {
             network_repo,
             coin_network_repo,
             reward_claim_repo,
+            outbox_event_repo,
+            webhook_delivery_repo,
+            audit_log_repo,
+            event_consumers,
             reward_claim_usecase,
+            webhook_usecase,
+            api_key_usecase,
+            coin_admin_usecase,
+            claim_admin_usecase,
+            signer_key_usecase,
+            health_usecase,
             near_rpc_manager,
+            token_verifier,
+            rate_limiter,
+            price_feed,
+            payout_gate,
         })
     }
 }
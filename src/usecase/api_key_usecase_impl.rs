@@ -0,0 +1,137 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::Duration;
+use uuid::Uuid;
+use crate::{
+    domain::model::{
+        api_key::{generate_api_key, hash_api_key, parse_key_prefix, ApiKey, NewApiKey, NewApiKeyPayload, RotateApiKeyPayload},
+        audit_log::AuditActor,
+    },
+    port::output::{api_key_repository::ApiKeyRepository, DbManager},
+};
+use super::error::{Error, Result};
+use super::utrait::api_key_usecase::ApiKeyUsecase;
+
+const DEFAULT_ROTATION_GRACE_SECS: i64 = 24 * 60 * 60;
+
+pub struct ApiKeyUsecaseImpl<D: DbManager, K: ApiKeyRepository> {
+    db_manager: Arc<D>,
+    api_key_repo: Arc<K>,
+}
+
+impl<D, K> ApiKeyUsecaseImpl<D, K>
+where
+    D: DbManager + Send + Sync,
+    K: ApiKeyRepository + Send + Sync,
+{
+    pub fn new(db_manager: Arc<D>, api_key_repo: Arc<K>) -> Self {
+        Self {
+            db_manager,
+            api_key_repo,
+        }
+    }
+
+    async fn get_api_key(&self, api_key_id: Uuid) -> Result<ApiKey> {
+        self.api_key_repo.get(self.db_manager.get_connection().await?, api_key_id).await.map_err(|_| {
+            tracing::error!("Api Key Not Found: {}", api_key_id);
+            Error::ApiKeyNotFound
+        })
+    }
+}
+
+#[async_trait]
+impl<D, K> ApiKeyUsecase for ApiKeyUsecaseImpl<D, K>
+where
+    D: DbManager + Send + Sync,
+    K: ApiKeyRepository + Send + Sync,
+{
+    async fn authenticate(&self, raw_key: &str) -> Result<ApiKey> {
+        let key_prefix = parse_key_prefix(raw_key).ok_or(Error::ApiKeyInvalid)?;
+        let api_key = self.api_key_repo
+            .get_by_prefix(self.db_manager.get_connection().await?, key_prefix.to_string())
+            .await
+            .map_err(|_| Error::ApiKeyInvalid)?;
+
+        let now = chrono::Utc::now().naive_utc();
+        if !api_key.matches(raw_key) || !api_key.is_active(now) {
+            tracing::info!("Api Key Rejected: {}", api_key.id);
+            return Err(Error::ApiKeyInvalid);
+        }
+
+        // --- a write per request would make every api call contend on the key row
+        if api_key.needs_last_used_touch(now) {
+            if let Err(e) = self.api_key_repo.touch_last_used(self.db_manager.get_connection().await?, api_key.id).await {
+                tracing::warn!("Failed to update last_used_date of api key {}: {}", api_key.id, e);
+            }
+        }
+        Ok(api_key)
+    }
+
+    async fn create_api_key(&self, payload: NewApiKeyPayload, actor: AuditActor) -> Result<(String, ApiKey)> {
+        if payload.name.trim().is_empty() || payload.scopes.is_empty() {
+            return Err(Error::InvalidApiKeyPayload { message: "name and scopes are required".to_string() });
+        }
+        if payload.expires_in_days.is_some_and(|days| days <= 0) {
+            return Err(Error::InvalidApiKeyPayload { message: "expires_in_days must be positive".to_string() });
+        }
+
+        let (raw_key, key_prefix) = generate_api_key();
+        let mut scopes: Vec<String> = payload.scopes.iter().map(|scope| scope.to_string()).collect();
+        scopes.sort();
+        scopes.dedup();
+
+        let new_api_key = NewApiKey {
+            id: Uuid::new_v4(),
+            name: payload.name.trim().to_string(),
+            key_hash: hash_api_key(&raw_key),
+            key_prefix,
+            scopes,
+            expires_date: payload.expires_in_days.map(|days| chrono::Utc::now().naive_utc() + Duration::days(days)),
+            rotated_from: None,
+            created_by: actor.actor_id.clone(),
+        };
+        let api_key = self.api_key_repo.insert(self.db_manager.get_connection().await?, new_api_key, actor).await?;
+        Ok((raw_key, api_key))
+    }
+
+    async fn list_api_keys(&self) -> Result<Vec<ApiKey>> {
+        Ok(self.api_key_repo.list(self.db_manager.get_connection().await?).await?)
+    }
+
+    /// Issues a new key with the same name, scopes and expiry, the old key keeps working for the grace period.
+    async fn rotate_api_key(&self, api_key_id: Uuid, payload: RotateApiKeyPayload, actor: AuditActor) -> Result<(String, ApiKey)> {
+        let old_key = self.get_api_key(api_key_id).await?;
+        let now = chrono::Utc::now().naive_utc();
+        if !old_key.is_active(now) {
+            return Err(Error::InvalidApiKeyPayload { message: "only active keys can be rotated".to_string() });
+        }
+
+        let grace_period_secs = payload.grace_period_secs.unwrap_or(DEFAULT_ROTATION_GRACE_SECS).max(0);
+        let (raw_key, key_prefix) = generate_api_key();
+        let new_api_key = NewApiKey {
+            id: Uuid::new_v4(),
+            name: old_key.name.clone(),
+            key_hash: hash_api_key(&raw_key),
+            key_prefix,
+            scopes: old_key.scopes.clone(),
+            expires_date: old_key.expires_date
+                .map(|expires_date| now + (expires_date - old_key.created_date)),
+            rotated_from: Some(old_key.id),
+            created_by: actor.actor_id.clone(),
+        };
+
+        let api_key = self.api_key_repo.rotate(
+            self.db_manager.get_connection().await?,
+            api_key_id,
+            new_api_key,
+            now + Duration::seconds(grace_period_secs),
+            actor,
+        ).await?;
+        Ok((raw_key, api_key))
+    }
+
+    async fn revoke_api_key(&self, api_key_id: Uuid, actor: AuditActor) -> Result<ApiKey> {
+        self.get_api_key(api_key_id).await?;
+        Ok(self.api_key_repo.revoke(self.db_manager.get_connection().await?, api_key_id, actor).await?)
+    }
+}
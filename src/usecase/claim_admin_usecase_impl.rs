@@ -0,0 +1,235 @@
+use std::str::FromStr;
+use std::sync::Arc;
+use async_trait::async_trait;
+use bigdecimal::BigDecimal;
+use chrono::{Duration, NaiveDateTime, Utc};
+use uuid::Uuid;
+use crate::{
+    adapter::output::near::rpc_client::NearRpcManager,
+    domain::model::{
+        audit_log::AuditActor,
+        near::OnChainTransactionStatus,
+        reward_claim::{
+            CombinedRewardClaimResponse, NewRewardClaimPayload, ReconcileEntry, ReconcileIssue, RewardClaim,
+            RewardClaimAdminResponse, RewardClaimFilter, RewardClaimStatus, STALE_READY_CLAIM_MINUTES,
+        },
+    },
+    port::output::{coin_network_repository::CoinNetworkRepository, reward_claim_repository::RewardClaimRepository, rpc_client::RpcClient, DbManager},
+};
+use super::error::{Error, Result};
+use super::utrait::claim_admin_usecase::ClaimAdminUsecase;
+use super::utrait::reward_claim_usecase::RewardClaimUsecase;
+
+pub struct ClaimAdminUsecaseImpl<D: DbManager, R: RewardClaimRepository, CN: CoinNetworkRepository> {
+    db_manager: Arc<D>,
+    reward_claim_repo: Arc<R>,
+    coin_network_repo: Arc<CN>,
+    near_rpc_manager: Arc<NearRpcManager>,
+    reward_claim_usecase: Arc<dyn RewardClaimUsecase + Send + Sync>,
+}
+
+impl<D, R, CN> ClaimAdminUsecaseImpl<D, R, CN>
+where
+    D: DbManager + Send + Sync,
+    R: RewardClaimRepository + Send + Sync,
+    CN: CoinNetworkRepository + Send + Sync,
+{
+    pub fn new(db_manager: Arc<D>, reward_claim_repo: Arc<R>, coin_network_repo: Arc<CN>, near_rpc_manager: Arc<NearRpcManager>, reward_claim_usecase: Arc<dyn RewardClaimUsecase + Send + Sync>) -> Self {
+        Self {
+            db_manager,
+            reward_claim_repo,
+            coin_network_repo,
+            near_rpc_manager,
+            reward_claim_usecase,
+        }
+    }
+
+    async fn get_reward_claim(&self, reward_claim_id: Uuid) -> Result<RewardClaim> {
+        self.reward_claim_repo.get(self.db_manager.get_connection().await?, reward_claim_id).await.map_err(|_| {
+            tracing::error!("Reward Claim Id Not Found: {}", reward_claim_id);
+            Error::RewardClaimIdNotFound
+        })
+    }
+
+    /// A claim is only paid again or closed when its last transaction can no longer land:
+    /// the chain must report it failed or not know it. Pending, succeeded or unreachable rpc refuse.
+    async fn ensure_last_transaction_settled(&self, claim: &RewardClaim) -> Result<()> {
+        let details = self.reward_claim_repo.list_details(self.db_manager.get_connection().await?, claim.id).await?;
+        let Some(latest) = details.into_iter().next() else {
+            return Ok(());
+        };
+
+        let on_chain_status = self.near_rpc_manager.transaction_status(&latest.transaction_hash).await?;
+        if !is_settled_as_unpaid(&on_chain_status) {
+            tracing::warn!("Reward Claim {} last transaction {} is {:?}", claim.id, latest.transaction_hash, on_chain_status);
+            return Err(Error::ClaimTransactionNotSettled {
+                transaction_hash: latest.transaction_hash,
+                status: format!("{:?}", on_chain_status),
+            });
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "reward_claim.reconcile", skip_all, fields(claim_id = %claim.id, tx_hash = tracing::field::Empty))]
+    async fn reconcile_claim(&self, claim: &RewardClaim, now: NaiveDateTime) -> Result<Option<ReconcileEntry>> {
+        match claim.reward_claim_status {
+            RewardClaimStatus::Ready => {
+                let entry = is_stale_ready(claim, now)
+                    .then(|| ReconcileEntry::new(claim, None, None, ReconcileIssue::StaleReady, None));
+                return Ok(entry);
+            }
+            RewardClaimStatus::Cancelled => return Ok(None),
+            RewardClaimStatus::TransactionApproved | RewardClaimStatus::TransactionFailed => {}
+        }
+
+        let details = self.reward_claim_repo.list_details(self.db_manager.get_connection().await?, claim.id).await?;
+        let Some(latest) = details.into_iter().next() else {
+            // --- failed claims may never have reached the chain
+            let entry = (claim.reward_claim_status == RewardClaimStatus::TransactionApproved)
+                .then(|| ReconcileEntry::new(claim, None, None, ReconcileIssue::ApprovedWithoutTransaction, None));
+            return Ok(entry);
+        };
+
+        tracing::Span::current().record("tx_hash", latest.transaction_hash.as_str());
+        let entry = match self.near_rpc_manager.transaction_status(&latest.transaction_hash).await {
+            Ok(on_chain_status) => reconcile_issue(&claim.reward_claim_status, &on_chain_status)
+                .map(|issue| ReconcileEntry::new(claim, Some(latest.transaction_hash), Some(on_chain_status), issue, None)),
+            Err(e) => Some(ReconcileEntry::new(claim, Some(latest.transaction_hash), None, ReconcileIssue::Unchecked, Some(e.to_string()))),
+        };
+        Ok(entry)
+    }
+}
+
+fn is_settled_as_unpaid(on_chain_status: &OnChainTransactionStatus) -> bool {
+    matches!(on_chain_status, OnChainTransactionStatus::Failed(_) | OnChainTransactionStatus::NotFound)
+}
+
+fn is_stale_ready(claim: &RewardClaim, now: NaiveDateTime) -> bool {
+    claim.reward_claim_status == RewardClaimStatus::Ready
+        && claim.updated_date < now - Duration::minutes(STALE_READY_CLAIM_MINUTES)
+}
+
+/// `None` when the stored status agrees with the chain, or the transaction is still pending.
+fn reconcile_issue(status: &RewardClaimStatus, on_chain_status: &OnChainTransactionStatus) -> Option<ReconcileIssue> {
+    match (status, on_chain_status) {
+        (RewardClaimStatus::TransactionApproved, OnChainTransactionStatus::Failed(_) | OnChainTransactionStatus::NotFound) => {
+            Some(ReconcileIssue::ApprovedNotOnChain)
+        }
+        (RewardClaimStatus::TransactionFailed, OnChainTransactionStatus::Succeeded) => Some(ReconcileIssue::FailedButSucceeded),
+        _ => None,
+    }
+}
+
+#[async_trait]
+impl<D, R, CN> ClaimAdminUsecase for ClaimAdminUsecaseImpl<D, R, CN>
+where
+    D: DbManager + Send + Sync,
+    R: RewardClaimRepository + Send + Sync,
+    CN: CoinNetworkRepository + Send + Sync,
+{
+    async fn list_claims(&self, filter: RewardClaimFilter) -> Result<Vec<RewardClaim>> {
+        Ok(self.reward_claim_repo.list(self.db_manager.get_connection().await?, filter).await?)
+    }
+
+    async fn get_claim(&self, reward_claim_id: Uuid) -> Result<RewardClaimAdminResponse> {
+        let claim = self.get_reward_claim(reward_claim_id).await?;
+        let details = self.reward_claim_repo.list_details(self.db_manager.get_connection().await?, claim.id).await?;
+        let coin_network = self.coin_network_repo
+            .get_with_coin_and_network(self.db_manager.get_connection().await?, claim.coin_network_id)
+            .await
+            .ok();
+
+        Ok(RewardClaimAdminResponse::from((claim, details, coin_network)))
+    }
+
+    async fn retry_claim(&self, reward_claim_id: Uuid, actor: AuditActor) -> Result<CombinedRewardClaimResponse> {
+        let claim = self.get_reward_claim(reward_claim_id).await?;
+        if claim.reward_claim_status != RewardClaimStatus::TransactionFailed {
+            return Err(Error::InvalidClaimStatusForRetry { status: claim.reward_claim_status.to_string() });
+        }
+        self.ensure_last_transaction_settled(&claim).await?;
+
+        let (_, coin, _) = self.coin_network_repo
+            .get_with_coin_and_network(self.db_manager.get_connection().await?, claim.coin_network_id)
+            .await
+            .map_err(|_| {
+                tracing::error!("Coin Network Id Not Found: {}", claim.coin_network_id);
+                Error::CoinNetworkIdNotFound
+            })?;
+
+        // --- the claim stores the smallest unit, the claim flow takes the display amount
+        let scale_factor = BigDecimal::from_str(&format!("1e{}", coin.decimals)).expect("Invalid decimal format");
+        let payload = NewRewardClaimPayload {
+            resource_id: claim.resource_id,
+            resource_type: claim.resource_type.to_string(),
+            coin_network_id: claim.coin_network_id,
+            amount: (claim.amount / scale_factor).normalized().to_string(),
+            user_address: claim.user_address,
+        };
+
+        tracing::info!("[Retry] Reward Claim: {} by {}", reward_claim_id, actor.actor_id);
+        self.reward_claim_usecase.create_reward_claim(claim.user_id, payload, actor).await
+    }
+
+    async fn cancel_claim(&self, reward_claim_id: Uuid, actor: AuditActor) -> Result<RewardClaim> {
+        let claim = self.get_reward_claim(reward_claim_id).await?;
+        let cancellable = claim.reward_claim_status == RewardClaimStatus::TransactionFailed
+            || is_stale_ready(&claim, Utc::now().naive_utc());
+        if !cancellable {
+            return Err(Error::InvalidClaimStatusForCancel { status: claim.reward_claim_status.to_string() });
+        }
+        self.ensure_last_transaction_settled(&claim).await?;
+
+        tracing::info!("[Cancel] Reward Claim: {} by {}", reward_claim_id, actor.actor_id);
+        Ok(self.reward_claim_repo.update_status(
+            self.db_manager.get_connection().await?,
+            reward_claim_id,
+            RewardClaimStatus::Cancelled,
+            false,
+            actor,
+        ).await?)
+    }
+
+    async fn reconcile(&self, updated_after: Option<NaiveDateTime>, limit: i64) -> Result<Vec<ReconcileEntry>> {
+        let filter = RewardClaimFilter { updated_after, limit, ..Default::default() };
+        let claims = self.reward_claim_repo.list(self.db_manager.get_connection().await?, filter).await?;
+        let now = Utc::now().naive_utc();
+
+        let mut entries = Vec::new();
+        for claim in claims.iter() {
+            if let Some(entry) = self.reconcile_claim(claim, now).await? {
+                entries.push(entry);
+            }
+        }
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reconcile_issue() {
+        let approved = RewardClaimStatus::TransactionApproved;
+        let failed = RewardClaimStatus::TransactionFailed;
+
+        assert_eq!(reconcile_issue(&approved, &OnChainTransactionStatus::Succeeded), None);
+        assert_eq!(reconcile_issue(&approved, &OnChainTransactionStatus::Pending), None);
+        assert_eq!(reconcile_issue(&approved, &OnChainTransactionStatus::NotFound), Some(ReconcileIssue::ApprovedNotOnChain));
+        assert_eq!(
+            reconcile_issue(&approved, &OnChainTransactionStatus::Failed("ActionError".to_string())),
+            Some(ReconcileIssue::ApprovedNotOnChain)
+        );
+        assert_eq!(reconcile_issue(&failed, &OnChainTransactionStatus::Succeeded), Some(ReconcileIssue::FailedButSucceeded));
+        assert_eq!(reconcile_issue(&failed, &OnChainTransactionStatus::Failed("ActionError".to_string())), None);
+    }
+
+    #[test]
+    fn test_is_settled_as_unpaid() {
+        assert!(is_settled_as_unpaid(&OnChainTransactionStatus::NotFound));
+        assert!(is_settled_as_unpaid(&OnChainTransactionStatus::Failed("ActionError".to_string())));
+        assert!(!is_settled_as_unpaid(&OnChainTransactionStatus::Pending));
+        assert!(!is_settled_as_unpaid(&OnChainTransactionStatus::Succeeded));
+    }
+}
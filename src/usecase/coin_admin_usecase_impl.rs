@@ -0,0 +1,332 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use uuid::Uuid;
+use crate::{
+    adapter::output::near::rpc_client::NearRpcManager,
+    domain::model::{
+        audit_log::AuditActor,
+        coin::{Coin, CoinChangeset, CoinType, NewCoin, NewCoinPayload, UpdateCoinPayload},
+        coin_network::{metadata_mismatch, validate_contract_address, CoinNetwork, FtMetadataCheckResponse, NewCoinNetwork, NewCoinNetworkPayload, UpdateCoinNetworkPayload},
+        near::FtMetadata,
+        network::{Network, NewNetwork, NewNetworkPayload, UpdateNetworkPayload, NEAR_NETWORK_CODE},
+    },
+    port::output::{coin_network_repository::CoinNetworkRepository, coin_repository::CoinRepository, network_repository::NetworkRepository, rpc_client::RpcClient, DbManager},
+};
+use super::error::{Error, Result};
+use super::utrait::coin_admin_usecase::CoinAdminUsecase;
+
+pub struct CoinAdminUsecaseImpl<D: DbManager, C: CoinRepository, N: NetworkRepository, CN: CoinNetworkRepository> {
+    db_manager: Arc<D>,
+    coin_repo: Arc<C>,
+    network_repo: Arc<N>,
+    coin_network_repo: Arc<CN>,
+    near_rpc_manager: Arc<NearRpcManager>,
+}
+
+impl<D, C, N, CN> CoinAdminUsecaseImpl<D, C, N, CN>
+where
+    D: DbManager + Send + Sync,
+    C: CoinRepository + Send + Sync,
+    N: NetworkRepository + Send + Sync,
+    CN: CoinNetworkRepository + Send + Sync,
+{
+    pub fn new(db_manager: Arc<D>, coin_repo: Arc<C>, network_repo: Arc<N>, coin_network_repo: Arc<CN>, near_rpc_manager: Arc<NearRpcManager>) -> Self {
+        Self {
+            db_manager,
+            coin_repo,
+            network_repo,
+            coin_network_repo,
+            near_rpc_manager,
+        }
+    }
+
+    async fn get_coin(&self, coin_id: Uuid) -> Result<Coin> {
+        self.coin_repo.get(self.db_manager.get_connection().await?, coin_id).await.map_err(|_| {
+            tracing::error!("Coin Id Not Found: {}", coin_id);
+            Error::CoinIdNotFound
+        })
+    }
+
+    async fn get_network(&self, network_id: Uuid) -> Result<Network> {
+        self.network_repo.get(self.db_manager.get_connection().await?, network_id).await.map_err(|_| {
+            tracing::error!("Network Id Not Found: {}", network_id);
+            Error::NetworkIdNotFound
+        })
+    }
+
+    async fn get_coin_network(&self, coin_network_id: Uuid) -> Result<(CoinNetwork, Coin, Network)> {
+        self.coin_network_repo.get_with_coin_and_network(self.db_manager.get_connection().await?, coin_network_id).await.map_err(|_| {
+            tracing::error!("Coin Network Id Not Found: {}", coin_network_id);
+            Error::CoinNetworkIdNotFound
+        })
+    }
+
+    /// `ft_metadata` of the contract, `None` when the coin network has none.
+    /// Read before the write it guards, so an rpc failure rejects the change instead of leaving it unchecked.
+    async fn fetch_ft_metadata(&self, contract_address: Option<&str>, coin: &Coin, network: &Network) -> Result<Option<FtMetadata>> {
+        match contract_address {
+            Some(contract_address) if is_ft_contract(coin, network) => Ok(Some(self.near_rpc_manager.ft_metadata(contract_address).await?)),
+            _ => Ok(None),
+        }
+    }
+
+    /// `ft_metadata` of every live coin network of `coin` except `skip`.
+    async fn fetch_coin_ft_metadata(&self, coin: &Coin, skip: Option<Uuid>) -> Result<Vec<(Uuid, FtMetadata)>> {
+        let coin_network_ids = self.coin_network_repo
+            .list_by_coin_id(self.db_manager.get_connection().await?, coin.id)
+            .await?
+            .into_iter()
+            .filter(|coin_network| !coin_network.is_deprecated() && Some(coin_network.id) != skip)
+            .map(|coin_network| coin_network.id)
+            .collect();
+        let coin_networks = self.coin_network_repo.list_all_by_ids(self.db_manager.get_connection().await?, coin_network_ids).await?;
+
+        let mut fetched = vec![];
+        for (coin_network, coin, network) in coin_networks {
+            if let Some(metadata) = self.fetch_ft_metadata(coin_network.contract_address.as_deref(), &coin, &network).await? {
+                fetched.push((coin_network.id, metadata));
+            }
+        }
+        Ok(fetched)
+    }
+
+    /// Compares `metadata` with `coin` and stores the result, payouts are refused while it mismatches.
+    async fn record_ft_metadata(&self, coin_network_id: Uuid, coin: &Coin, metadata: &FtMetadata, actor: AuditActor) -> Result<CoinNetwork> {
+        let mismatch = metadata_mismatch(coin, metadata);
+        if let Some(mismatch) = &mismatch {
+            tracing::warn!("[ft_metadata] coin network {} does not match chain, payouts are refused: {}", coin_network_id, mismatch);
+        }
+        Ok(self.coin_network_repo.record_metadata_check(self.db_manager.get_connection().await?, coin_network_id, mismatch, actor).await?)
+    }
+}
+
+/// Only FT contracts on NEAR expose `ft_metadata`.
+fn is_ft_contract(coin: &Coin, network: &Network) -> bool {
+    coin.coin_type == CoinType::FT && network.code.eq_ignore_ascii_case(NEAR_NETWORK_CODE)
+}
+
+fn has_ft_metadata(coin_network: &CoinNetwork, coin: &Coin, network: &Network) -> bool {
+    coin_network.contract_address.is_some() && is_ft_contract(coin, network)
+}
+
+fn normalize_contract_address(contract_address: Option<String>) -> Option<String> {
+    contract_address.map(|address| address.trim().to_string()).filter(|address| !address.is_empty())
+}
+
+#[async_trait]
+impl<D, C, N, CN> CoinAdminUsecase for CoinAdminUsecaseImpl<D, C, N, CN>
+where
+    D: DbManager + Send + Sync,
+    C: CoinRepository + Send + Sync,
+    N: NetworkRepository + Send + Sync,
+    CN: CoinNetworkRepository + Send + Sync,
+{
+    async fn create_coin(&self, payload: NewCoinPayload, actor: AuditActor) -> Result<Coin> {
+        payload.validate().map_err(|message| Error::InvalidCoinPayload { message })?;
+
+        let new_coin = NewCoin {
+            id: Uuid::new_v4(),
+            name: payload.name.trim().to_string(),
+            symbol: payload.symbol,
+            decimals: payload.decimals,
+            coin_type: CoinType::from(payload.coin_type),
+        };
+        Ok(self.coin_repo.create(self.db_manager.get_connection().await?, new_coin, actor).await?)
+    }
+
+    async fn update_coin(&self, coin_id: Uuid, payload: UpdateCoinPayload, actor: AuditActor) -> Result<Coin> {
+        payload.validate().map_err(|message| Error::InvalidCoinPayload { message })?;
+        let coin = self.get_coin(coin_id).await?;
+
+        // --- new decimals or symbol are checked against every FT contract of the coin
+        let metadata_changed = payload.decimals.is_some_and(|decimals| decimals != coin.decimals)
+            || payload.symbol.as_ref().is_some_and(|symbol| !symbol.eq_ignore_ascii_case(&coin.symbol));
+        let fetched = if metadata_changed { self.fetch_coin_ft_metadata(&coin, None).await? } else { vec![] };
+
+        let changeset = CoinChangeset::from(UpdateCoinPayload {
+            name: payload.name.map(|name| name.trim().to_string()),
+            ..payload
+        });
+        let coin = self.coin_repo.update(self.db_manager.get_connection().await?, coin_id, changeset, actor.clone()).await?;
+        for (coin_network_id, metadata) in fetched {
+            self.record_ft_metadata(coin_network_id, &coin, &metadata, actor.clone()).await?;
+        }
+        Ok(coin)
+    }
+
+    async fn set_coin_active(&self, coin_id: Uuid, is_active: bool, actor: AuditActor) -> Result<Coin> {
+        self.get_coin(coin_id).await?;
+        Ok(self.coin_repo.set_active(self.db_manager.get_connection().await?, coin_id, is_active, actor).await?)
+    }
+
+    async fn create_network(&self, payload: NewNetworkPayload, actor: AuditActor) -> Result<Network> {
+        payload.validate().map_err(|message| Error::InvalidNetworkPayload { message })?;
+
+        if self.network_repo.find_by_code(self.db_manager.get_connection().await?, payload.code.clone()).await?.is_some() {
+            return Err(Error::NetworkCodeDuplicate { code: payload.code });
+        }
+
+        let new_network = NewNetwork {
+            id: Uuid::new_v4(),
+            name: payload.name.trim().to_string(),
+            code: payload.code,
+        };
+        Ok(self.network_repo.create(self.db_manager.get_connection().await?, new_network, actor).await?)
+    }
+
+    async fn update_network(&self, network_id: Uuid, payload: UpdateNetworkPayload, actor: AuditActor) -> Result<Network> {
+        payload.validate().map_err(|message| Error::InvalidNetworkPayload { message })?;
+        self.get_network(network_id).await?;
+        Ok(self.network_repo.update_name(self.db_manager.get_connection().await?, network_id, payload.name.trim().to_string(), actor).await?)
+    }
+
+    async fn set_network_active(&self, network_id: Uuid, is_active: bool, actor: AuditActor) -> Result<Network> {
+        self.get_network(network_id).await?;
+        Ok(self.network_repo.set_active(self.db_manager.get_connection().await?, network_id, is_active, actor).await?)
+    }
+
+    async fn create_coin_network(&self, payload: NewCoinNetworkPayload, actor: AuditActor) -> Result<(CoinNetwork, Coin, Network)> {
+        let coin = self.get_coin(payload.coin_id).await?;
+        let network = self.get_network(payload.network_id).await?;
+        if !coin.is_active || !network.is_active {
+            return Err(Error::InvalidCoinNetworkPayload { message: "coin and network must be active".to_string() });
+        }
+
+        let contract_address = normalize_contract_address(payload.contract_address);
+        validate_contract_address(&network.code, &coin.coin_type, contract_address.as_deref())
+            .map_err(|message| Error::InvalidCoinNetworkPayload { message })?;
+
+        let existing = self.coin_network_repo.list_by_coin_id(self.db_manager.get_connection().await?, coin.id).await?;
+        if existing.iter().any(|coin_network| coin_network.network_id == network.id && !coin_network.is_deprecated()) {
+            return Err(Error::CoinNetworkDuplicate);
+        }
+        let metadata = self.fetch_ft_metadata(contract_address.as_deref(), &coin, &network).await?;
+
+        let new_coin_network = NewCoinNetwork {
+            id: Uuid::new_v4(),
+            coin_id: coin.id,
+            network_id: network.id,
+            contract_address,
+        };
+        let coin_network = self.coin_network_repo.create(self.db_manager.get_connection().await?, new_coin_network, actor.clone()).await?;
+        let coin_network = match metadata {
+            Some(metadata) => self.record_ft_metadata(coin_network.id, &coin, &metadata, actor).await?,
+            None => coin_network,
+        };
+        Ok((coin_network, coin, network))
+    }
+
+    async fn update_coin_network(&self, coin_network_id: Uuid, payload: UpdateCoinNetworkPayload, actor: AuditActor) -> Result<(CoinNetwork, Coin, Network)> {
+        let (target_coin_network, coin, network) = self.get_coin_network(coin_network_id).await?;
+        if target_coin_network.is_deprecated() {
+            return Err(Error::InvalidCoinNetworkPayload { message: "deprecated coin networks can not be updated".to_string() });
+        }
+
+        let contract_address = normalize_contract_address(payload.contract_address);
+        validate_contract_address(&network.code, &coin.coin_type, contract_address.as_deref())
+            .map_err(|message| Error::InvalidCoinNetworkPayload { message })?;
+        let metadata = self.fetch_ft_metadata(contract_address.as_deref(), &coin, &network).await?;
+
+        let coin_network = self.coin_network_repo
+            .update_contract_address(self.db_manager.get_connection().await?, coin_network_id, contract_address, actor.clone())
+            .await?;
+        let coin_network = match metadata {
+            Some(metadata) => self.record_ft_metadata(coin_network.id, &coin, &metadata, actor).await?,
+            None => coin_network,
+        };
+        Ok((coin_network, coin, network))
+    }
+
+    async fn set_coin_network_active(&self, coin_network_id: Uuid, is_active: bool, actor: AuditActor) -> Result<(CoinNetwork, Coin, Network)> {
+        let (target_coin_network, coin, network) = self.get_coin_network(coin_network_id).await?;
+        if is_active && target_coin_network.is_deprecated() {
+            return Err(Error::InvalidCoinNetworkPayload { message: "deprecated coin networks can not be enabled".to_string() });
+        }
+
+        let coin_network = self.coin_network_repo
+            .set_active(self.db_manager.get_connection().await?, coin_network_id, is_active, actor)
+            .await?;
+        Ok((coin_network, coin, network))
+    }
+
+    async fn deprecate_coin_network(&self, coin_network_id: Uuid, actor: AuditActor) -> Result<(CoinNetwork, Coin, Network)> {
+        let (target_coin_network, coin, network) = self.get_coin_network(coin_network_id).await?;
+        if target_coin_network.is_deprecated() {
+            return Ok((target_coin_network, coin, network));
+        }
+
+        let coin_network = self.coin_network_repo
+            .deprecate(self.db_manager.get_connection().await?, coin_network_id, actor)
+            .await?;
+        Ok((coin_network, coin, network))
+    }
+
+    async fn check_ft_metadata(&self, actor: AuditActor) -> Result<Vec<FtMetadataCheckResponse>> {
+        let coin_networks = self.coin_network_repo.list_all(self.db_manager.get_connection().await?, false).await?;
+
+        let mut checks = vec![];
+        for (coin_network, coin, network) in coin_networks {
+            if !has_ft_metadata(&coin_network, &coin, &network) {
+                continue;
+            }
+            let contract_address = coin_network.contract_address.clone().unwrap_or_default();
+
+            let check = match self.near_rpc_manager.ft_metadata(&contract_address).await {
+                Ok(metadata) => {
+                    let checked = self.record_ft_metadata(coin_network.id, &coin, &metadata, actor.clone()).await?;
+                    FtMetadataCheckResponse {
+                        coin_network_id: coin_network.id.to_string(),
+                        contract_address: coin_network.contract_address,
+                        metadata: Some(metadata),
+                        metadata_mismatch: checked.metadata_mismatch,
+                        error: None,
+                    }
+                }
+                Err(e) => FtMetadataCheckResponse {
+                    coin_network_id: coin_network.id.to_string(),
+                    contract_address: coin_network.contract_address,
+                    metadata: None,
+                    metadata_mismatch: coin_network.metadata_mismatch,
+                    error: Some(e.client_status_and_error().1),
+                },
+            };
+            checks.push(check);
+        }
+        Ok(checks)
+    }
+
+    async fn sync_ft_metadata(&self, coin_network_id: Uuid, actor: AuditActor) -> Result<(CoinNetwork, Coin, Network)> {
+        let (coin_network, coin, network) = self.get_coin_network(coin_network_id).await?;
+        if !has_ft_metadata(&coin_network, &coin, &network) {
+            return Err(Error::InvalidCoinNetworkPayload { message: "only FT coin networks on NEAR have ft_metadata".to_string() });
+        }
+
+        let contract_address = coin_network.contract_address.clone().unwrap_or_default();
+        let metadata = self.near_rpc_manager.ft_metadata(&contract_address).await?;
+
+        let coin = match metadata_mismatch(&coin, &metadata) {
+            None => coin,
+            Some(mismatch) => {
+                tracing::info!("[ft_metadata] syncing coin {} from {}: {}", coin.id, contract_address, mismatch);
+                // --- the other contracts of the coin must agree with the synced decimals and symbol too
+                let others = self.fetch_coin_ft_metadata(&coin, Some(coin_network_id)).await?;
+                let payload = UpdateCoinPayload {
+                    name: None,
+                    symbol: (!coin.symbol.eq_ignore_ascii_case(&metadata.symbol)).then(|| metadata.symbol.clone()),
+                    decimals: Some(i32::from(metadata.decimals)),
+                };
+                payload.validate().map_err(|message| Error::InvalidCoinPayload { message })?;
+                let coin = self.coin_repo.update(self.db_manager.get_connection().await?, coin.id, CoinChangeset::from(payload), actor.clone()).await?;
+                for (other_coin_network_id, other_metadata) in others {
+                    self.record_ft_metadata(other_coin_network_id, &coin, &other_metadata, actor.clone()).await?;
+                }
+                coin
+            }
+        };
+
+        let coin_network = self.coin_network_repo
+            .record_metadata_check(self.db_manager.get_connection().await?, coin_network_id, None, actor)
+            .await?;
+        Ok((coin_network, coin, network))
+    }
+}
@@ -15,7 +15,23 @@ pub enum Error {
     MissionSubmitNotApproved,
     DetailedPostingNotApproved,
 	InvalidResourceType { message: String },
+    InvalidApiKeyPayload { message: String },
+    InvalidCoinPayload { message: String },
+    InvalidNetworkPayload { message: String },
+    InvalidCoinNetworkPayload { message: String },
+    CoinNetworkDisabled,
+    InvalidClaimStatusForRetry { status: String },
+    InvalidClaimStatusForCancel { status: String },
+    /// the last transaction of the claim is pending or went through, paying or closing it again is refused
+    ClaimTransactionNotSettled { transaction_hash: String, status: String },
+    /// a reload that would leave the signer without any key
+    SignerKeysEmpty,
+    InvalidSignerKeysPayload { message: String },
+    /// adding and deleting keys needs a full-access key in the keys file
+    FullAccessKeyNotFound,
 
+    // --- 401
+    ApiKeyInvalid,
 
     // --- 404
     CoinTypeNotSupported{
@@ -25,9 +41,33 @@ pub enum Error {
     UserIdNotFound,
     MissionSubmitIdNotFound,
     DetailedPostingIdNotFound,
+    WebhookDeliveryNotFound,
+    OutboxEventNotFound,
+    WebhookEndpointNotConfigured {
+        endpoint_name: String,
+    },
+    ApiKeyNotFound,
+    CoinIdNotFound,
+    NetworkIdNotFound,
+    RewardClaimIdNotFound,
+    SignerKeyNotFound { public_key: String },
 
     // --- 409
     RewardClaimDuplicate,
+    RewardClaimCancelled,
+    NetworkCodeDuplicate {
+        code: String,
+    },
+    CoinNetworkDuplicate,
+    CoinMetadataMismatch {
+        message: String,
+    },
+    /// removed keys still signing transactions past the drain timeout, they are out of the signer but not deleted
+    SignerKeysInFlight { public_keys: Vec<String> },
+
+    // --- 503
+    /// the instance is draining for a shutdown, see `PayoutGate`
+    ShuttingDown,
 
     // --- 500
     InternalServerError  { 
@@ -39,6 +79,9 @@ pub enum Error {
     TransactionActionFailed { 
         message: String,
     },
+    SignerKeysReloadFailed {
+        message: String,
+    },
 
     // --- External
     AdapterOutputDB(db::error::Error),
@@ -77,10 +120,80 @@ impl Error {
                 StatusCode::NOT_FOUND,
                 format!("Detailed Posting Id Not Found")
             ),
+            Self::WebhookDeliveryNotFound => (
+                StatusCode::NOT_FOUND,
+                "Webhook Delivery Not Found".to_string(),
+            ),
+            Self::OutboxEventNotFound => (
+                StatusCode::NOT_FOUND,
+                "Outbox Event Not Found".to_string(),
+            ),
+            Self::WebhookEndpointNotConfigured { endpoint_name } => (
+                StatusCode::NOT_FOUND,
+                format!("Webhook Endpoint Not Configured: {}", endpoint_name),
+            ),
+            Self::ApiKeyNotFound => (
+                StatusCode::NOT_FOUND,
+                "Api Key Not Found".to_string(),
+            ),
+            Self::CoinIdNotFound => (
+                StatusCode::NOT_FOUND,
+                "Coin Id Not Found".to_string(),
+            ),
+            Self::NetworkIdNotFound => (
+                StatusCode::NOT_FOUND,
+                "Network Id Not Found".to_string(),
+            ),
+            Self::RewardClaimIdNotFound => (
+                StatusCode::NOT_FOUND,
+                "Reward Claim Id Not Found".to_string(),
+            ),
+            Self::ApiKeyInvalid => (
+                StatusCode::UNAUTHORIZED,
+                "Invalid Api Key".to_string(),
+            ),
+            Self::InvalidApiKeyPayload { message } => (
+                StatusCode::BAD_REQUEST,
+                message.to_string(),
+            ),
+            Self::CoinNetworkDisabled => (
+                StatusCode::BAD_REQUEST,
+                "Coin Network Is Disabled".to_string(),
+            ),
+            Self::InvalidCoinPayload { message }
+            | Self::InvalidNetworkPayload { message }
+            | Self::InvalidCoinNetworkPayload { message } => (
+                StatusCode::BAD_REQUEST,
+                message.to_string(),
+            ),
             Self::RewardClaimDuplicate => (
                 StatusCode::CONFLICT,
                 "Reward already claimed".to_string()
             ),
+            Self::RewardClaimCancelled => (
+                StatusCode::CONFLICT,
+                "Reward claim was cancelled".to_string()
+            ),
+            Self::ClaimTransactionNotSettled { transaction_hash, status } => (
+                StatusCode::CONFLICT,
+                format!("Last transaction {} of the claim is {}, reconcile it first", transaction_hash, status),
+            ),
+            Self::NetworkCodeDuplicate { code } => (
+                StatusCode::CONFLICT,
+                format!("Network code already exists: {}", code),
+            ),
+            Self::CoinNetworkDuplicate => (
+                StatusCode::CONFLICT,
+                "Coin is already registered on this network".to_string(),
+            ),
+            Self::CoinMetadataMismatch { message } => (
+                StatusCode::CONFLICT,
+                format!("Coin metadata does not match the token contract: {}", message),
+            ),
+            Self::ShuttingDown => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "Service is shutting down, retry shortly".to_string(),
+            ),
             Self::TranscationActionVerifyFailed => (
                 StatusCode::BAD_REQUEST,
                 "Transaction Action Verify Failed".to_string(),
@@ -93,6 +206,34 @@ impl Error {
                 StatusCode::BAD_REQUEST,
                 "Invalid Claim Status For Approve".to_string(),
             ),
+            Self::InvalidClaimStatusForRetry { status } => (
+                StatusCode::BAD_REQUEST,
+                format!("Invalid Claim Status For Retry: {}", status),
+            ),
+            Self::InvalidClaimStatusForCancel { status } => (
+                StatusCode::BAD_REQUEST,
+                format!("Invalid Claim Status For Cancel: {}", status),
+            ),
+            Self::SignerKeysEmpty => (
+                StatusCode::BAD_REQUEST,
+                "Signer Keys File Has No Keys".to_string(),
+            ),
+            Self::InvalidSignerKeysPayload { message } => (
+                StatusCode::BAD_REQUEST,
+                message.to_string(),
+            ),
+            Self::FullAccessKeyNotFound => (
+                StatusCode::BAD_REQUEST,
+                "No Full Access Key In Signer Keys File".to_string(),
+            ),
+            Self::SignerKeyNotFound { public_key } => (
+                StatusCode::NOT_FOUND,
+                format!("Signer Key Not Found: {}", public_key),
+            ),
+            Self::SignerKeysInFlight { public_keys } => (
+                StatusCode::CONFLICT,
+                format!("Signer Keys Still In Flight, Remove Them Again: {}", public_keys.join(", ")),
+            ),
             Self::InvalidAmountConversion => (
                 StatusCode::BAD_REQUEST,
                 "Invalid Amount Conversion".to_string(),
@@ -121,6 +262,10 @@ impl Error {
                 StatusCode::INTERNAL_SERVER_ERROR,
                 message.to_string(),
             ),
+            Self::SignerKeysReloadFailed { message } => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Signer Keys Reload Failed: {}", message),
+            ),
 
             Self::AdapterOutputDB(db::Error::QueryError(ref msg)) if msg == "Transaction rollback" => (
                 StatusCode::CONFLICT,
@@ -0,0 +1,194 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use async_trait::async_trait;
+use tokio::sync::Mutex;
+use chrono::{DateTime, Utc};
+use crate::{
+    adapter::output::{near::rpc_client::NearRpcManager, persistence::db::postgres::ping},
+    config::health::HealthConfig,
+    domain::model::{
+        health::{ComponentHealth, HealthStatus, ReadinessReport, COMPONENT_DB, COMPONENT_RPC, COMPONENT_SIGNER_KEYS, COMPONENT_TREASURY},
+        near::{CircuitState, RpcNodeStatus},
+    },
+    port::output::{rpc_client::RpcClient, DbManager},
+};
+use super::utrait::health_usecase::HealthUsecase;
+
+/// `Ok` carries what was seen, `Err` why the component is down.
+type CheckResult = core::result::Result<String, String>;
+
+pub struct HealthUsecaseImpl<D: DbManager> {
+    db_manager: Arc<D>,
+    near_rpc_manager: Arc<NearRpcManager>,
+    health_config: HealthConfig,
+    /// last component checks, a probe waiting on the lock gets the ones the probe ahead of it ran
+    checked: Mutex<Option<(Instant, Vec<ComponentHealth>)>>,
+}
+
+impl<D> HealthUsecaseImpl<D>
+where
+    D: DbManager + Send + Sync,
+{
+    pub fn new(db_manager: Arc<D>, near_rpc_manager: Arc<NearRpcManager>, health_config: HealthConfig) -> Self {
+        Self {
+            db_manager,
+            near_rpc_manager,
+            health_config,
+            checked: Mutex::new(None),
+        }
+    }
+
+    async fn check_components(&self) -> Vec<ComponentHealth> {
+        let (db, rpc, signer_keys, treasury) = tokio::join!(
+            self.check(COMPONENT_DB, self.check_db()),
+            self.check(COMPONENT_RPC, self.check_rpc()),
+            self.check(COMPONENT_SIGNER_KEYS, self.check_signer_keys()),
+            self.check(COMPONENT_TREASURY, self.check_treasury()),
+        );
+        vec![db, rpc, signer_keys, treasury]
+    }
+
+    async fn check<F>(&self, name: &str, check: F) -> ComponentHealth
+    where
+        F: Future<Output = CheckResult>,
+    {
+        let started = Instant::now();
+        let result = tokio::time::timeout(self.health_config.check_timeout(), check)
+            .await
+            .unwrap_or_else(|_| Err(format!("no answer within {}ms", self.health_config.check_timeout_ms)));
+        let latency_ms = started.elapsed().as_millis() as u64;
+
+        match result {
+            Ok(message) => ComponentHealth { name: name.to_string(), status: HealthStatus::Up, latency_ms, message: Some(message) },
+            Err(message) => {
+                tracing::warn!("[health] {name} is down: {message}");
+                ComponentHealth { name: name.to_string(), status: HealthStatus::Down, latency_ms, message: Some(message) }
+            }
+        }
+    }
+
+    async fn check_db(&self) -> CheckResult {
+        ping(self.db_manager.as_ref()).await.map(|_| "SELECT 1".to_string()).map_err(|e| e.to_string())
+    }
+
+    async fn check_rpc(&self) -> CheckResult {
+        // --- an open breaker already knows the answer, and would refuse the call anyway
+        let breaker = self.near_rpc_manager.circuit_breaker_status();
+        if breaker.state == CircuitState::Open {
+            return Err(format!("circuit breaker open, retry in {}s", breaker.retry_in_secs.unwrap_or_default()));
+        }
+        let status = self.near_rpc_manager.node_status().await.map_err(|e| e.to_string())?;
+        block_freshness(&status, Utc::now(), self.health_config.max_block_age_secs)
+    }
+
+    async fn check_signer_keys(&self) -> CheckResult {
+        match self.near_rpc_manager.signer.signers().len() {
+            0 => Err("no signer keys loaded".to_string()),
+            count => Ok(format!("{count} keys loaded")),
+        }
+    }
+
+    async fn check_treasury(&self) -> CheckResult {
+        let Some(signer) = self.near_rpc_manager.signer.signers().into_iter().next() else {
+            return Err("no signer keys loaded".to_string());
+        };
+        let balance = self.near_rpc_manager.view_account(signer.account_id.as_str()).await.map_err(|e| e.to_string())?;
+        treasury_balance(&balance.account_id, &balance.amount, self.health_config.min_treasury_balance())
+    }
+}
+
+fn is_fresh(checked_at: Instant, now: Instant, ttl: Duration) -> bool {
+    now.saturating_duration_since(checked_at) < ttl
+}
+
+fn block_freshness(status: &RpcNodeStatus, now: DateTime<Utc>, max_block_age_secs: u64) -> CheckResult {
+    if status.syncing {
+        return Err(format!("node is syncing at block {}", status.latest_block_height));
+    }
+    let block_time = DateTime::parse_from_rfc3339(&status.latest_block_time)
+        .map_err(|e| format!("invalid latest block time {}: {e}", status.latest_block_time))?;
+    let age_secs = (now - block_time.with_timezone(&Utc)).num_seconds().max(0) as u64;
+    if age_secs > max_block_age_secs {
+        return Err(format!("latest block {} is {age_secs}s old, over {max_block_age_secs}s", status.latest_block_height));
+    }
+    Ok(format!("block {} is {age_secs}s old", status.latest_block_height))
+}
+
+fn treasury_balance(account_id: &str, amount: &str, min_balance: u128) -> CheckResult {
+    let amount = amount.parse::<u128>().map_err(|e| format!("invalid balance {amount} of {account_id}: {e}"))?;
+    if amount < min_balance {
+        return Err(format!("{account_id} holds {amount} yoctoNEAR, below {min_balance}"));
+    }
+    Ok(format!("{account_id} holds {amount} yoctoNEAR"))
+}
+
+#[async_trait]
+impl<D> HealthUsecase for HealthUsecaseImpl<D>
+where
+    D: DbManager + Send + Sync,
+{
+    async fn readiness(&self) -> ReadinessReport {
+        let components = {
+            let mut checked = self.checked.lock().await;
+            match checked.as_ref() {
+                Some((checked_at, components)) if is_fresh(*checked_at, Instant::now(), self.health_config.cache_ttl()) => components.clone(),
+                _ => {
+                    let components = self.check_components().await;
+                    *checked = Some((Instant::now(), components.clone()));
+                    components
+                }
+            }
+        };
+
+        // --- endpoints and breaker are in memory, always current
+        ReadinessReport::new(
+            components,
+            self.near_rpc_manager.endpoints.statuses(),
+            self.near_rpc_manager.circuit_breaker_status(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use super::*;
+
+    fn node_status(latest_block_time: DateTime<Utc>, syncing: bool) -> RpcNodeStatus {
+        RpcNodeStatus {
+            chain_id: "testnet".to_string(),
+            latest_block_height: 100,
+            latest_block_time: latest_block_time.to_rfc3339(),
+            syncing,
+        }
+    }
+
+    #[test]
+    fn test_block_freshness() {
+        let now = Utc::now();
+
+        assert!(block_freshness(&node_status(now - Duration::seconds(5), false), now, 120).is_ok());
+        assert!(block_freshness(&node_status(now - Duration::seconds(300), false), now, 120).is_err());
+        assert!(block_freshness(&node_status(now, true), now, 120).is_err());
+    }
+
+    #[test]
+    fn test_is_fresh() {
+        use std::time::Duration;
+        let checked_at = Instant::now();
+        let ttl = Duration::from_millis(5_000);
+
+        assert!(is_fresh(checked_at, checked_at + Duration::from_millis(4_999), ttl));
+        assert!(!is_fresh(checked_at, checked_at + ttl, ttl));
+        assert!(!is_fresh(checked_at, checked_at + Duration::from_millis(1), Duration::ZERO));
+    }
+
+    #[test]
+    fn test_treasury_balance() {
+        assert!(treasury_balance("payout.testnet", "5000000000000000000000000", 1_000_000_000_000_000_000_000_000).is_ok());
+        assert!(treasury_balance("payout.testnet", "500", 1_000).is_err());
+        assert!(treasury_balance("payout.testnet", "0", 0).is_ok());
+        assert!(treasury_balance("payout.testnet", "not a number", 0).is_err());
+    }
+}
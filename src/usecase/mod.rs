@@ -1,4 +1,13 @@
 pub mod utrait;
 pub mod reward_claim_usecase_impl;
+pub mod outbox_dispatcher;
+pub mod webhook_usecase_impl;
+pub mod api_key_usecase_impl;
+pub mod coin_admin_usecase_impl;
+pub mod claim_admin_usecase_impl;
+pub mod signer_key_usecase_impl;
+pub mod signer_key_watcher;
+pub mod health_usecase_impl;
+pub mod payout_gate;
 pub mod error;
 
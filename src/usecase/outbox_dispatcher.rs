@@ -0,0 +1,164 @@
+use std::{sync::Arc, time::{Duration, Instant}};
+use tokio::time::MissedTickBehavior;
+use uuid::Uuid;
+use crate::{
+    config::outbox::OutboxConfig,
+    domain::model::{outbox_event::{OutboxEvent, OutboxEventMessage}, webhook_delivery::{NewWebhookDelivery, WebhookDelivery}},
+    port::output::{event_consumer::EventConsumer, outbox_event_repository::OutboxEventRepository, webhook_delivery_repository::WebhookDeliveryRepository, DbManager},
+};
+use super::error::Result;
+
+/// Polls `outbox_event` and pushes due events to every consumer that accepts them.
+///
+/// Delivery is at-least-once: an event is only marked DELIVERED after all accepting consumers
+/// answered successfully, so a partial failure redelivers to every consumer on the next attempt.
+/// Consumers deduplicate on the event id.
+pub struct OutboxDispatcher<D: DbManager, O: OutboxEventRepository, W: WebhookDeliveryRepository> {
+    db_manager: Arc<D>,
+    outbox_event_repo: Arc<O>,
+    webhook_delivery_repo: Arc<W>,
+    consumers: Vec<Arc<dyn EventConsumer>>,
+    config: OutboxConfig,
+}
+
+impl<D, O, W> OutboxDispatcher<D, O, W>
+where
+    D: DbManager + Send + Sync + 'static,
+    O: OutboxEventRepository + Send + Sync + 'static,
+    W: WebhookDeliveryRepository + Send + Sync + 'static,
+{
+    pub fn new(db_manager: Arc<D>, outbox_event_repo: Arc<O>, webhook_delivery_repo: Arc<W>, consumers: Vec<Arc<dyn EventConsumer>>, config: OutboxConfig) -> Self {
+        Self {
+            db_manager,
+            outbox_event_repo,
+            webhook_delivery_repo,
+            consumers,
+            config,
+        }
+    }
+
+    pub async fn run(self: Arc<Self>) {
+        let mut interval = tokio::time::interval(Duration::from_millis(self.config.poll_interval_ms));
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            interval.tick().await;
+            if let Err(err) = self.dispatch_due().await {
+                tracing::error!("[outbox] dispatch failed: {err:?}");
+            }
+        }
+    }
+
+    pub async fn dispatch_due(&self) -> Result<usize> {
+        let events = self.outbox_event_repo
+            .claim_due(self.db_manager.get_connection().await?, self.config.batch_size, self.config.lease_secs)
+            .await?;
+
+        // --- one failing event must not hold back the rest of the batch,
+        // its lease expires and it is claimed again on a later poll
+        let count = events.len();
+        for event in events {
+            let event_id = event.id;
+            if let Err(err) = self.dispatch(event).await {
+                tracing::error!("[outbox] {event_id} dispatch failed: {err:?}");
+            }
+        }
+        Ok(count)
+    }
+
+    async fn dispatch(&self, event: OutboxEvent) -> Result<()> {
+        let event_id = event.id;
+        let attempts = event.attempts + 1;
+        let message = OutboxEventMessage::from(event);
+
+        let mut errors = Vec::new();
+        for consumer in self.consumers.iter().filter(|c| c.accepts(&message.event_type)) {
+            let delivery = deliver_and_record(
+                self.db_manager.as_ref(),
+                self.webhook_delivery_repo.as_ref(),
+                consumer.as_ref(),
+                event_id,
+                attempts,
+                &message,
+            ).await?;
+            if let Some(err) = delivery.error {
+                tracing::warn!("[outbox] {} delivery to {} failed (attempt {attempts}): {err}", message.id, consumer.name());
+                errors.push(err);
+            }
+        }
+
+        let conn = self.db_manager.get_connection().await?;
+        if errors.is_empty() {
+            tracing::debug!("[outbox] {} {} delivered", message.id, message.event_type);
+            self.outbox_event_repo.mark_delivered(conn, event_id).await?;
+        } else if attempts >= self.config.max_attempts {
+            tracing::error!("[outbox] {} {} moved to dead letter after {attempts} attempts", message.id, message.event_type);
+            self.outbox_event_repo.mark_dead_letter(conn, event_id, attempts, errors.join(", ")).await?;
+        } else {
+            let delay_ms = backoff_ms(self.config.initial_backoff_ms, self.config.max_backoff_ms, attempts);
+            let next_attempt_date = chrono::Utc::now().naive_utc() + chrono::Duration::milliseconds(delay_ms as i64);
+            self.outbox_event_repo.mark_retry(conn, event_id, attempts, next_attempt_date, errors.join(", ")).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Delivers `message` to a single consumer and records the attempt in `webhook_delivery`.
+/// A failed delivery is not an error here, it is returned as a row with `success = false`.
+pub async fn deliver_and_record<D, W>(
+    db_manager: &D,
+    webhook_delivery_repo: &W,
+    consumer: &dyn EventConsumer,
+    event_id: Uuid,
+    attempt: i32,
+    message: &OutboxEventMessage,
+) -> Result<WebhookDelivery>
+where
+    D: DbManager + Send + Sync,
+    W: WebhookDeliveryRepository + Send + Sync,
+{
+    let started = Instant::now();
+    let result = consumer.deliver(message).await;
+    let duration_ms = started.elapsed().as_millis().min(i32::MAX as u128) as i32;
+
+    let (success, status_code, error) = match result {
+        Ok(status) => (true, Some(status as i32), None),
+        Err(err) => (false, err.status_code().map(i32::from), Some(err.to_string())),
+    };
+
+    let delivery = webhook_delivery_repo.insert(
+        db_manager.get_connection().await?,
+        NewWebhookDelivery {
+            id: Uuid::new_v4(),
+            event_id,
+            endpoint_name: consumer.name().to_string(),
+            endpoint_url: consumer.endpoint().to_string(),
+            attempt,
+            success,
+            status_code,
+            error,
+            duration_ms,
+        },
+    ).await?;
+    Ok(delivery)
+}
+
+/// Exponential backoff: `initial * 2^(attempts - 1)`, capped at `max`.
+fn backoff_ms(initial_ms: u64, max_ms: u64, attempts: i32) -> u64 {
+    let exponent = (attempts - 1).clamp(0, 32) as u32;
+    initial_ms.saturating_mul(2u64.saturating_pow(exponent)).min(max_ms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::backoff_ms;
+
+    #[test]
+    fn test_backoff_ms() {
+        assert_eq!(backoff_ms(1_000, 60_000, 1), 1_000);
+        assert_eq!(backoff_ms(1_000, 60_000, 2), 2_000);
+        assert_eq!(backoff_ms(1_000, 60_000, 4), 8_000);
+        assert_eq!(backoff_ms(1_000, 60_000, 10), 60_000);
+        assert_eq!(backoff_ms(1_000, 60_000, 100), 60_000);
+    }
+}
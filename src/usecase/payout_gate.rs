@@ -0,0 +1,92 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+use super::error::{Error, Result};
+
+/// Counts the payouts in flight, from the claim check to the persisted status, so a shutdown can wait for them.
+/// Once closed it refuses new payouts with `ShuttingDown`.
+#[derive(Debug, Default)]
+pub struct PayoutGate {
+    closed: AtomicBool,
+    in_flight: AtomicUsize,
+    idle: Notify,
+}
+
+/// Held for the whole payout, dropping it ends it.
+#[derive(Debug)]
+pub struct PayoutPermit {
+    gate: Arc<PayoutGate>,
+}
+
+impl PayoutGate {
+    pub fn enter(self: &Arc<Self>) -> Result<PayoutPermit> {
+        // --- counted before the check: a concurrent `drained` either sees this payout or it sees the gate closed
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        let permit = PayoutPermit { gate: Arc::clone(self) };
+        if self.closed.load(Ordering::SeqCst) {
+            return Err(Error::ShuttingDown);
+        }
+        Ok(permit)
+    }
+
+    pub fn close(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::SeqCst)
+    }
+
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Waits up to `timeout` for the payouts in flight to end, `false` when some are still running.
+    pub async fn drained(&self, timeout: Duration) -> bool {
+        tokio::time::timeout(timeout, async {
+            loop {
+                // --- registered before the check, so the last permit's notify is not missed
+                let idle = self.idle.notified();
+                if self.in_flight() == 0 {
+                    return;
+                }
+                idle.await;
+            }
+        })
+        .await
+        .is_ok()
+    }
+}
+
+impl Drop for PayoutPermit {
+    fn drop(&mut self) {
+        if self.gate.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.gate.idle.notify_waiters();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_payout_gate() {
+        let gate = Arc::new(PayoutGate::default());
+        let permit = gate.enter().unwrap();
+        assert_eq!(gate.in_flight(), 1);
+
+        gate.close();
+        assert!(matches!(gate.enter(), Err(Error::ShuttingDown)));
+        assert_eq!(gate.in_flight(), 1);
+        assert!(!gate.drained(Duration::from_millis(10)).await);
+
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            drop(permit);
+        });
+        assert!(gate.drained(Duration::from_secs(1)).await);
+        assert_eq!(gate.in_flight(), 0);
+    }
+}
@@ -3,15 +3,17 @@ use async_trait::async_trait;
 use bigdecimal::BigDecimal;
 use uuid::Uuid;
 use crate::{
-    adapter::output::near::{retry_async, rpc_client::NearRpcManager, MAX_RETRY_COUNT, RETRY_DELAY}, domain::model::{
-        coin::{Coin, CoinType}, coin_network::CoinNetwork, near::{TransactionResultResponse, TransferActionType}, network::Network, reward_claim::{
+    adapter::output::near::rpc_client::NearRpcManager, domain::model::{
+        audit_log::AuditActor, coin::{Coin, CoinType}, coin_network::CoinNetwork, near::{TransactionResultResponse, TransferActionType}, network::Network, price::RewardClaimValuation, reward_claim::{
             CombinedRewardClaimResponse, NewRewardClaim, NewRewardClaimPayload, ResourceType, RewardClaim, RewardClaimStatus
         }, reward_claim_detail::{NewRewardClaimDetail, RewardClaimDetail}
     }, port::output::{
-        coin_network_repository::CoinNetworkRepository, detailed_posting_repository::DetailedPostingRepository, mission_submit_repository::MissionSubmitRepository, reward_claim_repository::RewardClaimRepository, rpc_client::RpcClient, DbManager, UserRepository
+        coin_network_repository::CoinNetworkRepository, detailed_posting_repository::DetailedPostingRepository, mission_submit_repository::MissionSubmitRepository, price_feed::PriceFeed, reward_claim_repository::RewardClaimRepository, rpc_client::RpcClient, DbManager, UserRepository
     }
 };
+use crate::config::metrics::metrics;
 use super::error::{Error, Result};
+use super::payout_gate::PayoutGate;
 use super::utrait::reward_claim_usecase::RewardClaimUsecase;
 use std::str::FromStr;
 use near_primitives::types::AccountId;
@@ -24,6 +26,9 @@ pub struct RewardClaimUsecaseImpl<D: DbManager, R: RewardClaimRepository, C: Coi
     user_repo: Arc<U>,
     mission_submit_repo: Arc<MS>,
     detailed_posting_repo: Arc<DP>,
+    /// `None` pays claims without a fiat value snapshot
+    price_feed: Option<Arc<dyn PriceFeed>>,
+    payout_gate: Arc<PayoutGate>,
 }
 
 impl<D, R, C, U, MS, DP> RewardClaimUsecaseImpl<D, R, C, U, MS, DP>
@@ -35,7 +40,7 @@ where
     MS: MissionSubmitRepository + Send + Sync,
     DP: DetailedPostingRepository + Send + Sync,
 {
-    pub fn new(db_manger: Arc<D>, reward_claim_repo: Arc<R>, coin_network_repo: Arc<C>, near_rpc_manager: Arc<NearRpcManager>, user_repo: Arc<U>, mission_submit_repo: Arc<MS>, detailed_posting_repo: Arc<DP>) -> Self {
+    pub fn new(db_manger: Arc<D>, reward_claim_repo: Arc<R>, coin_network_repo: Arc<C>, near_rpc_manager: Arc<NearRpcManager>, user_repo: Arc<U>, mission_submit_repo: Arc<MS>, detailed_posting_repo: Arc<DP>, price_feed: Option<Arc<dyn PriceFeed>>, payout_gate: Arc<PayoutGate>) -> Self {
         Self {
             db_manager: db_manger,
             reward_claim_repo,
@@ -44,6 +49,8 @@ where
             user_repo,
             mission_submit_repo,
             detailed_posting_repo,
+            price_feed,
+            payout_gate,
         }
     }
 
@@ -86,7 +93,7 @@ where
         Ok(resource_type)
     }
 
-    async fn handle_existing_reward_claim(&self, existed_reward_claim: RewardClaim) -> Result<()> {
+    async fn handle_existing_reward_claim(&self, existed_reward_claim: RewardClaim, actor: &AuditActor) -> Result<()> {
         match existed_reward_claim.reward_claim_status {
             // --- user 중복 요청 방지 (only READY, TRANSACTION_APPROVED)
             RewardClaimStatus::Ready => {
@@ -97,6 +104,10 @@ where
                 tracing::error!("[TransactionApproved] Reward Claim Duplicate: Resource Id: {}, Resource Type: {}, User Id: {}", existed_reward_claim.resource_id, existed_reward_claim.resource_type, existed_reward_claim.user_id);
                 return Err(Error::RewardClaimDuplicate);
             }
+            RewardClaimStatus::Cancelled => {
+                tracing::error!("[Cancelled] Reward Claim Cancelled: Resource Id: {}, Resource Type: {}, User Id: {}", existed_reward_claim.resource_id, existed_reward_claim.resource_type, existed_reward_claim.user_id);
+                return Err(Error::RewardClaimCancelled);
+            }
             RewardClaimStatus::TransactionFailed => {
                 // --- 실패한 트랜잭션 재시도 (TRANSACTION_FAILED -> READY)
                 tracing::debug!(
@@ -109,14 +120,15 @@ where
                     self.db_manager.get_connection().await?.into(),
                     existed_reward_claim.id,
                     RewardClaimStatus::Ready,
-                    true
+                    true,
+                    actor.clone()
                 ).await?;
             }
         }
         Ok(())
     }
 
-    async fn create_new_reward_claim(&self, payload: &NewRewardClaimPayload, resource_type: ResourceType, amount_in_smallest_unit: BigDecimal, user_id: Uuid) -> Result<RewardClaim> {
+    async fn create_new_reward_claim(&self, payload: &NewRewardClaimPayload, resource_type: ResourceType, amount_in_smallest_unit: BigDecimal, user_id: Uuid, actor: &AuditActor) -> Result<RewardClaim> {
         let new_reward_claim = NewRewardClaim {
             id: Uuid::new_v4(),
             resource_id: payload.resource_id,
@@ -128,10 +140,26 @@ where
             user_address: payload.user_address.clone(),
         };
         
-        let reward_claim = self.reward_claim_repo.insert(self.db_manager.get_connection().await?.into(), new_reward_claim).await?;
+        let reward_claim = self.reward_claim_repo.insert(self.db_manager.get_connection().await?.into(), new_reward_claim, actor.clone()).await?;
         Ok(reward_claim)
     }
 
+    /// Best effort, a payout that already went through on chain is never failed for a missing price.
+    async fn snapshot_valuation(&self, reward_claim: &RewardClaim, coin: &Coin, actor: &AuditActor) -> Option<RewardClaim> {
+        let price_feed = self.price_feed.as_ref()?;
+        let rates = price_feed.get_rates(&coin.symbol).await
+            .map_err(|e| tracing::warn!("[price] {} has no rates for {}, Reward Claim {} is not valued: {}", price_feed.name(), coin.symbol, reward_claim.id, e))
+            .ok()?;
+
+        let valuation = RewardClaimValuation::new(&reward_claim.amount, coin.decimals, rates);
+        let conn = self.db_manager.get_connection().await
+            .map_err(|e| tracing::warn!("[price] Reward Claim {} valuation not stored: {}", reward_claim.id, e))
+            .ok()?;
+        self.reward_claim_repo.update_valuation(conn.into(), reward_claim.id, valuation, actor.clone()).await
+            .map_err(|e| tracing::warn!("[price] Reward Claim {} valuation not stored: {}", reward_claim.id, e))
+            .ok()
+    }
+
 }
 
 #[async_trait]
@@ -171,8 +199,12 @@ where
         Ok(combined_responses)
     }
 
-    async fn create_reward_claim(&self, user_id: Uuid, payload: NewRewardClaimPayload) -> Result<CombinedRewardClaimResponse> {
+    /// Runs in a `reward_claim.create` span, the parent of its db and rpc spans, that carries the claim id and tx hash once known.
+    #[tracing::instrument(name = "reward_claim.create", skip_all, fields(user_id = %user_id, claim_id = tracing::field::Empty, tx_hash = tracing::field::Empty))]
+    async fn create_reward_claim(&self, user_id: Uuid, payload: NewRewardClaimPayload, actor: AuditActor) -> Result<CombinedRewardClaimResponse> {
         let db_manager = &self.db_manager;
+        // --- held until the final status is stored, a shutdown waits for it
+        let _payout = self.payout_gate.enter()?;
     
         // --- user validation
         self.validate_user(user_id).await?;
@@ -192,21 +224,39 @@ where
                 Error::CoinNetworkIdNotFound
             })?;
 
+        // --- disabled coin networks keep their claims but take no new ones, retries included
+        if !coin_network.is_available(&coin, &network) {
+            tracing::error!("Coin Network Disabled: {}", payload.coin_network_id.to_string());
+            return Err(Error::CoinNetworkDisabled);
+        }
+
+        // --- a wrong decimals would scale the payout by a power of ten, wait for an admin to resolve it
+        if let Some(mismatch) = &coin_network.metadata_mismatch {
+            tracing::error!("Coin Metadata Mismatch: {}: {}", payload.coin_network_id.to_string(), mismatch);
+            return Err(Error::CoinMetadataMismatch { message: mismatch.clone() });
+        }
+
+        // --- while the rpc circuit breaker is open no claim is created or flipped back to READY
+        self.near_rpc_manager.ensure_available()?;
 
         let scale_factor = BigDecimal::from_str(&format!("1e{}", coin.decimals)).expect("Invalid decimal format");
         let amount_decimal: BigDecimal = BigDecimal::from_str(&payload.amount).expect("Invalid amount format");
         let amount_in_smallest_unit = amount_decimal * scale_factor;
         
+        let coin_network_label = format!("{}:{}", coin.symbol, network.code);
         let existed_reward_claim_result = self.reward_claim_repo.get_by_resource_and_user(db_manager.get_connection().await?.into(), resource_type.clone(), payload.resource_id, user_id).await;
         let reward_claim = match existed_reward_claim_result {
             Ok(existed_reward_claim) => {
-                self.handle_existing_reward_claim(existed_reward_claim.clone()).await?;
+                self.handle_existing_reward_claim(existed_reward_claim.clone(), &actor).await?;
                 existed_reward_claim
             }
             Err(_) => { 
-                self.create_new_reward_claim(&payload, resource_type, amount_in_smallest_unit.clone(), user_id).await?
+                let reward_claim = self.create_new_reward_claim(&payload, resource_type, amount_in_smallest_unit.clone(), user_id, &actor).await?;
+                metrics().claims_created.with_label_values(&[coin_network_label.as_str()]).inc();
+                reward_claim
             }
         };
+        tracing::Span::current().record("claim_id", tracing::field::display(reward_claim.id));
 
         let tx_result_response = match coin.coin_type {
             CoinType::Native => {
@@ -222,23 +272,29 @@ where
 
         let response = match tx_result_response {
             Ok(response) => response,
+            // --- the breaker opening on the way included: an FT storage_deposit may already be through,
+            // and retry_claim takes the claim back from TRANSACTION_FAILED once the chain shows nothing paid
             Err(err) => {
                 self.reward_claim_repo.update_status(
                     db_manager.get_connection().await?.into(),
                     reward_claim.id,
                     RewardClaimStatus::TransactionFailed,
-                    false
+                    false,
+                    actor.clone()
                 ).await?;
+                metrics().claims_finalized.with_label_values(&[coin_network_label.as_str(), &RewardClaimStatus::TransactionFailed.to_string()]).inc();
                 return Err(err);
             }
         };
 
+        tracing::Span::current().record("tx_hash", tracing::field::display(&response.transaction_hash));
         let reward_claim_status = if response.has_errors {
             RewardClaimStatus::TransactionFailed
         } else {
             RewardClaimStatus::TransactionApproved
         };
-        let reward_claim = self.reward_claim_repo.update_status(db_manager.get_connection().await?.into(), reward_claim.id, reward_claim_status, false).await?;
+        let reward_claim = self.reward_claim_repo.update_status(db_manager.get_connection().await?.into(), reward_claim.id, reward_claim_status, false, actor.clone()).await?;
+        metrics().claims_finalized.with_label_values(&[coin_network_label.as_str(), &reward_claim.reward_claim_status.to_string()]).inc();
 
         let new_reward_claim_detail = NewRewardClaimDetail {
             id: Uuid::new_v4(),
@@ -248,6 +304,14 @@ where
             sended_user_address: response.receiver_id.to_string(),
         };
         let claim_detail = self.reward_claim_repo.insert_detail(db_manager.get_connection().await?.into(), new_reward_claim_detail).await?;
+
+        // --- fiat value at payout time for accounting and grant reports, after the tx hash is stored
+        // so a slow price feed never delays the record of a payout already on chain
+        let reward_claim = if response.has_errors {
+            reward_claim
+        } else {
+            self.snapshot_valuation(&reward_claim, &coin, &actor).await.unwrap_or(reward_claim)
+        };
         if response.has_errors {
             Err(Error::TransactionActionFailed { message: response.error_details.join(", ") })
         }else {
@@ -256,23 +320,12 @@ where
     }
 
     async fn process_native_transfer(&self, payload: NewRewardClaimPayload, amount_in_smallest_unit: BigDecimal) -> Result<TransactionResultResponse> {
-        retry_async(
-            || {
-                let near_rpc_manager = self.near_rpc_manager.clone();
-                let user_address = payload.user_address.clone();
-                let amount_in_smallest_unit = amount_in_smallest_unit.clone();
-                Box::pin(async move {
-                    near_rpc_manager.process_transfer_action(
-                        TransferActionType::Native {
-                            user_address: user_address.to_string(),
-                            amount_in_smallest_unit: amount_in_smallest_unit.clone(),
-                        },
-                        false
-                    ).await
-                })
+        self.near_rpc_manager.process_transfer_action(
+            TransferActionType::Native {
+                user_address: payload.user_address.to_string(),
+                amount_in_smallest_unit,
             },
-            MAX_RETRY_COUNT,
-            RETRY_DELAY
+            false
         ).await.map_err(Into::into)
     }
     
@@ -280,41 +333,18 @@ where
         let contract_address = coin_network.contract_address.as_ref().ok_or_else(|| Error::InternalServerError { message: "contract_address is empty".to_string() })?;
     
         // todo: storage_deposit check
-        let _ = retry_async(
-            || {
-                let near_rpc_manager = self.near_rpc_manager.clone();
-                let contract_address = contract_address.clone();
-                let user_address = payload.user_address.clone();
-                Box::pin(async move {
-                    near_rpc_manager.send_storage_deposit(
-                        AccountId::from_str(&contract_address).unwrap(),
-                        AccountId::from_str(user_address.as_str()).unwrap(),
-                    ).await
-                })
-            },
-            MAX_RETRY_COUNT,
-            RETRY_DELAY
+        let _ = self.near_rpc_manager.send_storage_deposit(
+            AccountId::from_str(contract_address).unwrap(),
+            AccountId::from_str(payload.user_address.as_str()).unwrap(),
         ).await?;
     
-        retry_async(
-            || {
-                let near_rpc_manager = self.near_rpc_manager.clone();
-                let contract_address = contract_address.clone();
-                let user_address = payload.user_address.clone();
-                let amount_in_smallest_unit = amount_in_smallest_unit.clone();
-                Box::pin(async move {
-                    near_rpc_manager.process_transfer_action(
-                        TransferActionType::FtTransfer {
-                            ft_contract_id: AccountId::from_str(&contract_address).unwrap(),
-                            user_address: user_address.to_string(),
-                            amount_in_smallest_unit: amount_in_smallest_unit.clone(),
-                        },
-                        false
-                    ).await
-                })
+        self.near_rpc_manager.process_transfer_action(
+            TransferActionType::FtTransfer {
+                ft_contract_id: AccountId::from_str(contract_address).unwrap(),
+                user_address: payload.user_address.to_string(),
+                amount_in_smallest_unit,
             },
-            MAX_RETRY_COUNT,
-            RETRY_DELAY
+            false
         ).await.map_err(Into::into)
     }
 }
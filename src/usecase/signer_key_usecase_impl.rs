@@ -0,0 +1,310 @@
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use async_trait::async_trait;
+use near_crypto::{InMemorySigner, KeyType, PublicKey, SecretKey};
+use near_primitives::account::{AccessKeyPermission, FunctionCallPermission};
+use near_primitives::types::AccountId;
+use tokio::sync::Mutex;
+use crate::{
+    adapter::output::{keystore, near::rpc_client::NearRpcManager},
+    config::near::{KeyRotatingSignerWrapper, NearNetworkConfig},
+    domain::model::{
+        audit_log::{AuditActor, NewAuditLog, ACTION_SIGNER_KEY_ADD, ACTION_SIGNER_KEY_REMOVE, ENTITY_SIGNER_KEY},
+        near::{AddSignerKeysPayload, RemoveSignerKeysPayload, SignerKey, SignerKeyChanges, ACCESS_KEY_FULL_ACCESS},
+    },
+    port::output::{audit_log_repository::AuditLogRepository, rpc_client::RpcClient, DbManager},
+};
+use super::error::{Error, Result};
+use super::utrait::signer_key_usecase::SignerKeyUsecase;
+
+pub struct SignerKeyUsecaseImpl<D: DbManager, A: AuditLogRepository> {
+    db_manager: Arc<D>,
+    audit_log_repo: Arc<A>,
+    near_rpc_manager: Arc<NearRpcManager>,
+    near_network_config: NearNetworkConfig,
+    signer: KeyRotatingSignerWrapper,
+    /// the watcher, reloads and key provisioning all rewrite the same key set
+    keys_lock: Mutex<()>,
+}
+
+impl<D, A> SignerKeyUsecaseImpl<D, A>
+where
+    D: DbManager + Send + Sync,
+    A: AuditLogRepository + Send + Sync,
+{
+    pub fn new(db_manager: Arc<D>, audit_log_repo: Arc<A>, near_rpc_manager: Arc<NearRpcManager>, near_network_config: NearNetworkConfig, signer: KeyRotatingSignerWrapper) -> Self {
+        Self {
+            db_manager,
+            audit_log_repo,
+            near_rpc_manager,
+            near_network_config,
+            signer,
+            keys_lock: Mutex::new(()),
+        }
+    }
+
+    async fn audit_key(&self, actor: &AuditActor, action: &str, key: &SignerKey) -> Result<()> {
+        let (before, after) = if action == ACTION_SIGNER_KEY_ADD { (None, Some(key)) } else { (Some(key), None) };
+        self.audit_log_repo.insert(
+            self.db_manager.get_connection().await?,
+            NewAuditLog::new(actor, action, ENTITY_SIGNER_KEY, &key.public_key, before, after),
+        ).await?;
+        Ok(())
+    }
+
+    /// argon2 and file io, off the async workers
+    async fn load_signers(&self) -> Result<Vec<InMemorySigner>> {
+        let near_network_config = self.near_network_config.clone();
+        spawn_keys_file_task(move || near_network_config.try_load_signers()).await
+    }
+
+    async fn stage_signers(&self, signers: Vec<InMemorySigner>) -> Result<()> {
+        let near_network_config = self.near_network_config.clone();
+        spawn_keys_file_task(move || near_network_config.stage_signers(&signers)).await
+    }
+
+    /// Swaps the signer to the keys file, callers hold `keys_lock`.
+    async fn swap_keys(&self, actor: &AuditActor) -> Result<SignerKeyChanges> {
+        let signers = self.load_signers().await?;
+        if signers.is_empty() {
+            return Err(Error::SignerKeysEmpty);
+        }
+
+        let next: Vec<SignerKey> = signers.iter().map(signer_key).collect();
+        let changes = SignerKeyChanges::between(&self.signer.public_keys(), &next);
+        if changes.is_empty() {
+            return Ok(changes);
+        }
+
+        for key in changes.added.iter() {
+            self.audit_key(actor, ACTION_SIGNER_KEY_ADD, key).await?;
+        }
+        for key in changes.removed.iter() {
+            self.audit_key(actor, ACTION_SIGNER_KEY_REMOVE, key).await?;
+        }
+
+        let changes = self.signer.replace(signers);
+        tracing::info!(
+            "[signer] keys reloaded by {}: {} added, {} removed",
+            actor.actor_id, changes.added.len(), changes.removed.len(),
+        );
+        Ok(changes)
+    }
+
+    /// First key of the keys file that the chain reports as full access.
+    async fn full_access_signer(&self, signers: &[InMemorySigner]) -> Result<InMemorySigner> {
+        for signer in signers {
+            let access_key = self.near_rpc_manager
+                .view_access_key(signer.account_id.as_str(), &signer.public_key.to_string())
+                .await;
+            if matches!(access_key, Ok(access_key) if access_key.permission == ACCESS_KEY_FULL_ACCESS) {
+                return Ok(signer.clone());
+            }
+        }
+        Err(Error::FullAccessKeyNotFound)
+    }
+}
+
+fn signer_key(signer: &InMemorySigner) -> SignerKey {
+    SignerKey { account_id: signer.account_id.to_string(), public_key: signer.public_key.to_string() }
+}
+
+async fn spawn_keys_file_task<T, F>(task: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> keystore::error::Result<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(task)
+        .await
+        .map_err(|e| Error::SignerKeysReloadFailed { message: e.to_string() })?
+        .map_err(|e| {
+            tracing::error!("[signer] keys file failed: {e}");
+            Error::SignerKeysReloadFailed { message: e.to_string() }
+        })
+}
+
+/// `count` fresh keys of `account_id`.
+fn generate_signers(account_id: &AccountId, count: usize) -> Vec<InMemorySigner> {
+    (0..count)
+        .map(|_| InMemorySigner::from_secret_key(account_id.clone(), SecretKey::from_random(KeyType::ED25519)))
+        .collect()
+}
+
+/// The keys `payload` names, or the last `count` of the keys file. Named keys may be gone from the keys file
+/// already, a removal that timed out on the drain is run again that way. The full-access key is never removed.
+fn keys_to_remove(payload: &RemoveSignerKeysPayload, signers: &[InMemorySigner], full_access_signer: &InMemorySigner) -> Result<Vec<PublicKey>> {
+    let public_keys: Vec<PublicKey> = match payload.count {
+        Some(count) => signers
+            .iter()
+            .rev()
+            .filter(|signer| signer.public_key != full_access_signer.public_key)
+            .take(count)
+            .map(|signer| signer.public_key.clone())
+            .collect(),
+        None => payload.public_keys
+            .iter()
+            .map(|public_key| PublicKey::from_str(public_key)
+                .map_err(|e| Error::InvalidSignerKeysPayload { message: format!("invalid public key {public_key}: {e}") }))
+            .collect::<Result<Vec<PublicKey>>>()?,
+    };
+    if public_keys.contains(&full_access_signer.public_key) {
+        return Err(Error::InvalidSignerKeysPayload { message: "the full-access key signing the batch can not be deleted".to_string() });
+    }
+    Ok(public_keys)
+}
+
+fn access_key_permission(payload: &AddSignerKeysPayload, account_id: &str) -> AccessKeyPermission {
+    if payload.full_access {
+        return AccessKeyPermission::FullAccess;
+    }
+    AccessKeyPermission::FunctionCall(FunctionCallPermission {
+        allowance: payload.allowance.as_ref().and_then(|allowance| allowance.parse::<u128>().ok()),
+        receiver_id: payload.receiver_id.clone().unwrap_or_else(|| account_id.to_string()),
+        method_names: payload.method_names.clone(),
+    })
+}
+
+#[async_trait]
+impl<D, A> SignerKeyUsecase for SignerKeyUsecaseImpl<D, A>
+where
+    D: DbManager + Send + Sync,
+    A: AuditLogRepository + Send + Sync,
+{
+    fn list_keys(&self) -> Vec<SignerKey> {
+        self.signer.public_keys()
+    }
+
+    async fn reload_keys(&self, actor: AuditActor) -> Result<SignerKeyChanges> {
+        let _guard = self.keys_lock.lock().await;
+        self.swap_keys(&actor).await
+    }
+
+    async fn add_keys(&self, payload: AddSignerKeysPayload, actor: AuditActor) -> Result<SignerKeyChanges> {
+        payload.validate().map_err(|message| Error::InvalidSignerKeysPayload { message })?;
+        let _guard = self.keys_lock.lock().await;
+
+        let mut signers = self.load_signers().await?;
+        let full_access_signer = self.full_access_signer(&signers).await?;
+        let account_id = full_access_signer.account_id.clone();
+        let new_signers = generate_signers(&account_id, payload.count);
+        let public_keys: Vec<PublicKey> = new_signers.iter().map(|signer| signer.public_key.clone()).collect();
+        signers.extend(new_signers);
+
+        // --- the secrets are on disk before the chain knows their public keys
+        self.stage_signers(signers).await?;
+        let permission = access_key_permission(&payload, account_id.as_str());
+        let failure = match self.near_rpc_manager.add_access_keys(&full_access_signer, &public_keys, permission).await {
+            Ok(response) if !response.has_errors => None,
+            Ok(response) => Some(Error::TransactionActionFailed { message: response.error_details.join(", ") }),
+            Err(e) => Some(Error::from(e)),
+        };
+        if let Some(err) = failure {
+            self.near_network_config.discard_staged_signers();
+            tracing::error!(
+                "[signer] AddKey batch failed, check the account for orphaned keys: {:?}",
+                public_keys.iter().map(PublicKey::to_string).collect::<Vec<String>>(),
+            );
+            return Err(err);
+        }
+
+        self.near_network_config.commit_staged_signers()
+            .map_err(|e| Error::SignerKeysReloadFailed { message: e.to_string() })?;
+        self.swap_keys(&actor).await
+    }
+
+    async fn remove_keys(&self, payload: RemoveSignerKeysPayload, actor: AuditActor) -> Result<SignerKeyChanges> {
+        payload.validate().map_err(|message| Error::InvalidSignerKeysPayload { message })?;
+        let _guard = self.keys_lock.lock().await;
+
+        let signers = self.load_signers().await?;
+        let full_access_signer = self.full_access_signer(&signers).await?;
+        let public_keys = keys_to_remove(&payload, &signers, &full_access_signer)?;
+        if public_keys.is_empty() {
+            return Ok(SignerKeyChanges::default());
+        }
+        for public_key in public_keys.iter().filter(|public_key| !signers.iter().any(|signer| &signer.public_key == *public_key)) {
+            self.near_rpc_manager
+                .view_access_key(full_access_signer.account_id.as_str(), &public_key.to_string())
+                .await
+                .map_err(|_| Error::SignerKeyNotFound { public_key: public_key.to_string() })?;
+        }
+
+        // --- out of the signer before the DeleteKey, nothing new is signed with a key about to go
+        let remaining: Vec<InMemorySigner> = signers
+            .iter()
+            .filter(|signer| !public_keys.contains(&signer.public_key))
+            .cloned()
+            .collect();
+        let changes = if remaining.len() < signers.len() {
+            self.stage_signers(remaining).await?;
+            self.near_network_config.commit_staged_signers()
+                .map_err(|e| Error::SignerKeysReloadFailed { message: e.to_string() })?;
+            self.swap_keys(&actor).await?
+        } else {
+            SignerKeyChanges::default()
+        };
+
+        // --- and the transactions already signed with them reach the chain first, or they fail on a deleted key
+        let drain_timeout = Duration::from_secs(self.near_network_config.keys_drain_timeout_secs);
+        if !self.near_rpc_manager.signer_keys_drained(&public_keys, drain_timeout).await {
+            let public_keys: Vec<String> = public_keys.iter().map(PublicKey::to_string).collect();
+            tracing::warn!("[signer] keys still in flight after {drain_timeout:?}, not deleted: {public_keys:?}");
+            return Err(Error::SignerKeysInFlight { public_keys });
+        }
+
+        match self.near_rpc_manager.delete_access_keys(&full_access_signer, &public_keys).await {
+            Ok(response) if !response.has_errors => Ok(changes),
+            Ok(response) => Err(Error::TransactionActionFailed { message: response.error_details.join(", ") }),
+            Err(e) => Err(Error::from(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signer(seed: &str) -> InMemorySigner {
+        InMemorySigner::from_seed("payout.testnet".parse().unwrap(), KeyType::ED25519, seed)
+    }
+
+    fn remove_payload(public_keys: Vec<String>, count: Option<usize>) -> RemoveSignerKeysPayload {
+        RemoveSignerKeysPayload { public_keys, count }
+    }
+
+    #[test]
+    fn test_generate_signers() {
+        let account_id: AccountId = "payout.testnet".parse().unwrap();
+        let signers = generate_signers(&account_id, 3);
+
+        assert_eq!(signers.len(), 3);
+        assert!(signers.iter().all(|signer| signer.account_id == account_id));
+        assert_ne!(signers[0].public_key, signers[1].public_key);
+        assert_ne!(signers[1].public_key, signers[2].public_key);
+    }
+
+    #[test]
+    fn test_keys_to_remove() {
+        let signers = vec![signer("full"), signer("a"), signer("b")];
+        let full_access = &signers[0];
+
+        let by_count = keys_to_remove(&remove_payload(vec![], Some(5)), &signers, full_access).unwrap();
+        assert_eq!(by_count, vec![signer("b").public_key, signer("a").public_key]);
+
+        // --- a key already out of the keys file is still named, for a removal run again
+        let gone = signer("gone").public_key.to_string();
+        let by_key = keys_to_remove(&remove_payload(vec![gone.clone()], None), &signers, full_access).unwrap();
+        assert_eq!(by_key, vec![signer("gone").public_key]);
+
+        let full = full_access.public_key.to_string();
+        assert!(matches!(
+            keys_to_remove(&remove_payload(vec![full], None), &signers, full_access),
+            Err(Error::InvalidSignerKeysPayload { .. })
+        ));
+        assert!(matches!(
+            keys_to_remove(&remove_payload(vec!["not a key".to_string()], None), &signers, full_access),
+            Err(Error::InvalidSignerKeysPayload { .. })
+        ));
+    }
+}
@@ -0,0 +1,47 @@
+use std::{path::PathBuf, sync::Arc, time::{Duration, SystemTime}};
+use tokio::time::MissedTickBehavior;
+use crate::domain::model::audit_log::AuditActor;
+use super::utrait::signer_key_usecase::SignerKeyUsecase;
+
+/// Polls the modification time of the keys file and reloads the signer when it moves.
+/// A failed reload keeps the current keys and is retried on the next change.
+pub struct SignerKeyWatcher {
+    signer_key_usecase: Arc<dyn SignerKeyUsecase + Send + Sync>,
+    keys_filename: PathBuf,
+    poll_interval: Duration,
+}
+
+impl SignerKeyWatcher {
+    pub fn new(signer_key_usecase: Arc<dyn SignerKeyUsecase + Send + Sync>, keys_filename: PathBuf, poll_interval: Duration) -> Self {
+        Self {
+            signer_key_usecase,
+            keys_filename,
+            poll_interval,
+        }
+    }
+
+    pub async fn run(self) {
+        let mut interval = tokio::time::interval(self.poll_interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut last_modified = self.modified();
+
+        loop {
+            interval.tick().await;
+            let modified = self.modified();
+            if modified.is_none() || modified == last_modified {
+                continue;
+            }
+            last_modified = modified;
+
+            match self.signer_key_usecase.reload_keys(AuditActor::system()).await {
+                Ok(changes) if changes.is_empty() => tracing::debug!("[signer] keys file changed, key set unchanged"),
+                Ok(_) => {}
+                Err(err) => tracing::error!("[signer] keys reload failed, keeping the current keys: {err:?}"),
+            }
+        }
+    }
+
+    fn modified(&self) -> Option<SystemTime> {
+        std::fs::metadata(&self.keys_filename).and_then(|metadata| metadata.modified()).ok()
+    }
+}
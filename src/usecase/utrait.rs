@@ -1 +1,7 @@
-pub mod reward_claim_usecase;
\ No newline at end of file
+pub mod reward_claim_usecase;
+pub mod webhook_usecase;
+pub mod api_key_usecase;
+pub mod coin_admin_usecase;
+pub mod claim_admin_usecase;
+pub mod signer_key_usecase;
+pub mod health_usecase;
\ No newline at end of file
@@ -0,0 +1,16 @@
+use axum::async_trait;
+use uuid::Uuid;
+use crate::domain::model::api_key::{ApiKey, NewApiKeyPayload, RotateApiKeyPayload};
+use crate::domain::model::audit_log::AuditActor;
+use crate::usecase::error::Result;
+
+#[async_trait]
+pub trait ApiKeyUsecase {
+    /// Resolves a plaintext `x-api-key` to an active key.
+    async fn authenticate(&self, raw_key: &str) -> Result<ApiKey>;
+    /// Returns the plaintext key together with the stored row.
+    async fn create_api_key(&self, payload: NewApiKeyPayload, actor: AuditActor) -> Result<(String, ApiKey)>;
+    async fn list_api_keys(&self) -> Result<Vec<ApiKey>>;
+    async fn rotate_api_key(&self, api_key_id: Uuid, payload: RotateApiKeyPayload, actor: AuditActor) -> Result<(String, ApiKey)>;
+    async fn revoke_api_key(&self, api_key_id: Uuid, actor: AuditActor) -> Result<ApiKey>;
+}
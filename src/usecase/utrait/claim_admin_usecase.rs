@@ -0,0 +1,20 @@
+use axum::async_trait;
+use chrono::NaiveDateTime;
+use uuid::Uuid;
+use crate::domain::model::audit_log::AuditActor;
+use crate::domain::model::reward_claim::{CombinedRewardClaimResponse, ReconcileEntry, RewardClaim, RewardClaimAdminResponse, RewardClaimFilter};
+use crate::usecase::error::Result;
+
+/// Operator tasks on reward claims, used by the `payment-admin` cli.
+#[async_trait]
+pub trait ClaimAdminUsecase {
+    async fn list_claims(&self, filter: RewardClaimFilter) -> Result<Vec<RewardClaim>>;
+    async fn get_claim(&self, reward_claim_id: Uuid) -> Result<RewardClaimAdminResponse>;
+    /// Pays a TRANSACTION_FAILED claim again through the regular claim flow,
+    /// once the chain reports its last transaction as failed or unknown.
+    async fn retry_claim(&self, reward_claim_id: Uuid, actor: AuditActor) -> Result<CombinedRewardClaimResponse>;
+    /// Closes a TRANSACTION_FAILED or stale READY claim for good, with the same check of its last transaction.
+    async fn cancel_claim(&self, reward_claim_id: Uuid, actor: AuditActor) -> Result<RewardClaim>;
+    /// Compares claims updated after `updated_after` with their transactions on chain.
+    async fn reconcile(&self, updated_after: Option<NaiveDateTime>, limit: i64) -> Result<Vec<ReconcileEntry>>;
+}
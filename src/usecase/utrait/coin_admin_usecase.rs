@@ -0,0 +1,32 @@
+use axum::async_trait;
+use uuid::Uuid;
+use crate::domain::model::audit_log::AuditActor;
+use crate::domain::model::coin::{Coin, NewCoinPayload, UpdateCoinPayload};
+use crate::domain::model::coin_network::{CoinNetwork, FtMetadataCheckResponse, NewCoinNetworkPayload, UpdateCoinNetworkPayload};
+use crate::domain::model::network::{Network, NewNetworkPayload, UpdateNetworkPayload};
+use crate::usecase::error::Result;
+
+/// Admin management of coins, networks and the coin networks payouts are made in.
+#[async_trait]
+pub trait CoinAdminUsecase {
+    async fn create_coin(&self, payload: NewCoinPayload, actor: AuditActor) -> Result<Coin>;
+    /// New decimals or symbol are checked against the `ft_metadata` of every FT coin network of the coin.
+    async fn update_coin(&self, coin_id: Uuid, payload: UpdateCoinPayload, actor: AuditActor) -> Result<Coin>;
+    async fn set_coin_active(&self, coin_id: Uuid, is_active: bool, actor: AuditActor) -> Result<Coin>;
+
+    async fn create_network(&self, payload: NewNetworkPayload, actor: AuditActor) -> Result<Network>;
+    async fn update_network(&self, network_id: Uuid, payload: UpdateNetworkPayload, actor: AuditActor) -> Result<Network>;
+    async fn set_network_active(&self, network_id: Uuid, is_active: bool, actor: AuditActor) -> Result<Network>;
+
+    /// FT contracts on NEAR are checked against their `ft_metadata`, an rpc failure rejects the change.
+    async fn create_coin_network(&self, payload: NewCoinNetworkPayload, actor: AuditActor) -> Result<(CoinNetwork, Coin, Network)>;
+    async fn update_coin_network(&self, coin_network_id: Uuid, payload: UpdateCoinNetworkPayload, actor: AuditActor) -> Result<(CoinNetwork, Coin, Network)>;
+    async fn set_coin_network_active(&self, coin_network_id: Uuid, is_active: bool, actor: AuditActor) -> Result<(CoinNetwork, Coin, Network)>;
+    async fn deprecate_coin_network(&self, coin_network_id: Uuid, actor: AuditActor) -> Result<(CoinNetwork, Coin, Network)>;
+
+    /// Compares every active FT coin network with its contract `ft_metadata` and flags mismatches.
+    async fn check_ft_metadata(&self, actor: AuditActor) -> Result<Vec<FtMetadataCheckResponse>>;
+    /// Copies `decimals` and `symbol` from the contract `ft_metadata` into the coin and clears the mismatch,
+    /// then checks the other FT coin networks of the coin against the new values.
+    async fn sync_ft_metadata(&self, coin_network_id: Uuid, actor: AuditActor) -> Result<(CoinNetwork, Coin, Network)>;
+}
@@ -0,0 +1,10 @@
+use axum::async_trait;
+use crate::domain::model::health::ReadinessReport;
+
+/// Dependency checks behind `/health/ready`.
+#[async_trait]
+pub trait HealthUsecase {
+    /// Checks the db, the rpc, the signer keys and the treasury balance concurrently,
+    /// each bounded by `[health] check_timeout_ms`.
+    async fn readiness(&self) -> ReadinessReport;
+}
@@ -0,0 +1,20 @@
+use axum::async_trait;
+use crate::domain::model::audit_log::AuditActor;
+use crate::domain::model::near::{AddSignerKeysPayload, RemoveSignerKeysPayload, SignerKey, SignerKeyChanges};
+use crate::usecase::error::Result;
+
+/// Access keys the payouts are signed with, swappable without a restart.
+#[async_trait]
+pub trait SignerKeyUsecase {
+    fn list_keys(&self) -> Vec<SignerKey>;
+    /// Reads the keys file again and swaps the signer when its key set changed.
+    /// Every added and removed key is written to the audit log before the swap.
+    async fn reload_keys(&self, actor: AuditActor) -> Result<SignerKeyChanges>;
+    /// Generates ed25519 keys, adds them on chain with one `AddKey` batch signed by a full-access key,
+    /// then writes them to the keys file and swaps the signer.
+    async fn add_keys(&self, payload: AddSignerKeysPayload, actor: AuditActor) -> Result<SignerKeyChanges>;
+    /// Takes keys out of the keys file and the signer, waits for the transactions still signed with them,
+    /// then deletes them on chain with one `DeleteKey` batch. `SignerKeysInFlight` when the wait times out,
+    /// the keys are then out of the signer but still on the account, and removing them again deletes them.
+    async fn remove_keys(&self, payload: RemoveSignerKeysPayload, actor: AuditActor) -> Result<SignerKeyChanges>;
+}
@@ -0,0 +1,11 @@
+use axum::async_trait;
+use uuid::Uuid;
+use crate::domain::model::{audit_log::AuditActor, webhook_delivery::WebhookDelivery};
+use crate::usecase::error::Result;
+
+#[async_trait]
+pub trait WebhookUsecase {
+    async fn list_deliveries(&self, event_id: Option<Uuid>, limit: i64) -> Result<Vec<WebhookDelivery>>;
+    async fn redeliver(&self, delivery_id: Uuid, actor: AuditActor) -> Result<WebhookDelivery>;
+    async fn requeue_event(&self, event_id: Uuid, actor: AuditActor) -> Result<()>;
+}
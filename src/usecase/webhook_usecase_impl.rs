@@ -0,0 +1,104 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use uuid::Uuid;
+use crate::{
+    domain::model::{
+        audit_log::{AuditActor, NewAuditLog, ACTION_WEBHOOK_REDELIVER, ENTITY_WEBHOOK_DELIVERY},
+        outbox_event::OutboxEventMessage, webhook_delivery::WebhookDelivery
+    },
+    port::output::{audit_log_repository::AuditLogRepository, event_consumer::EventConsumer, outbox_event_repository::OutboxEventRepository, webhook_delivery_repository::WebhookDeliveryRepository, DbManager},
+};
+use super::error::{Error, Result};
+use super::outbox_dispatcher::deliver_and_record;
+use super::utrait::webhook_usecase::WebhookUsecase;
+
+pub struct WebhookUsecaseImpl<D: DbManager, O: OutboxEventRepository, W: WebhookDeliveryRepository, A: AuditLogRepository> {
+    db_manager: Arc<D>,
+    outbox_event_repo: Arc<O>,
+    webhook_delivery_repo: Arc<W>,
+    audit_log_repo: Arc<A>,
+    consumers: Vec<Arc<dyn EventConsumer>>,
+}
+
+impl<D, O, W, A> WebhookUsecaseImpl<D, O, W, A>
+where
+    D: DbManager + Send + Sync,
+    O: OutboxEventRepository + Send + Sync,
+    W: WebhookDeliveryRepository + Send + Sync,
+    A: AuditLogRepository + Send + Sync,
+{
+    pub fn new(db_manager: Arc<D>, outbox_event_repo: Arc<O>, webhook_delivery_repo: Arc<W>, audit_log_repo: Arc<A>, consumers: Vec<Arc<dyn EventConsumer>>) -> Self {
+        Self {
+            db_manager,
+            outbox_event_repo,
+            webhook_delivery_repo,
+            audit_log_repo,
+            consumers,
+        }
+    }
+}
+
+#[async_trait]
+impl<D, O, W, A> WebhookUsecase for WebhookUsecaseImpl<D, O, W, A>
+where
+    D: DbManager + Send + Sync,
+    O: OutboxEventRepository + Send + Sync,
+    W: WebhookDeliveryRepository + Send + Sync,
+    A: AuditLogRepository + Send + Sync,
+{
+    async fn list_deliveries(&self, event_id: Option<Uuid>, limit: i64) -> Result<Vec<WebhookDelivery>> {
+        let conn = self.db_manager.get_connection().await?;
+        let deliveries = match event_id {
+            Some(event_id) => self.webhook_delivery_repo.list_by_event(conn, event_id).await?,
+            None => self.webhook_delivery_repo.list_recent(conn, limit).await?,
+        };
+        Ok(deliveries)
+    }
+
+    /// Sends the event of a past delivery to the same endpoint right away.
+    /// The outbox event status is left untouched; the attempt is recorded as a new delivery row.
+    async fn redeliver(&self, delivery_id: Uuid, actor: AuditActor) -> Result<WebhookDelivery> {
+        let delivery = self.webhook_delivery_repo.get(self.db_manager.get_connection().await?, delivery_id).await.map_err(|_| {
+            tracing::error!("Webhook Delivery Not Found: {}", delivery_id);
+            Error::WebhookDeliveryNotFound
+        })?;
+
+        let consumer = self.consumers
+            .iter()
+            .find(|c| c.name() == delivery.endpoint_name)
+            .ok_or_else(|| Error::WebhookEndpointNotConfigured { endpoint_name: delivery.endpoint_name.clone() })?;
+
+        let event = self.outbox_event_repo.get(self.db_manager.get_connection().await?, delivery.event_id).await.map_err(|_| {
+            tracing::error!("Outbox Event Not Found: {}", delivery.event_id);
+            Error::OutboxEventNotFound
+        })?;
+
+        let event_id = event.id;
+        let attempt = event.attempts + 1;
+        let message = OutboxEventMessage::from(event);
+        let redelivery = deliver_and_record(
+            self.db_manager.as_ref(),
+            self.webhook_delivery_repo.as_ref(),
+            consumer.as_ref(),
+            event_id,
+            attempt,
+            &message,
+        ).await?;
+
+        self.audit_log_repo.insert(
+            self.db_manager.get_connection().await?,
+            NewAuditLog::new(&actor, ACTION_WEBHOOK_REDELIVER, ENTITY_WEBHOOK_DELIVERY, delivery_id, Some(&delivery), Some(&redelivery)),
+        ).await?;
+        Ok(redelivery)
+    }
+
+    /// Puts the event back to PENDING so the dispatcher delivers it to every consumer again.
+    async fn requeue_event(&self, event_id: Uuid, actor: AuditActor) -> Result<()> {
+        self.outbox_event_repo.get(self.db_manager.get_connection().await?, event_id).await.map_err(|_| {
+            tracing::error!("Outbox Event Not Found: {}", event_id);
+            Error::OutboxEventNotFound
+        })?;
+        self.outbox_event_repo.requeue(self.db_manager.get_connection().await?, event_id, actor).await?;
+        Ok(())
+    }
+}